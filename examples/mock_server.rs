@@ -0,0 +1,19 @@
+//! Runs the in-memory mock backend on the port the app expects.
+//!
+//! `cargo run --example mock_server`, then log in as `example@example.com`
+//! (admin) or `expert@example.com`. Run with `RUST_LOG=debug` to see every request.
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    env_logger::init();
+    let server = ranking_client::mock_server::MockServer::start(
+        "127.0.0.1:8000",
+        ranking_client::mock_server::State::example(),
+    )
+    .expect("failed to bind 127.0.0.1:8000");
+    println!("Mock ranking backend listening on {}", server.base_url());
+    server.join();
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
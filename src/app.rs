@@ -3,14 +3,32 @@ use std::any::Any;
 use egui::{Context, FontId, RichText};
 use poll_promise::Promise;
 
+mod accessibility;
+pub mod ahp;
+mod algorithm;
+pub mod api;
 mod data;
 mod download;
-mod login;
+mod experts;
+pub mod hierarchy;
+pub mod i18n;
+mod images;
+pub mod login;
+pub mod markdown;
+mod participation;
 mod rank;
 mod ranking_list;
+mod scale_editor;
+pub mod schedule;
+pub mod sensitivity;
+pub mod study;
+#[cfg(feature = "content-updates")]
+mod translations;
 mod view;
-mod schema;
+pub mod schema;
 
+use accessibility::accessibility::DisplaySettings;
+use i18n::i18n::{tr, Language};
 use login::login::*;
 use ranking_list::ranking_list::*;
 
@@ -26,6 +44,17 @@ pub struct TemplateApp {
 
     #[serde(skip)]
     login_form: LoginForm,
+
+    /// URL fragment we last saw in, or wrote to, the address bar
+    #[serde(skip)]
+    reported_hash: String,
+
+    #[serde(skip)]
+    image_loaders_installed: bool,
+
+    display: DisplaySettings,
+
+    language: Language,
 }
 
 impl Default for TemplateApp {
@@ -34,6 +63,10 @@ impl Default for TemplateApp {
             value: 2.7,
             data: data::Data::default(),
             login_form: LoginForm::default(),
+            reported_hash: String::new(),
+            image_loaders_installed: false,
+            display: DisplaySettings::default(),
+            language: Language::default(),
         }
     }
 }
@@ -52,19 +85,27 @@ impl TemplateApp {
 
         Default::default()
     }
-}
 
-impl eframe::App for TemplateApp {
-    /// Called by the frame work to save state before shutdown.
-    fn save(&mut self, storage: &mut dyn eframe::Storage) {
-        eframe::set_value(storage, eframe::APP_KEY, self);
+    /// An app talking to the backend at `base_url` instead of the default one.
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        let mut app = Self::default();
+        app.data.base_url = base_url.into();
+        app
     }
 
-    /// Called each time the UI needs repainting, which may be many times per second.
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    /// Draws the whole app. Split out of [`eframe::App::update`] so it can be
+    /// driven headlessly, without an [`eframe::Frame`].
+    pub fn show(&mut self, ctx: &egui::Context) {
         // Put your widgets into a `SidePanel`, `TopPanel`, `CentralPanel`, `Window` or `Area`.
         // For inspiration and more examples, go to https://emilk.github.io/egui
 
+        if !self.image_loaders_installed {
+            images::images::install(ctx);
+            self.image_loaders_installed = true;
+        }
+        self.display.apply(ctx);
+        i18n::i18n::set_language(self.language);
+
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             egui::menu::bar(ui, |ui| {
                 // #[cfg(not(target_arch = "wasm32"))] // no File->Quit on web pages!
@@ -78,15 +119,21 @@ impl eframe::App for TemplateApp {
                 // }
 
                 egui::widgets::global_dark_light_mode_buttons(ui);
+                self.display.show_controls(ui);
+                i18n::i18n::show_language_picker(ui, &mut self.language);
                 ui.add_space(15.0);
                 if let LoginStep::Finished(session) = &self.login_form.step {
                     if session.user_info.admin {
-                        ui.label("Logged in as admin: ");
+                        ui.label(tr("Logged in as admin: "));
                     } else {
-                        ui.label("Logged in as: ");
+                        ui.label(tr("Logged in as: "));
                     }
                 }
                 ui.monospace(&self.login_form.email);
+                if let LoginStep::Finished(session) = &self.login_form.step {
+                    ui.separator();
+                    self.data.show_navigation(ui, ctx, session);
+                }
             });
         });
 
@@ -104,4 +151,43 @@ impl eframe::App for TemplateApp {
             });
         });
     }
+
+    /// Keeps the current view and the URL fragment in sync: follows links the
+    /// user typed or navigated to with the browser, and shows where we are.
+    #[cfg(target_arch = "wasm32")]
+    fn sync_location(&mut self, ctx: &egui::Context, hash: &str) {
+        if hash != self.reported_hash {
+            self.reported_hash = hash.to_string();
+            if let Some(route) = view::Route::parse(hash) {
+                let session = match &self.login_form.step {
+                    LoginStep::Finished(session) => Some(session),
+                    _ => None,
+                };
+                self.data.follow_route(route, ctx, session);
+            }
+        }
+        if let Some(route) = self.data.route() {
+            let fragment = route.fragment();
+            if fragment != self.reported_hash {
+                if let Some(window) = eframe::web_sys::window() {
+                    let _ = window.location().set_hash(&fragment);
+                }
+                self.reported_hash = fragment;
+            }
+        }
+    }
+}
+
+impl eframe::App for TemplateApp {
+    /// Called by the frame work to save state before shutdown.
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, eframe::APP_KEY, self);
+    }
+
+    /// Called each time the UI needs repainting, which may be many times per second.
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        #[cfg(target_arch = "wasm32")]
+        self.sync_location(ctx, &_frame.info().web_info.location.hash);
+        self.show(ctx);
+    }
 }
@@ -0,0 +1,109 @@
+pub mod accessibility {
+    use egui::{Color32, Context, Response, Stroke, Ui, Visuals};
+
+    use crate::app::i18n::i18n::tr;
+
+    /// Tells screen readers more about a widget than its label does, e.g.
+    /// which row of a grid a "Delete" button belongs to.
+    pub fn describe(response: &Response, description: impl Into<String>) {
+        let description = description.into();
+        response.ctx.accesskit_node_builder(response.id, |builder| {
+            builder.set_description(description);
+        });
+    }
+
+    /// Contrast and text size chosen in the top bar, kept between sessions.
+    #[derive(serde::Deserialize, serde::Serialize)]
+    #[serde(default)]
+    pub struct DisplaySettings {
+        pub high_contrast: bool,
+        /// Multiplies the size of all text
+        pub text_scale: f32,
+    }
+
+    impl Default for DisplaySettings {
+        fn default() -> Self {
+            Self {
+                high_contrast: false,
+                text_scale: 1.0,
+            }
+        }
+    }
+
+    impl DisplaySettings {
+        pub const TEXT_SCALES: std::ops::RangeInclusive<f32> = 0.75..=2.0;
+
+        /// Updates the style of `ctx` to the settings, keeping its dark or light mode.
+        pub fn apply(&self, ctx: &Context) {
+            let style = ctx.style();
+            let visuals = match (self.high_contrast, style.visuals.dark_mode) {
+                (true, dark_mode) => high_contrast(dark_mode),
+                (false, true) => Visuals::dark(),
+                (false, false) => Visuals::light(),
+            };
+            let mut text_styles = egui::style::default_text_styles();
+            for font in text_styles.values_mut() {
+                font.size *= self.text_scale;
+            }
+            if style.visuals != visuals || style.text_styles != text_styles {
+                let mut style = (*style).clone();
+                style.visuals = visuals;
+                style.text_styles = text_styles;
+                ctx.set_style(style);
+            }
+        }
+
+        pub fn show_controls(&mut self, ui: &mut Ui) {
+            ui.checkbox(&mut self.high_contrast, tr("High contrast"))
+                .on_hover_text(tr("Black and white colors with strong outlines"));
+            ui.add(
+                egui::Slider::new(&mut self.text_scale, Self::TEXT_SCALES)
+                    .step_by(0.25)
+                    .text(tr("Text size")),
+            );
+        }
+    }
+
+    /// Pure black and white with bright outlines around every widget.
+    pub fn high_contrast(dark_mode: bool) -> Visuals {
+        let (mut visuals, background, text, accent) = match dark_mode {
+            true => (
+                Visuals::dark(),
+                Color32::BLACK,
+                Color32::WHITE,
+                Color32::YELLOW,
+            ),
+            false => (
+                Visuals::light(),
+                Color32::WHITE,
+                Color32::BLACK,
+                Color32::from_rgb(0, 0, 160),
+            ),
+        };
+        visuals.panel_fill = background;
+        visuals.window_fill = background;
+        visuals.extreme_bg_color = background;
+        visuals.faint_bg_color = background;
+        visuals.window_stroke = Stroke::new(2.0, text);
+        visuals.hyperlink_color = accent;
+        visuals.selection.bg_fill = accent;
+        visuals.selection.stroke = Stroke::new(2.0, background);
+        let widgets = &mut visuals.widgets;
+        for widget in [
+            &mut widgets.noninteractive,
+            &mut widgets.inactive,
+            &mut widgets.hovered,
+            &mut widgets.active,
+            &mut widgets.open,
+        ] {
+            widget.bg_fill = background;
+            widget.weak_bg_fill = background;
+            widget.bg_stroke = Stroke::new(1.5, text);
+            widget.fg_stroke = Stroke::new(1.5, text);
+        }
+        widgets.noninteractive.bg_stroke = Stroke::new(1.0, text);
+        widgets.hovered.bg_stroke = Stroke::new(2.5, accent);
+        widgets.active.bg_stroke = Stroke::new(3.0, accent);
+        visuals
+    }
+}
@@ -0,0 +1,158 @@
+pub mod ahp {
+    /// Saaty's scale: how strongly one item is preferred over the other.
+    pub const INTENSITIES: [(f64, &str); 5] = [
+        (1.0, "Equally"),
+        (3.0, "Moderately"),
+        (5.0, "Strongly"),
+        (7.0, "Very strongly"),
+        (9.0, "Extremely"),
+    ];
+
+    /// A ready-made scale for rating criteria, entries from least to most important.
+    pub struct ScalePreset {
+        pub name: &'static str,
+        pub entries: &'static [(&'static str, f32)],
+    }
+
+    /// Labels of Saaty's 1–9 scale, shared by the presets using all nine steps.
+    const NINE_STEPS: [&str; 9] = [
+        "Equally important",
+        "Equally to moderately more important",
+        "Moderately more important",
+        "Moderately to strongly more important",
+        "Strongly more important",
+        "Strongly to very strongly more important",
+        "Very strongly more important",
+        "Very strongly to extremely more important",
+        "Extremely more important",
+    ];
+
+    pub const SCALE_PRESETS: [ScalePreset; 3] = [
+        ScalePreset {
+            name: "Saaty 1–9",
+            entries: &[
+                (NINE_STEPS[0], 1.0),
+                (NINE_STEPS[1], 2.0),
+                (NINE_STEPS[2], 3.0),
+                (NINE_STEPS[3], 4.0),
+                (NINE_STEPS[4], 5.0),
+                (NINE_STEPS[5], 6.0),
+                (NINE_STEPS[6], 7.0),
+                (NINE_STEPS[7], 8.0),
+                (NINE_STEPS[8], 9.0),
+            ],
+        },
+        ScalePreset {
+            name: "Likert 1–5",
+            entries: &[
+                ("Not important", 1.0),
+                ("Slightly important", 2.0),
+                ("Moderately important", 3.0),
+                ("Important", 4.0),
+                ("Very important", 5.0),
+            ],
+        },
+        // Salo and Hämäläinen: w / (1 - w) for w = 0.5, 0.55, ..., 0.9, so the
+        // steps are even in the weights they give rather than in the ratios
+        ScalePreset {
+            name: "Balanced",
+            entries: &[
+                (NINE_STEPS[0], 1.0),
+                (NINE_STEPS[1], 1.22),
+                (NINE_STEPS[2], 1.5),
+                (NINE_STEPS[3], 1.86),
+                (NINE_STEPS[4], 2.33),
+                (NINE_STEPS[5], 3.0),
+                (NINE_STEPS[6], 4.0),
+                (NINE_STEPS[7], 5.67),
+                (NINE_STEPS[8], 9.0),
+            ],
+        },
+    ];
+
+    /// Random consistency index by matrix size, from Saaty.
+    const RANDOM_INDEX: [f64; 11] = [0.0, 0.0, 0.0, 0.58, 0.9, 1.12, 1.24, 1.32, 1.41, 1.45, 1.49];
+
+    /// Above this consistency ratio the answers are usually considered too contradictory.
+    pub const ACCEPTABLE_CONSISTENCY: f64 = 0.1;
+
+    /// Item `preferred` is `intensity` times as important as item `other`.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Judgement {
+        pub preferred: usize,
+        pub other: usize,
+        pub intensity: f64,
+    }
+
+    /// Reciprocal comparison matrix of `n` items. Pairs without a judgement count as equal.
+    pub fn matrix(n: usize, judgements: &[Judgement]) -> Vec<Vec<f64>> {
+        let mut matrix = vec![vec![1.0; n]; n];
+        for judgement in judgements {
+            matrix[judgement.preferred][judgement.other] = judgement.intensity;
+            matrix[judgement.other][judgement.preferred] = 1.0 / judgement.intensity;
+        }
+        matrix
+    }
+
+    /// Weights summing to 1, the normalized geometric means of the matrix rows.
+    pub fn geometric_mean_weights(matrix: &[Vec<f64>]) -> Vec<f64> {
+        let n = matrix.len() as f64;
+        let means: Vec<f64> = matrix
+            .iter()
+            .map(|row| row.iter().product::<f64>().powf(1.0 / n))
+            .collect();
+        let sum: f64 = means.iter().sum();
+        means.iter().map(|mean| mean / sum).collect()
+    }
+
+    /// Saaty's consistency ratio of `matrix` given its `weights`, 0 when perfectly consistent.
+    pub fn consistency_ratio(matrix: &[Vec<f64>], weights: &[f64]) -> f64 {
+        let n = matrix.len();
+        if n < 3 {
+            return 0.0;
+        }
+        let lambda_max = matrix
+            .iter()
+            .zip(weights)
+            .map(|(row, weight)| {
+                let weighted: f64 = row.iter().zip(weights).map(|(a, w)| a * w).sum();
+                weighted / weight
+            })
+            .sum::<f64>()
+            / n as f64;
+        let consistency_index = (lambda_max - n as f64) / (n as f64 - 1.0);
+        let random_index = RANDOM_INDEX[n.min(RANDOM_INDEX.len() - 1)];
+        (consistency_index / random_index).max(0.0)
+    }
+
+    /// Weights of `n` items compared only within their `groups`, each group's
+    /// weights summing to 1, and the worst consistency ratio of the groups.
+    pub fn grouped_weights(
+        n: usize,
+        groups: &[Vec<usize>],
+        judgements: &[Judgement],
+    ) -> (Vec<f64>, f64) {
+        let mut weights = vec![1.0; n];
+        let mut worst_consistency: f64 = 0.0;
+        for group in groups {
+            let local = |item: usize| group.iter().position(|&i| i == item);
+            let in_group: Vec<Judgement> = judgements
+                .iter()
+                .filter_map(|j| {
+                    Some(Judgement {
+                        preferred: local(j.preferred)?,
+                        other: local(j.other)?,
+                        intensity: j.intensity,
+                    })
+                })
+                .collect();
+            let matrix = matrix(group.len(), &in_group);
+            let group_weights = geometric_mean_weights(&matrix);
+            worst_consistency = worst_consistency.max(consistency_ratio(&matrix, &group_weights));
+            for (&item, weight) in group.iter().zip(group_weights) {
+                weights[item] = weight;
+            }
+        }
+        (weights, worst_consistency)
+    }
+}
@@ -0,0 +1,224 @@
+pub mod algorithm {
+    use egui::{Color32, RichText, Ui};
+    use ehttp::Request;
+
+    use crate::app::{
+        download::download::Download,
+        i18n::i18n::{tr, tr_args, tr_plural},
+        login::login::Session,
+        ranking_list::ranking_list::DownloadResults,
+        schema::schema::{Participation, ParticipationStatus, Variables},
+        view::Navigation,
+    };
+
+    /// What the ranking looks like, as far as running the algorithm is concerned.
+    /// `None` means still downloading.
+    pub struct Readiness<'a> {
+        pub variables: Option<&'a Variables>,
+        pub alternatives: Option<usize>,
+        pub criteria: Option<usize>,
+        /// Criteria without sub-criteria, the ones alternatives are compared on
+        pub leaf_criteria: Option<usize>,
+        pub scales: Option<usize>,
+        pub participation: Option<&'a [Participation]>,
+    }
+
+    impl Readiness<'_> {
+        /// Reasons the algorithm can't be run yet, empty when it can.
+        pub fn problems(&self) -> Vec<String> {
+            let (Some(alternatives), Some(criteria), Some(leaf_criteria), Some(scales)) = (
+                self.alternatives,
+                self.criteria,
+                self.leaf_criteria,
+                self.scales,
+            )
+            else {
+                return vec![tr("Still downloading the ranking")];
+            };
+            let mut problems = Vec::new();
+            match self.variables {
+                Some(variables) if variables.exists_in_ranking => {
+                    problems.extend(variables.warnings());
+                }
+                _ => problems.push(tr("The ranking has no variables yet")),
+            }
+            if alternatives < 3 {
+                problems.push(tr_args(
+                    "Needs at least 3 alternatives, has {count}",
+                    &[("count", &alternatives)],
+                ));
+            }
+            if criteria < 2 {
+                problems.push(tr_args(
+                    "Needs at least 2 criteria, has {count}",
+                    &[("count", &criteria)],
+                ));
+            }
+            if scales < 2 {
+                problems.push(tr_args(
+                    "Needs at least 2 scale entries, has {count}",
+                    &[("count", &scales)],
+                ));
+            }
+            if let Some(variables) = self.variables.filter(|v| v.completness_required) {
+                match self.participation {
+                    Some(participation) => {
+                        let unfinished = participation
+                            .iter()
+                            .filter(|p| {
+                                let status = p.status_in(
+                                    &variables.design,
+                                    alternatives,
+                                    leaf_criteria,
+                                    criteria,
+                                );
+                                status != ParticipationStatus::Complete
+                            })
+                            .count();
+                        if unfinished > 0 {
+                            problems.push(tr_plural(
+                                "Completeness is required, but {count} expert has not answered everything",
+                                "Completeness is required, but {count} experts have not answered everything",
+                                unfinished as u64,
+                                &[],
+                            ));
+                        }
+                    }
+                    None => problems.push(tr("Still downloading participation")),
+                }
+            }
+            problems
+        }
+    }
+
+    enum RunState {
+        Idle,
+        Confirming,
+        /// The server answers the trigger once the results are computed
+        Running,
+        Finished,
+        Failed(String),
+    }
+
+    /// "Trigger algorithm" button that checks the ranking first, asks for
+    /// confirmation and then waits for the results.
+    pub struct AlgorithmRunner {
+        ranking_id: i64,
+        state: RunState,
+        download_trigger: Download,
+    }
+
+    impl AlgorithmRunner {
+        pub fn new(ranking_id: i64) -> Self {
+            Self {
+                ranking_id,
+                state: RunState::Idle,
+                download_trigger: Download::default(),
+            }
+        }
+
+        pub fn show(
+            &mut self,
+            ui: &mut Ui,
+            ctx: &egui::Context,
+            base_url: &String,
+            session: &Session,
+            readiness: &Readiness,
+        ) -> Option<Navigation> {
+            let mut ret: Option<Navigation> = None;
+            let problems = readiness.problems();
+            let running = matches!(self.state, RunState::Running);
+
+            ui.horizontal(|ui| {
+                let enabled = problems.is_empty() && !running;
+                if ui
+                    .add_enabled(enabled, egui::Button::new(tr("Trigger algorithm")))
+                    .clicked()
+                {
+                    self.state = RunState::Confirming;
+                }
+                match &self.state {
+                    RunState::Running => {
+                        ui.spinner();
+                        ui.label(tr("Running..."));
+                    }
+                    RunState::Finished => {
+                        ui.label(RichText::new(tr("Finished")).color(Color32::GREEN));
+                        if ui.button(tr("Show results")).clicked() {
+                            ret = Some(Navigation::Push(Box::new(DownloadResults::new(
+                                self.ranking_id,
+                            ))));
+                        }
+                    }
+                    RunState::Failed(error) => {
+                        ui.label(RichText::new(error).color(Color32::RED));
+                    }
+                    RunState::Idle | RunState::Confirming => {}
+                }
+            });
+            for problem in &problems {
+                ui.label(RichText::new(problem).color(Color32::YELLOW));
+            }
+
+            if matches!(self.state, RunState::Confirming) {
+                self.show_confirmation(ctx, base_url, session);
+            }
+            self.receive(ui);
+            ret
+        }
+
+        fn show_confirmation(&mut self, ctx: &egui::Context, base_url: &String, session: &Session) {
+            egui::Window::new(tr("Run the algorithm?"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+                .show(ctx, |ui| {
+                    ui.label(tr("This replaces any results computed before."));
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("Run")).clicked() {
+                            let url = format!("{}/trigger_algorithm/{}", base_url, self.ranking_id);
+                            let request = Request::post(url, [].to_vec());
+                            self.download_trigger.download(
+                                ctx,
+                                session.access_token.add_authorization_header(request),
+                            );
+                            self.state = RunState::Running;
+                        }
+                        if ui.button(tr("Cancel")).clicked() {
+                            self.state = RunState::Idle;
+                        }
+                    });
+                });
+        }
+
+        fn receive(&mut self, ui: &mut Ui) {
+            if !matches!(self.state, RunState::Running) {
+                return;
+            }
+            let mut next_state = None;
+            self.download_trigger
+                .run_when_downloaded(ui, |response, _| {
+                    next_state = Some(match response.ok {
+                        true => RunState::Finished,
+                        false => RunState::Failed(tr_args(
+                            "Server responded with {status} {text}",
+                            &[
+                                ("status", &response.status),
+                                ("text", &response.status_text),
+                            ],
+                        )),
+                    });
+                });
+            // Not reaching the server at all fails the run too
+            if let Some(Some(Err(error))) =
+                self.download_trigger.promise.as_ref().map(|p| p.ready())
+            {
+                next_state = Some(RunState::Failed(error.clone()));
+            }
+            if let Some(state) = next_state {
+                self.download_trigger.promise = None;
+                self.state = state;
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+pub mod api {
+    //! Requests understood by the ranking backend.
+    //!
+    //! The egui views send these through [`Download`](crate::app::download::download::Download),
+    //! native tools can use the blocking [`Client`] instead.
+
+    use ehttp::Request;
+    use serde::Serialize;
+
+    use crate::app::login::login::AccessToken;
+
+    /// `POST /token`, exchanging the expert's email for an access token.
+    pub fn token(base_url: &str, email: &str) -> Request {
+        let mut request = Request::post(
+            format!("{}/token", base_url),
+            format!(
+                "grant_type=password&username={}&password=abc",
+                percent_encode(email)
+            )
+            .into_bytes(),
+        );
+        request.headers.insert(
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string(),
+        );
+        request
+    }
+
+    /// Escapes everything but unreserved characters, for query strings and forms.
+    pub fn percent_encode(text: &str) -> String {
+        let mut encoded = String::new();
+        for byte in text.bytes() {
+            match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                    encoded.push(byte as char)
+                }
+                _ => encoded.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        encoded
+    }
+
+    pub fn get(url: String, token: &AccessToken) -> Request {
+        token.add_authorization_header(Request::get(url))
+    }
+
+    pub fn delete(url: String, token: &AccessToken) -> Request {
+        let mut request = Request::post(url, Vec::new());
+        request.method = "DELETE".to_string();
+        token.add_authorization_header(request)
+    }
+
+    pub fn post_json<T>(url: String, value: &T, token: &AccessToken) -> Result<Request, String>
+    where
+        T: Serialize,
+    {
+        let json = serde_json::to_vec(value).map_err(|error| error.to_string())?;
+        let mut request = Request::post(url, json);
+        request
+            .headers
+            .insert("Content-Type".to_string(), "application/json".to_string());
+        Ok(token.add_authorization_header(request))
+    }
+
+    pub fn put_json<T>(url: String, value: &T, token: &AccessToken) -> Result<Request, String>
+    where
+        T: Serialize,
+    {
+        let mut request = post_json(url, value, token)?;
+        request.method = "PUT".to_string();
+        Ok(request)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use blocking::Client;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    mod blocking {
+        use serde::{de::DeserializeOwned, Serialize};
+
+        use crate::app::{login::login::AccessToken, schema::schema::Expert};
+
+        /// Blocking client for native tools and integration tests.
+        pub struct Client {
+            base_url: String,
+            token: Option<AccessToken>,
+        }
+
+        impl Client {
+            pub fn new(base_url: impl Into<String>) -> Self {
+                Self {
+                    base_url: base_url.into(),
+                    token: None,
+                }
+            }
+
+            pub fn with_token(base_url: impl Into<String>, token: AccessToken) -> Self {
+                Self {
+                    base_url: base_url.into(),
+                    token: Some(token),
+                }
+            }
+
+            pub fn base_url(&self) -> &str {
+                &self.base_url
+            }
+
+            pub fn token(&self) -> Option<&AccessToken> {
+                self.token.as_ref()
+            }
+
+            /// Logs in and returns the user info from `/me`.
+            pub fn login(&mut self, email: &str) -> Result<Expert, String> {
+                let response = send(&super::token(&self.base_url, email))?;
+                let token = parse::<AccessToken>(&response)?;
+                self.token = Some(token);
+                self.get("/me")
+            }
+
+            pub fn get<T>(&self, path: &str) -> Result<T, String>
+            where
+                T: DeserializeOwned,
+            {
+                let request = super::get(self.url(path), self.access_token()?);
+                parse(&send(&request)?)
+            }
+
+            /// Like `get`, but `None` when the server has nothing at `path` (404).
+            pub fn get_optional<T>(&self, path: &str) -> Result<Option<T>, String>
+            where
+                T: DeserializeOwned,
+            {
+                let request = super::get(self.url(path), self.access_token()?);
+                let response = ehttp::fetch_blocking(&request)?;
+                if response.status == 404 {
+                    return Ok(None);
+                }
+                parse(&check(response)?).map(Some)
+            }
+
+            /// Posts `value` and returns the raw response body.
+            pub fn post<T>(&self, path: &str, value: &T) -> Result<String, String>
+            where
+                T: Serialize,
+            {
+                let request = super::post_json(self.url(path), value, self.access_token()?)?;
+                Ok(body(&send(&request)?))
+            }
+
+            pub fn put<T>(&self, path: &str, value: &T) -> Result<(), String>
+            where
+                T: Serialize,
+            {
+                let request = super::put_json(self.url(path), value, self.access_token()?)?;
+                send(&request).map(|_| ())
+            }
+
+            pub fn delete(&self, path: &str) -> Result<(), String> {
+                let request = super::delete(self.url(path), self.access_token()?);
+                send(&request).map(|_| ())
+            }
+
+            fn url(&self, path: &str) -> String {
+                format!("{}{}", self.base_url, path)
+            }
+
+            fn access_token(&self) -> Result<&AccessToken, String> {
+                self.token
+                    .as_ref()
+                    .ok_or_else(|| "Not logged in".to_string())
+            }
+        }
+
+        fn send(request: &ehttp::Request) -> Result<ehttp::Response, String> {
+            check(ehttp::fetch_blocking(request)?)
+        }
+
+        /// The response if it is a success, the error the server gave otherwise.
+        fn check(response: ehttp::Response) -> Result<ehttp::Response, String> {
+            match response.ok {
+                true => Ok(response),
+                false => Err(format!(
+                    "Server responded with {} {}: {}",
+                    response.status,
+                    response.status_text,
+                    body(&response)
+                )),
+            }
+        }
+
+        fn body(response: &ehttp::Response) -> String {
+            response.text().unwrap_or_default().to_string()
+        }
+
+        fn parse<T>(response: &ehttp::Response) -> Result<T, String>
+        where
+            T: DeserializeOwned,
+        {
+            serde_json::from_slice(&response.bytes).map_err(|error| {
+                format!(
+                    "Failed to parse {} response due to: {}\nJson: {}",
+                    std::any::type_name::<T>(),
+                    error,
+                    body(response)
+                )
+            })
+        }
+    }
+}
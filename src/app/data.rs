@@ -1,12 +1,12 @@
-use egui::{Context, Ui};
-use poll_promise::Promise;
+use egui::Ui;
 
-use crate::app::view::View;
-use json_minimal::{self, Json};
-use std::any::Any;
+#[cfg(target_arch = "wasm32")]
+use crate::app::view::Route;
+use crate::app::view::{Navigation, View};
 
 use super::{
     download::download::Download,
+    i18n::i18n::tr,
     login::login::{LoginForm, Session},
     ranking_list::ranking_list::RankingList,
 };
@@ -15,6 +15,10 @@ pub struct Data {
     pub base_url: String,
     download: Download,
     current_view: Box<dyn View>,
+    /// Views to go Back to, most recent last
+    history: Vec<Box<dyn View>>,
+    /// Views to go Forward to, most recent last
+    forward: Vec<Box<dyn View>>,
     parsed: bool,
 }
 
@@ -24,27 +28,85 @@ impl Data {
             base_url: "http://127.0.0.1:8000".to_string(),
             download: Download::default(),
             current_view: Box::new(RankingList::default()),
+            history: Vec::new(),
+            forward: Vec::new(),
             parsed: false,
         }
     }
 
+    /// Location of the current view, for deep links. Only the web build has
+    /// an address bar to follow and show them in.
+    #[cfg(target_arch = "wasm32")]
+    pub fn route(&self) -> Option<Route> {
+        self.current_view.route()
+    }
+
+    /// Goes back to a view matching `route` if there is one in the history,
+    /// otherwise starts over from the ranking list and opens `route` from there.
+    #[cfg(target_arch = "wasm32")]
+    pub fn follow_route(&mut self, route: Route, ctx: &egui::Context, session: Option<&Session>) {
+        if self.current_view.route() == Some(route.clone()) {
+            return;
+        }
+        let position = self
+            .history
+            .iter()
+            .rposition(|view| view.route() == Some(route.clone()));
+        match (position, session) {
+            (Some(position), Some(session)) => {
+                self.go_back(self.history.len() - position, ctx, session)
+            }
+            _ => {
+                self.history.clear();
+                self.forward.clear();
+                self.current_view = Box::new(RankingList::with_route(route));
+                self.reload();
+            }
+        }
+    }
+
+    /// Back/Forward buttons and breadcrumbs of the views leading to the current one.
+    pub fn show_navigation(&mut self, ui: &mut Ui, ctx: &egui::Context, session: &Session) {
+        if ui
+            .add_enabled(!self.history.is_empty(), egui::Button::new("⬅"))
+            .on_hover_text(tr("Back"))
+            .clicked()
+        {
+            self.go_back(1, ctx, session);
+        }
+        if ui
+            .add_enabled(!self.forward.is_empty(), egui::Button::new("➡"))
+            .on_hover_text(tr("Forward"))
+            .clicked()
+        {
+            self.go_forward(ctx, session);
+        }
+
+        let mut back_to = None;
+        for (i, view) in self.history.iter().enumerate() {
+            if ui.link(view.title()).clicked() {
+                back_to = Some(self.history.len() - i);
+            }
+            ui.label(">");
+        }
+        ui.strong(self.current_view.title());
+        if let Some(steps) = back_to {
+            self.go_back(steps, ctx, session);
+        }
+    }
+
     pub fn show(&mut self, ui: &mut Ui, ctx: &egui::Context, login: &LoginForm, session: &Session) {
         ui.vertical_centered(|ui| {
-            if ui.button("Reload").clicked() {
-                self.download.promise = None;
-                self.parsed = false;
+            if ui.button(tr("Reload")).clicked() {
+                self.reload();
+                self.current_view.refresh(ctx, &self.base_url, session);
             }
         });
 
         if self.parsed {
-            match self.current_view.show(ui, ctx, &self.base_url, session) {
-                Some(view) => {
-                    self.current_view = view;
-                    self.parsed = false;
-                    self.download.promise = None;
-                }
-                None => {}
-            };
+            if let Some(navigation) = self.current_view.show(ui, ctx, &self.base_url, session) {
+                self.navigate(navigation, ctx, session);
+            }
         }
 
         let request = self.current_view.get_request(&self.base_url, session);
@@ -78,4 +140,49 @@ impl Data {
             }
         }
     }
+
+    fn navigate(&mut self, navigation: Navigation, ctx: &egui::Context, session: &Session) {
+        match navigation {
+            Navigation::Push(view) => {
+                let previous = std::mem::replace(&mut self.current_view, view);
+                self.history.push(previous);
+                self.forward.clear();
+                self.reload();
+            }
+            Navigation::Replace(view) => {
+                self.current_view = view;
+                self.forward.clear();
+                self.reload();
+            }
+            Navigation::Back => self.go_back(1, ctx, session),
+        }
+    }
+
+    fn go_back(&mut self, steps: usize, ctx: &egui::Context, session: &Session) {
+        for _ in 0..steps {
+            match self.history.pop() {
+                Some(previous) => {
+                    let current = std::mem::replace(&mut self.current_view, previous);
+                    self.forward.push(current);
+                }
+                None => break,
+            }
+        }
+        self.reload();
+        self.current_view.refresh(ctx, &self.base_url, session);
+    }
+
+    fn go_forward(&mut self, ctx: &egui::Context, session: &Session) {
+        if let Some(next) = self.forward.pop() {
+            let current = std::mem::replace(&mut self.current_view, next);
+            self.history.push(current);
+            self.reload();
+            self.current_view.refresh(ctx, &self.base_url, session);
+        }
+    }
+
+    fn reload(&mut self) {
+        self.parsed = false;
+        self.download.promise = None;
+    }
 }
@@ -4,6 +4,7 @@ pub mod download {
     use poll_promise::Promise;
     use serde::Deserialize;
 
+    use crate::app::api::api;
     use crate::app::login::login::{LoginForm, Session};
 
     pub struct Download {
@@ -47,33 +48,17 @@ pub mod download {
         where
             T: serde::Serialize,
         {
-            let json = serde_json::to_vec(value);
-            match json {
-                Ok(json) => {
-                    let mut request = Request::post(url, json);
-                    request
-                        .headers
-                        .insert("Content-Type".to_string(), "application/json".to_string());
-                    self.download(ctx, session.access_token.add_authorization_header(request));
-                    Result::Ok(true)
-                }
-                Err(error) => {
-                    // self.error = error.to_string();
-                    Result::Err(error.to_string())
-                }
-            }
+            let request = api::post_json(url, value, &session.access_token)?;
+            self.download(ctx, request);
+            Result::Ok(true)
         }
 
         pub fn delete_schema(&mut self, url: String, ctx: &Context, session: &Session) {
-            let mut request = Request::post(url, "".to_string().into_bytes());
-            request.method = "DELETE".to_string();
-            self.download(ctx, session.access_token.add_authorization_header(request));
+            self.download(ctx, api::delete(url, &session.access_token));
         }
 
         pub fn get_schema(&mut self, url: String, ctx: &Context, session: &Session){
-            let mut request = Request::get(url);
-            self.download(ctx, session.access_token.add_authorization_header(request));
-
+            self.download(ctx, api::get(url, &session.access_token));
         }
 
         pub fn deserialize_when_got<T>(&self, ui: &mut egui::Ui) -> Option<T>
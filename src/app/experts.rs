@@ -0,0 +1,386 @@
+pub mod experts {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use egui::{Color32, RichText, Ui};
+    use ehttp::Request;
+
+    use crate::app::{
+        api::api::percent_encode,
+        download::download::Download,
+        i18n::i18n::{tr, tr_args},
+        login::login::Session,
+        schema::schema::{Expert, Ranking},
+        view::{Navigation, Route, View},
+    };
+
+    /// Invitation for `expert` to take part in `ranking`, with the link to it
+    /// in the web app served at `web_app_url`.
+    pub fn invitation(expert: &Expert, ranking: &Ranking, web_app_url: &str) -> String {
+        tr_args(
+            "Hello {name},\n\n\
+            You have been invited to take part in the ranking \"{ranking}\".\n\
+            Open {link} and log in with your e-mail address {email}, \
+            then answer the questions shown on the screen.\n",
+            &[
+                ("name", &expert.name),
+                ("ranking", &ranking.description),
+                ("link", &Route::Rank(ranking.ranking_id).link(web_app_url)),
+                ("email", &expert.email),
+            ],
+        )
+    }
+
+    /// `mailto:` link opening the invitation in the user's e-mail client.
+    pub fn mailto(expert: &Expert, ranking: &Ranking, web_app_url: &str) -> String {
+        format!(
+            "mailto:{}?subject={}&body={}",
+            expert.email,
+            percent_encode(&tr_args(
+                "Invitation to \"{ranking}\"",
+                &[("ranking", &ranking.description)]
+            )),
+            percent_encode(&invitation(expert, ranking, web_app_url))
+        )
+    }
+
+    /// Every expert and the rankings they are on, for assigning them to more
+    /// rankings and inviting them. Experts are told apart by their e-mail,
+    /// the same person may have another id on each ranking.
+    pub struct ExpertDirectory {
+        /// Everyone on any of the rankings, known once all of them are downloaded
+        experts: Option<Vec<Expert>>,
+        rankings: Option<Vec<Ranking>>,
+        /// Experts on each ranking
+        members: BTreeMap<i64, Vec<Expert>>,
+        search: String,
+        selected_experts: BTreeSet<String>,
+        selected_rankings: BTreeSet<i64>,
+        /// Where the invitations link to. The native app can't tell where the
+        /// web app is served, blank means next to the backend.
+        web_app_url: String,
+        requested: bool,
+        error: String,
+
+        download_rankings: Download,
+        download_members: Vec<(i64, Download)>,
+        download_assign: Vec<Download>,
+    }
+
+    impl ExpertDirectory {
+        pub fn new() -> Self {
+            Self {
+                experts: None,
+                rankings: None,
+                members: BTreeMap::new(),
+                search: String::new(),
+                selected_experts: BTreeSet::new(),
+                selected_rankings: BTreeSet::new(),
+                web_app_url: String::new(),
+                requested: false,
+                error: String::new(),
+                download_rankings: Download::default(),
+                download_members: Vec::new(),
+                download_assign: Vec::new(),
+            }
+        }
+
+        fn download_all(&mut self, ctx: &egui::Context, base_url: &String, session: &Session) {
+            self.experts = None;
+            self.rankings = None;
+            self.members.clear();
+            self.download_members.clear();
+            self.download_rankings
+                .get_schema(format!("{}/all_rankings", base_url), ctx, session);
+            self.requested = true;
+        }
+
+        fn download_members(&mut self, ctx: &egui::Context, base_url: &String, session: &Session) {
+            self.experts = None;
+            self.download_members.clear();
+            for ranking in self.rankings.iter().flatten() {
+                let mut download = Download::default();
+                download.get_schema(
+                    format!("{}/experts/{}", base_url, ranking.ranking_id),
+                    ctx,
+                    session,
+                );
+                self.download_members.push((ranking.ranking_id, download));
+            }
+        }
+
+        fn matches_search(&self, expert: &Expert) -> bool {
+            let search = self.search.to_lowercase();
+            expert.name.to_lowercase().contains(&search)
+                || expert.email.to_lowercase().contains(&search)
+        }
+
+        fn is_member(&self, ranking_id: i64, email: &str) -> bool {
+            self.members
+                .get(&ranking_id)
+                .map_or(false, |members| members.iter().any(|e| e.email == email))
+        }
+
+        fn rankings_of(&self, email: &str) -> Vec<&Ranking> {
+            self.rankings
+                .iter()
+                .flatten()
+                .filter(|ranking| self.is_member(ranking.ranking_id, email))
+                .collect()
+        }
+
+        /// Adds the selected experts to the selected rankings they aren't on
+        /// yet, creating an expert on a ranking assigns the one with that e-mail.
+        fn assign_selected(&mut self, ctx: &egui::Context, base_url: &String, session: &Session) {
+            let Some(experts) = &self.experts else {
+                return;
+            };
+            let mut downloads = Vec::new();
+            let mut error = None;
+            for ranking_id in &self.selected_rankings {
+                let experts = experts
+                    .iter()
+                    .filter(|e| self.selected_experts.contains(&e.email))
+                    .filter(|e| !self.is_member(*ranking_id, &e.email));
+                for expert in experts {
+                    let url = format!("{}/create_expert/{}", base_url, ranking_id);
+                    let mut download = Download::default();
+                    match download.post_schema(expert, url, ctx, session) {
+                        Ok(_) => downloads.push(download),
+                        Err(err) => error = Some(err),
+                    }
+                }
+            }
+            self.download_assign.extend(downloads);
+            if let Some(error) = error {
+                self.error = error;
+            }
+        }
+
+        fn show_experts(&mut self, ui: &mut Ui) {
+            let Some(experts) = &self.experts else {
+                return;
+            };
+            let mut toggled = Vec::new();
+            egui::Grid::new("Expert directory")
+                .striped(true)
+                .num_columns(3)
+                .show(ui, |ui| {
+                    ui.strong(tr("Name"));
+                    ui.strong(tr("E-mail"));
+                    ui.strong(tr("Rankings"));
+                    ui.end_row();
+                    for expert in experts.iter().filter(|e| self.matches_search(e)) {
+                        let mut selected = self.selected_experts.contains(&expert.email);
+                        if ui.checkbox(&mut selected, &expert.name).changed() {
+                            toggled.push(expert.email.clone());
+                        }
+                        ui.label(&expert.email);
+                        let rankings: Vec<&str> = self
+                            .rankings_of(&expert.email)
+                            .iter()
+                            .map(|ranking| ranking.description.as_str())
+                            .collect();
+                        match rankings.is_empty() {
+                            true => ui.weak(tr("None")),
+                            false => ui.label(rankings.join(", ")),
+                        };
+                        ui.end_row();
+                    }
+                });
+            for email in toggled {
+                if !self.selected_experts.remove(&email) {
+                    self.selected_experts.insert(email);
+                }
+            }
+        }
+
+        fn show_rankings(&mut self, ui: &mut Ui) {
+            let Some(rankings) = &self.rankings else {
+                return;
+            };
+            for ranking in rankings {
+                let mut selected = self.selected_rankings.contains(&ranking.ranking_id);
+                if ui.checkbox(&mut selected, &ranking.description).changed() {
+                    match selected {
+                        true => self.selected_rankings.insert(ranking.ranking_id),
+                        false => self.selected_rankings.remove(&ranking.ranking_id),
+                    };
+                }
+            }
+        }
+
+        fn show_invitations(&mut self, ui: &mut Ui, ctx: &egui::Context, base_url: &str) {
+            let (Some(experts), Some(rankings)) = (&self.experts, &self.rankings) else {
+                return;
+            };
+            // The web build links to wherever it is served itself
+            #[cfg(not(target_arch = "wasm32"))]
+            if !self.selected_experts.is_empty() && !self.selected_rankings.is_empty() {
+                ui.horizontal(|ui| {
+                    let label = ui.label(tr("Web app address: "));
+                    let field =
+                        egui::TextEdit::singleline(&mut self.web_app_url).hint_text(base_url);
+                    ui.add(field).labelled_by(label.id);
+                });
+            }
+            let web_app_url = match self.web_app_url.trim() {
+                "" => base_url,
+                url => url,
+            };
+            let experts = experts
+                .iter()
+                .filter(|e| self.selected_experts.contains(&e.email));
+            for expert in experts {
+                let rankings = rankings
+                    .iter()
+                    .filter(|r| self.selected_rankings.contains(&r.ranking_id));
+                for ranking in rankings {
+                    let text = invitation(expert, ranking, web_app_url);
+                    ui.group(|ui| {
+                        ui.label(&text);
+                        ui.horizontal(|ui| {
+                            if ui.button(tr("Copy invitation")).clicked() {
+                                ctx.output_mut(|output| output.copied_text = text.clone());
+                            }
+                            ui.hyperlink_to(
+                                tr("Send by e-mail"),
+                                mailto(expert, ranking, web_app_url),
+                            );
+                        });
+                    });
+                }
+            }
+        }
+    }
+
+    impl View for ExpertDirectory {
+        fn show(
+            &mut self,
+            ui: &mut Ui,
+            ctx: &egui::Context,
+            base_url: &String,
+            session: &Session,
+        ) -> Option<Navigation> {
+            if ui.button(tr("Back to ranking_list")).clicked() {
+                return Some(Navigation::Back);
+            }
+            if !self.requested {
+                self.download_all(ctx, base_url, session);
+            }
+
+            if self.rankings.is_none() {
+                if let Some(rankings) = self.download_rankings.deserialize_when_got(ui) {
+                    self.rankings = Some(rankings);
+                    self.download_members(ctx, base_url, session);
+                }
+            }
+            let mut loaded = Vec::new();
+            for (ranking_id, download) in &self.download_members {
+                if let Some(experts) = download.deserialize_when_got::<Vec<Expert>>(ui) {
+                    loaded.push((*ranking_id, experts));
+                }
+            }
+            for (ranking_id, experts) in loaded {
+                self.members.insert(ranking_id, experts);
+                self.download_members.retain(|(id, _)| *id != ranking_id);
+            }
+            if self.experts.is_none() && self.rankings.is_some() && self.download_members.is_empty()
+            {
+                let mut experts: Vec<Expert> = Vec::new();
+                for expert in self.members.values().flatten() {
+                    if !experts.iter().any(|e| e.email == expert.email) {
+                        experts.push(expert.clone());
+                    }
+                }
+                experts.sort_by(|a, b| a.name.cmp(&b.name));
+                self.experts = Some(experts);
+            }
+
+            let assigning = !self.download_assign.is_empty();
+            if assigning {
+                let finished = self.download_assign.iter().all(|download| {
+                    download
+                        .promise
+                        .as_ref()
+                        .map_or(true, |p| p.ready().is_some())
+                });
+                if finished {
+                    self.error = String::new();
+                    for download in &self.download_assign {
+                        if let Some(Some(Ok(response))) =
+                            download.promise.as_ref().map(|p| p.ready())
+                        {
+                            if !response.ok {
+                                self.error = tr_args(
+                                    "Server responded with {status} {text}",
+                                    &[
+                                        ("status", &response.status),
+                                        ("text", &response.status_text),
+                                    ],
+                                );
+                            }
+                        }
+                    }
+                    self.download_assign.clear();
+                    self.download_members(ctx, base_url, session);
+                } else {
+                    ui.spinner();
+                }
+            }
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                ui.heading(tr("Expert directory"));
+                ui.horizontal(|ui| {
+                    ui.label(tr("Search:"));
+                    ui.text_edit_singleline(&mut self.search);
+                });
+                self.show_experts(ui);
+
+                ui.separator();
+                ui.heading(tr("Assign selected experts to"));
+                self.show_rankings(ui);
+                let can_assign = !assigning
+                    && !self.selected_experts.is_empty()
+                    && !self.selected_rankings.is_empty();
+                if ui
+                    .add_enabled(can_assign, egui::Button::new(tr("Assign")))
+                    .clicked()
+                {
+                    self.assign_selected(ctx, base_url, session);
+                }
+                if !self.error.is_empty() {
+                    ui.label(RichText::new(&self.error).color(Color32::RED));
+                }
+
+                ui.separator();
+                ui.heading(tr("Invitations"));
+                if self.selected_experts.is_empty() || self.selected_rankings.is_empty() {
+                    ui.label(tr("Select experts and rankings to invite them to"));
+                }
+                self.show_invitations(ui, ctx, base_url);
+            });
+
+            None
+        }
+
+        fn get_request(&self, _base_url: &String, _session: &Session) -> Option<Request> {
+            None
+        }
+
+        fn populate_from_json(&mut self, _json: &String) {
+            // Nothing
+        }
+
+        fn title(&self) -> String {
+            tr("Expert directory")
+        }
+
+        fn route(&self) -> Option<Route> {
+            Some(Route::Experts)
+        }
+
+        fn refresh(&mut self, _ctx: &egui::Context, _base_url: &String, _session: &Session) {
+            self.requested = false;
+        }
+    }
+}
@@ -0,0 +1,98 @@
+pub mod hierarchy {
+    use crate::app::schema::schema::Criterion;
+
+    /// Index of the parent of `criteria[index]`, `None` for main criteria. A
+    /// parent that isn't in `criteria` makes it a main criterion too.
+    pub fn parent(criteria: &[Criterion], index: usize) -> Option<usize> {
+        let parent_id = criteria[index].parent_id?;
+        criteria.iter().position(|c| c.criteria_id == parent_id)
+    }
+
+    /// Indices of the parents of `criteria[index]`, nearest first. Stops at a
+    /// cycle, which only broken data can contain.
+    pub fn ancestors(criteria: &[Criterion], index: usize) -> Vec<usize> {
+        let mut ancestors = Vec::new();
+        let mut current = index;
+        while let Some(parent) = parent(criteria, current) {
+            if parent == index || ancestors.contains(&parent) {
+                break;
+            }
+            ancestors.push(parent);
+            current = parent;
+        }
+        ancestors
+    }
+
+    /// Indices of the criteria directly below `parent`, or of the main criteria.
+    pub fn children(criteria: &[Criterion], parent: Option<usize>) -> Vec<usize> {
+        (0..criteria.len())
+            .filter(|&i| self::parent(criteria, i) == parent)
+            .collect()
+    }
+
+    /// Criteria without sub-criteria, the ones alternatives are compared on.
+    pub fn leaves(criteria: &[Criterion]) -> Vec<Criterion> {
+        (0..criteria.len())
+            .filter(|&i| children(criteria, Some(i)).is_empty())
+            .map(|i| criteria[i].clone())
+            .collect()
+    }
+
+    /// Groups of sibling criteria, every level before the one below it.
+    /// Each group is weighed on its own.
+    pub fn sibling_groups(criteria: &[Criterion]) -> Vec<Vec<usize>> {
+        let mut groups = vec![children(criteria, None)];
+        let mut next = 0;
+        while next < groups.len() {
+            for &index in &groups[next].clone() {
+                let below = children(criteria, Some(index));
+                if !below.is_empty() {
+                    groups.push(below);
+                }
+            }
+            next += 1;
+        }
+        groups
+    }
+
+    /// Depth-first order of the tree with the depth of every criterion, for showing it.
+    pub fn tree_order(criteria: &[Criterion]) -> Vec<(usize, usize)> {
+        fn visit(
+            criteria: &[Criterion],
+            parent: Option<usize>,
+            depth: usize,
+            order: &mut Vec<(usize, usize)>,
+        ) {
+            for index in children(criteria, parent) {
+                order.push((index, depth));
+                visit(criteria, Some(index), depth + 1, order);
+            }
+        }
+        let mut order = Vec::new();
+        visit(criteria, None, 0, &mut order);
+        order
+    }
+
+    /// Names from the main criterion down to `criteria[index]`, like "Cost › Fuel".
+    pub fn path(criteria: &[Criterion], index: usize) -> String {
+        let mut names: Vec<&str> = ancestors(criteria, index)
+            .iter()
+            .rev()
+            .map(|&i| criteria[i].name.as_str())
+            .collect();
+        names.push(&criteria[index].name);
+        names.join(" › ")
+    }
+
+    /// Global weights from weights local to each group of siblings: every
+    /// criterion's local weight multiplied by those of all its parents.
+    pub fn global_weights(criteria: &[Criterion], local: &[f64]) -> Vec<f64> {
+        (0..criteria.len())
+            .map(|index| {
+                ancestors(criteria, index)
+                    .iter()
+                    .fold(local[index], |weight, &parent| weight * local[parent])
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,194 @@
+pub mod i18n {
+    use std::cell::Cell;
+    use std::collections::HashMap;
+    use std::fmt::Display;
+    use std::sync::OnceLock;
+
+    /// Languages the interface is available in. The English texts are the
+    /// message ids themselves, other languages have a catalog in `locales/`.
+    #[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum Language {
+        #[default]
+        English,
+        Polish,
+    }
+
+    impl Language {
+        pub const ALL: [Language; 2] = [Language::English, Language::Polish];
+
+        /// Name of the language in itself, for the language picker.
+        pub fn name(&self) -> &'static str {
+            match self {
+                Language::English => "English",
+                Language::Polish => "Polski",
+            }
+        }
+
+        /// Code the translations of alternatives and criteria are stored under.
+        pub fn code(&self) -> &'static str {
+            match self {
+                Language::English => "en",
+                Language::Polish => "pl",
+            }
+        }
+
+        /// Which of the plural forms of a message to use for `count` things.
+        pub fn plural_form(&self, count: u64) -> usize {
+            match self {
+                Language::English => (count != 1) as usize,
+                // 1 pytanie, 2-4 pytania (but 12-14 pytań), 5 pytań
+                Language::Polish => {
+                    if count == 1 {
+                        0
+                    } else if (2..=4).contains(&(count % 10)) && !(12..=14).contains(&(count % 100))
+                    {
+                        1
+                    } else {
+                        2
+                    }
+                }
+            }
+        }
+
+        pub fn catalog(&self) -> Option<&'static Catalog> {
+            static POLISH: OnceLock<Catalog> = OnceLock::new();
+            match self {
+                Language::English => None,
+                Language::Polish => {
+                    Some(POLISH.get_or_init(|| parse_po(include_str!("../../locales/pl.po"))))
+                }
+            }
+        }
+    }
+
+    /// Translations by message id, one entry per plural form.
+    pub type Catalog = HashMap<String, Vec<String>>;
+
+    /// Reads the `msgid`, `msgid_plural` and `msgstr`/`msgstr[n]` entries of a
+    /// gettext `.po` file. Untranslated entries are left out.
+    pub fn parse_po(text: &str) -> Catalog {
+        let mut catalog = Catalog::new();
+        let mut id: Option<String> = None;
+        let mut translations: Vec<String> = Vec::new();
+        // Where continuation lines of a multi-line string go
+        let mut continuing_id = false;
+        let mut finish = |id: &mut Option<String>, translations: &mut Vec<String>| {
+            if let Some(id) = id.take() {
+                // The empty id holds the header, not a message
+                if !id.is_empty() && translations.iter().any(|t| !t.is_empty()) {
+                    catalog.insert(id, std::mem::take(translations));
+                }
+            }
+            translations.clear();
+        };
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with("msgid_plural ") {
+                // The English plural is in the code already
+                continuing_id = false;
+            } else if let Some(rest) = line.strip_prefix("msgid ") {
+                finish(&mut id, &mut translations);
+                id = Some(unquote(rest));
+                continuing_id = true;
+            } else if let Some(rest) = line.strip_prefix("msgstr") {
+                let value = rest.split_once(' ').map_or("", |(_, value)| value);
+                translations.push(unquote(value));
+                continuing_id = false;
+            } else if line.starts_with('"') {
+                let continuation = unquote(line);
+                match (continuing_id, &mut id, translations.last_mut()) {
+                    (true, Some(id), _) => id.push_str(&continuation),
+                    (false, _, Some(translation)) => translation.push_str(&continuation),
+                    _ => {}
+                }
+            }
+        }
+        finish(&mut id, &mut translations);
+        catalog
+    }
+
+    fn unquote(text: &str) -> String {
+        let text = text.trim();
+        let inner = text.strip_prefix('"').unwrap_or(text);
+        let inner = inner.strip_suffix('"').unwrap_or(inner);
+        inner
+            .replace("\\n", "\n")
+            .replace("\\\"", "\"")
+            .replace("\\\\", "\\")
+    }
+
+    thread_local! {
+        static LANGUAGE: Cell<Language> = Cell::new(Language::English);
+    }
+
+    /// Language `tr` and friends translate to, set by the app every frame.
+    pub fn set_language(language: Language) {
+        LANGUAGE.with(|current| current.set(language));
+    }
+
+    pub fn language() -> Language {
+        LANGUAGE.with(|current| current.get())
+    }
+
+    /// `message` in the current language.
+    pub fn tr(message: &str) -> String {
+        lookup(message, None).to_string()
+    }
+
+    /// `message` in the current language with its `{placeholders}` filled in.
+    pub fn tr_args(message: &str, args: &[(&str, &dyn Display)]) -> String {
+        fill(lookup(message, None), args)
+    }
+
+    /// The form of `singular`/`plural` that fits `count` things in the current
+    /// language, with `{count}` and the other placeholders filled in.
+    pub fn tr_plural(
+        singular: &str,
+        plural: &str,
+        count: u64,
+        args: &[(&str, &dyn Display)],
+    ) -> String {
+        let message = match lookup(singular, Some(count)) {
+            // Not translated, English rules apply
+            found if found == singular && count != 1 => plural,
+            found => found,
+        };
+        let mut args = args.to_vec();
+        args.push(("count", &count));
+        fill(message, &args)
+    }
+
+    fn lookup(message: &str, count: Option<u64>) -> &str {
+        let language = language();
+        let Some(forms) = language.catalog().and_then(|catalog| catalog.get(message)) else {
+            return message;
+        };
+        let form = count.map_or(0, |count| language.plural_form(count));
+        forms
+            .get(form)
+            .or(forms.last())
+            .filter(|translation| !translation.is_empty())
+            .map_or(message, String::as_str)
+    }
+
+    fn fill(message: &str, args: &[(&str, &dyn Display)]) -> String {
+        let mut text = message.to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{}}}", name), &value.to_string());
+        }
+        text
+    }
+
+    /// Picker for the interface language, for the top bar.
+    pub fn show_language_picker(ui: &mut egui::Ui, language: &mut Language) {
+        egui::ComboBox::from_label(tr("Language"))
+            .selected_text(language.name())
+            .show_ui(ui, |ui| {
+                for option in Language::ALL {
+                    ui.selectable_value(language, option, option.name());
+                }
+            });
+    }
+}
@@ -0,0 +1,181 @@
+pub mod images {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use base64::Engine;
+    use egui::load::{
+        Bytes, BytesLoadResult, BytesLoader, BytesPoll, ImageLoadResult, ImageLoader, ImagePoll,
+        LoadError, SizeHint,
+    };
+    use egui::{ColorImage, Context};
+
+    /// Lets `egui::Image` show pictures from `http(s)://` URLs and from
+    /// uploaded pictures embedded as `data:` URIs.
+    pub fn install(ctx: &Context) {
+        ctx.add_bytes_loader(Arc::new(WebLoader::default()));
+        ctx.add_image_loader(Arc::new(PngLoader::default()));
+    }
+
+    /// `data:` URI embedding a picture, as stored for uploaded images.
+    pub fn data_uri(mime: &str, bytes: &[u8]) -> String {
+        format!(
+            "data:{};base64,{}",
+            mime,
+            base64::engine::general_purpose::STANDARD.encode(bytes)
+        )
+    }
+
+    /// Contents of a `data:` URI, `None` when `uri` isn't a base64 one.
+    pub fn decode_data_uri(uri: &str) -> Option<Result<Vec<u8>, String>> {
+        let rest = uri.strip_prefix("data:")?;
+        let (header, data) = rest.split_once(',')?;
+        if !header.ends_with(";base64") {
+            return None;
+        }
+        Some(
+            base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .map_err(|err| err.to_string()),
+        )
+    }
+
+    type Entry = Option<Result<Arc<[u8]>, String>>;
+
+    /// Downloads pictures with `ehttp`, or decodes them from `data:` URIs.
+    #[derive(Default)]
+    struct WebLoader {
+        /// `None` while still downloading
+        cache: Arc<Mutex<HashMap<String, Entry>>>,
+    }
+
+    impl BytesLoader for WebLoader {
+        fn id(&self) -> &str {
+            "ranking_client::images::WebLoader"
+        }
+
+        fn load(&self, ctx: &Context, uri: &str) -> BytesLoadResult {
+            let is_web = uri.starts_with("http://") || uri.starts_with("https://");
+            if !is_web && !uri.starts_with("data:") {
+                return Err(LoadError::NotSupported);
+            }
+            let mut cache = self.cache.lock().unwrap();
+            if let Some(entry) = cache.get(uri) {
+                return match entry {
+                    None => Ok(BytesPoll::Pending { size: None }),
+                    Some(Ok(bytes)) => Ok(BytesPoll::Ready {
+                        size: None,
+                        bytes: Bytes::Shared(bytes.clone()),
+                        mime: None,
+                    }),
+                    Some(Err(error)) => Err(LoadError::Loading(error.clone())),
+                };
+            }
+
+            if let Some(decoded) = decode_data_uri(uri) {
+                cache.insert(uri.to_string(), Some(decoded.map(Arc::from)));
+                drop(cache);
+                return self.load(ctx, uri);
+            }
+            cache.insert(uri.to_string(), None);
+            let ctx = ctx.clone();
+            let entries = self.cache.clone();
+            let uri = uri.to_string();
+            ehttp::fetch(ehttp::Request::get(&uri), move |response| {
+                let entry = match response {
+                    Ok(response) if response.ok => Ok(Arc::from(response.bytes)),
+                    Ok(response) => Err(format!(
+                        "Server responded with {} {}",
+                        response.status, response.status_text
+                    )),
+                    Err(error) => Err(error),
+                };
+                entries.lock().unwrap().insert(uri, Some(entry));
+                ctx.request_repaint();
+            });
+            Ok(BytesPoll::Pending { size: None })
+        }
+
+        fn forget(&self, uri: &str) {
+            self.cache.lock().unwrap().remove(uri);
+        }
+
+        fn forget_all(&self) {
+            self.cache.lock().unwrap().clear();
+        }
+
+        fn byte_size(&self) -> usize {
+            self.cache
+                .lock()
+                .unwrap()
+                .values()
+                .map(|entry| match entry {
+                    Some(Ok(bytes)) => bytes.len(),
+                    _ => 0,
+                })
+                .sum()
+        }
+    }
+
+    /// Decodes PNG pictures with the `image` crate.
+    #[derive(Default)]
+    struct PngLoader {
+        cache: Mutex<HashMap<String, Result<Arc<ColorImage>, String>>>,
+    }
+
+    impl ImageLoader for PngLoader {
+        fn id(&self) -> &str {
+            "ranking_client::images::PngLoader"
+        }
+
+        fn load(&self, ctx: &Context, uri: &str, _: SizeHint) -> ImageLoadResult {
+            if let Some(image) = self.cache.lock().unwrap().get(uri) {
+                return match image {
+                    Ok(image) => Ok(ImagePoll::Ready {
+                        image: image.clone(),
+                    }),
+                    Err(error) => Err(LoadError::Loading(error.clone())),
+                };
+            }
+            match ctx.try_load_bytes(uri)? {
+                BytesPoll::Pending { size } => Ok(ImagePoll::Pending { size }),
+                BytesPoll::Ready { bytes, .. } => {
+                    let image = image::load_from_memory(&bytes)
+                        .map(|image| {
+                            let image = image.to_rgba8();
+                            let size = [image.width() as usize, image.height() as usize];
+                            Arc::new(ColorImage::from_rgba_unmultiplied(size, image.as_raw()))
+                        })
+                        .map_err(|error| format!("Only PNG pictures are supported: {}", error));
+                    self.cache
+                        .lock()
+                        .unwrap()
+                        .insert(uri.to_string(), image.clone());
+                    match image {
+                        Ok(image) => Ok(ImagePoll::Ready { image }),
+                        Err(error) => Err(LoadError::Loading(error)),
+                    }
+                }
+            }
+        }
+
+        fn forget(&self, uri: &str) {
+            self.cache.lock().unwrap().remove(uri);
+        }
+
+        fn forget_all(&self) {
+            self.cache.lock().unwrap().clear();
+        }
+
+        fn byte_size(&self) -> usize {
+            self.cache
+                .lock()
+                .unwrap()
+                .values()
+                .map(|image| match image {
+                    Ok(image) => image.pixels.len() * 4,
+                    Err(_) => 0,
+                })
+                .sum()
+        }
+    }
+}
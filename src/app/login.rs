@@ -1,15 +1,17 @@
 pub mod login {
 
     use crate::app::{
+        api::api,
         data::Data,
         download::{self, download::Download},
+        i18n::i18n::{tr, tr_args},
         schema::schema::Expert,
     };
     use egui::{Response, Ui};
     use ehttp::Request;
     use serde::Deserialize;
 
-    #[derive(serde::Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+    #[derive(serde::Deserialize, serde::Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
     pub struct AccessToken {
         acces_token: String,
         token_type: String,
@@ -70,22 +72,13 @@ pub mod login {
         pub fn show(&mut self, ui: &mut Ui, ctx: &egui::Context, data: &Data) {
             // ui.horizontal_centered(|ui| {
             egui::Grid::new("Login form").num_columns(2).show(ui, |ui| {
-                ui.label("Email: ");
-                ui.text_edit_singleline(&mut self.email);
+                let label = ui.label(tr("Email: "));
+                ui.text_edit_singleline(&mut self.email)
+                    .labelled_by(label.id);
                 ui.end_row();
 
-                if ui.button("Login").clicked() {
-                    let mut request = Request::post(
-                        data.base_url.clone() + "/token",
-                        format!("grant_type=password&username={}&password=abc", self.email)
-                            .to_string()
-                            .as_bytes()
-                            .to_vec(),
-                    );
-                    request.headers.insert(
-                        "Content-Type".to_string(),
-                        "application/x-www-form-urlencoded".to_string(),
-                    );
+                if ui.button(tr("Login")).clicked() {
+                    let request = api::token(&data.base_url, &self.email);
                     self.download.download(ctx, request);
                 }
 
@@ -100,20 +93,22 @@ pub mod login {
                                                 if let Ok(token) =
                                                     serde_json::from_str::<AccessToken>(json)
                                                 {
-                                                    let request =
-                                                        Request::get(data.base_url.clone() + "/me");
-                                                    let request =
-                                                        token.add_authorization_header(request);
+                                                    let request = api::get(
+                                                        data.base_url.clone() + "/me",
+                                                        &token,
+                                                    );
                                                     self.step = LoginStep::GetUserInfo(token);
                                                     self.download.download(ctx, request);
                                                     return;
                                                 } else {
-                                                    ui.label("Failed to deserialze token response");
+                                                    ui.label(tr(
+                                                        "Failed to read the server's response",
+                                                    ));
                                                 }
                                             } else {
-                                                ui.label(format!(
-                                                    "Failed to log in due to: {}",
-                                                    &response.status_text
+                                                ui.label(tr_args(
+                                                    "Failed to log in due to: {reason}",
+                                                    &[("reason", &response.status_text)],
                                                 ));
                                             }
                                         }
@@ -131,12 +126,14 @@ pub mod login {
                                                         expert,
                                                     ));
                                                 } else {
-                                                    ui.label("Failed to deserialze token response");
+                                                    ui.label(tr(
+                                                        "Failed to read the server's response",
+                                                    ));
                                                 }
                                             } else {
-                                                ui.label(format!(
-                                                    "Failed to log in due to: {}",
-                                                    &response.status_text
+                                                ui.label(tr_args(
+                                                    "Failed to log in due to: {reason}",
+                                                    &[("reason", &response.status_text)],
                                                 ));
                                             }
                                         }
@@ -146,7 +143,7 @@ pub mod login {
                                     println!("{}", &response.text().unwrap());
                                 }
                                 None => {
-                                    ui.label("Failed request");
+                                    ui.label(tr("Failed request"));
                                 }
                             },
                             Err(error) => {
@@ -0,0 +1,186 @@
+pub mod markdown {
+    use egui::{RichText, Ui};
+
+    /// Piece of a line with one style.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Span {
+        Text {
+            text: String,
+            strong: bool,
+            italic: bool,
+            code: bool,
+        },
+        Link {
+            text: String,
+            url: String,
+        },
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Block {
+        /// `# Title`, with the number of `#`
+        Heading(usize, Vec<Span>),
+        /// `- item` or `* item`
+        Bullet(Vec<Span>),
+        Paragraph(Vec<Span>),
+    }
+
+    /// The small part of markdown descriptions need: headings, bullet lists,
+    /// `**strong**`, `*italic*`, `` `code` `` and `[links](https://...)`.
+    pub fn parse(text: &str) -> Vec<Block> {
+        let mut blocks = Vec::new();
+        let mut paragraph: Vec<&str> = Vec::new();
+        let end_paragraph = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+            if !paragraph.is_empty() {
+                blocks.push(Block::Paragraph(parse_spans(&paragraph.join(" "))));
+                paragraph.clear();
+            }
+        };
+        for line in text.lines() {
+            let line = line.trim();
+            let level = line.chars().take_while(|c| *c == '#').count();
+            if line.is_empty() {
+                end_paragraph(&mut paragraph, &mut blocks);
+            } else if level > 0 && line[level..].starts_with(' ') {
+                end_paragraph(&mut paragraph, &mut blocks);
+                blocks.push(Block::Heading(level, parse_spans(line[level..].trim())));
+            } else if let Some(item) = line.strip_prefix("- ").or(line.strip_prefix("* ")) {
+                end_paragraph(&mut paragraph, &mut blocks);
+                blocks.push(Block::Bullet(parse_spans(item)));
+            } else {
+                paragraph.push(line);
+            }
+        }
+        end_paragraph(&mut paragraph, &mut blocks);
+        blocks
+    }
+
+    fn parse_spans(text: &str) -> Vec<Span> {
+        let mut spans = Vec::new();
+        let (mut strong, mut italic, mut code) = (false, false, false);
+        let mut current = String::new();
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            let flush = |current: &mut String, spans: &mut Vec<Span>| {
+                if !current.is_empty() {
+                    spans.push(Span::Text {
+                        text: std::mem::take(current),
+                        strong,
+                        italic,
+                        code,
+                    });
+                }
+            };
+            if c == '`' {
+                flush(&mut current, &mut spans);
+                code = !code;
+                rest = &rest[1..];
+            } else if code {
+                current.push(c);
+                rest = &rest[c.len_utf8()..];
+            } else if rest.starts_with("**") {
+                flush(&mut current, &mut spans);
+                strong = !strong;
+                rest = &rest[2..];
+            } else if c == '*' && (italic || opens_italic(&rest[1..])) {
+                flush(&mut current, &mut spans);
+                italic = !italic;
+                rest = &rest[1..];
+            } else if let Some((link, after)) = parse_link(rest) {
+                flush(&mut current, &mut spans);
+                spans.push(link);
+                rest = after;
+            } else {
+                current.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        if !current.is_empty() {
+            spans.push(Span::Text {
+                text: current,
+                strong,
+                italic,
+                code,
+            });
+        }
+        spans
+    }
+
+    /// Whether a `*` followed by `after` starts italics: it has to touch the
+    /// text it emphasises and be closed later on.
+    fn opens_italic(after: &str) -> bool {
+        after.starts_with(|c: char| !c.is_whitespace()) && after.contains('*')
+    }
+
+    /// `[text](url)` at the start of `text`, and what follows it.
+    fn parse_link(text: &str) -> Option<(Span, &str)> {
+        let inner = text.strip_prefix('[')?;
+        let (label, after) = inner.split_once("](")?;
+        let (url, rest) = after.split_once(')')?;
+        if label.contains('[') || url.contains(char::is_whitespace) {
+            return None;
+        }
+        let link = Span::Link {
+            text: label.to_string(),
+            url: url.to_string(),
+        };
+        Some((link, rest))
+    }
+
+    fn show_spans(ui: &mut Ui, spans: &[Span], size: Option<f32>) {
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.0;
+            for span in spans {
+                match span {
+                    Span::Text {
+                        text,
+                        strong,
+                        italic,
+                        code,
+                    } => {
+                        let mut text = RichText::new(text);
+                        if let Some(size) = size {
+                            text = text.size(size).strong();
+                        }
+                        if *strong {
+                            text = text.strong();
+                        }
+                        if *italic {
+                            text = text.italics();
+                        }
+                        if *code {
+                            text = text.code();
+                        }
+                        ui.label(text);
+                    }
+                    Span::Link { text, url } => {
+                        ui.hyperlink_to(text, url);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Shows `text` rendered as markdown.
+    pub fn show(ui: &mut Ui, text: &str) {
+        for block in parse(text) {
+            match block {
+                Block::Heading(level, spans) => {
+                    let size = match level {
+                        1 => 20.0,
+                        2 => 17.0,
+                        _ => 15.0,
+                    };
+                    show_spans(ui, &spans, Some(size));
+                }
+                Block::Bullet(spans) => {
+                    ui.horizontal(|ui| {
+                        ui.label("•");
+                        show_spans(ui, &spans, None);
+                    });
+                }
+                Block::Paragraph(spans) => show_spans(ui, &spans, None),
+            }
+        }
+    }
+}
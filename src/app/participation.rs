@@ -0,0 +1,281 @@
+pub mod participation {
+    use egui::{Color32, RichText, Ui};
+    use ehttp::Request;
+
+    use crate::app::{
+        download::download::Download,
+        hierarchy::hierarchy,
+        i18n::i18n::{tr, tr_args, tr_plural},
+        login::login::Session,
+        schema::schema::{
+            Alternative, Criterion, Expert, Participation, ParticipationStatus, Ranking, Variables,
+        },
+        view::{Navigation, Route, View},
+    };
+
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum Filter {
+        All,
+        NotStarted,
+        Incomplete,
+    }
+
+    impl Filter {
+        fn matches(&self, status: ParticipationStatus) -> bool {
+            match self {
+                Filter::All => true,
+                Filter::NotStarted => status == ParticipationStatus::NotStarted,
+                Filter::Incomplete => status != ParticipationStatus::Complete,
+            }
+        }
+    }
+
+    /// Which experts of a ranking answered how many questions.
+    pub struct ParticipationView {
+        ranking: Ranking,
+        experts: Option<Vec<Expert>>,
+        alternatives: Option<Vec<Alternative>>,
+        criteria: Option<Vec<Criterion>>,
+        participation: Option<Vec<Participation>>,
+        variables: Option<Variables>,
+        filter: Filter,
+        requested: bool,
+
+        download_experts: Download,
+        download_alternatives: Download,
+        download_criteria: Download,
+        download_participation: Download,
+        download_variables: Download,
+    }
+
+    impl ParticipationView {
+        pub fn new(ranking: Ranking) -> Self {
+            Self {
+                ranking,
+                experts: None,
+                alternatives: None,
+                criteria: None,
+                participation: None,
+                variables: None,
+                filter: Filter::All,
+                requested: false,
+                download_experts: Download::default(),
+                download_alternatives: Download::default(),
+                download_criteria: Download::default(),
+                download_participation: Download::default(),
+                download_variables: Download::default(),
+            }
+        }
+
+        fn download_all(&mut self, ctx: &egui::Context, base_url: &String, session: &Session) {
+            self.experts = None;
+            self.alternatives = None;
+            self.criteria = None;
+            self.participation = None;
+            self.variables = None;
+            let url = |middle_url: &str| {
+                format!("{}/{}/{}", base_url, middle_url, self.ranking.ranking_id)
+            };
+            self.download_experts
+                .get_schema(url("experts"), ctx, session);
+            self.download_alternatives
+                .get_schema(url("all_alternatives"), ctx, session);
+            self.download_criteria
+                .get_schema(url("criteria"), ctx, session);
+            // The answers themselves are only part of the export
+            self.download_participation
+                .get_schema(url("export_data"), ctx, session);
+            self.download_variables
+                .get_schema(url("variables"), ctx, session);
+            self.requested = true;
+        }
+
+        fn show_table(&self, ui: &mut Ui) {
+            let (
+                Some(experts),
+                Some(alternatives),
+                Some(criteria),
+                Some(participation),
+                Some(variables),
+            ) = (
+                &self.experts,
+                &self.alternatives,
+                &self.criteria,
+                &self.participation,
+                &self.variables,
+            )
+            else {
+                return;
+            };
+            let design = &variables.design;
+            let leaf_criteria = hierarchy::leaves(criteria).len();
+            let required_comparisons = design.pair_count(alternatives.len()) * leaf_criteria;
+
+            let statuses: Vec<(&Expert, &Participation, ParticipationStatus)> = participation
+                .iter()
+                .filter_map(|p| {
+                    let expert = experts.iter().find(|e| e.expert_id == p.expert_id)?;
+                    let status =
+                        p.status_in(design, alternatives.len(), leaf_criteria, criteria.len());
+                    Some((expert, p, status))
+                })
+                .collect();
+            let complete = statuses
+                .iter()
+                .filter(|(_, _, status)| *status == ParticipationStatus::Complete)
+                .count();
+            ui.label(tr_plural(
+                "{complete} of {count} expert has answered everything",
+                "{complete} of {count} experts have answered everything",
+                statuses.len() as u64,
+                &[("complete", &complete)],
+            ));
+
+            egui::Grid::new("Participation")
+                .striped(true)
+                .num_columns(5)
+                .show(ui, |ui| {
+                    ui.strong(tr("Expert"));
+                    ui.strong(tr("Comparisons"));
+                    ui.strong(tr("Weights"));
+                    ui.strong(tr("Status"));
+                    ui.strong(tr("Last answered"));
+                    ui.end_row();
+                    for (expert, participation, status) in &statuses {
+                        if !self.filter.matches(*status) {
+                            continue;
+                        }
+                        ui.label(&expert.name).on_hover_text(&expert.email);
+                        ui.label(format!(
+                            "{} / {}",
+                            participation.comparisons, required_comparisons
+                        ));
+                        ui.label(format!("{} / {}", participation.weights, criteria.len()));
+                        ui.label(match status {
+                            ParticipationStatus::NotStarted => {
+                                RichText::new(tr("Not started")).color(Color32::RED)
+                            }
+                            ParticipationStatus::Incomplete => {
+                                RichText::new(tr("Incomplete")).color(Color32::YELLOW)
+                            }
+                            ParticipationStatus::Complete => {
+                                RichText::new(tr("Complete")).color(Color32::GREEN)
+                            }
+                        });
+                        let last_answered = participation.last_answered.and_then(|timestamp| {
+                            chrono::NaiveDateTime::from_timestamp_opt(timestamp, 0)
+                        });
+                        match last_answered {
+                            Some(time) => ui.label(time.format("%Y-%m-%d %H:%M").to_string()),
+                            None if *status == ParticipationStatus::NotStarted => {
+                                ui.label(tr("Never"))
+                            }
+                            // The server didn't say when
+                            None => ui.label(tr("Unknown")),
+                        };
+                        ui.end_row();
+                    }
+                });
+        }
+    }
+
+    impl View for ParticipationView {
+        fn show(
+            &mut self,
+            ui: &mut Ui,
+            ctx: &egui::Context,
+            base_url: &String,
+            session: &Session,
+        ) -> Option<Navigation> {
+            if ui.button(tr("Back to ranking editing")).clicked() {
+                return Some(Navigation::Back);
+            }
+            if !self.requested {
+                self.download_all(ctx, base_url, session);
+            }
+            if self.experts.is_none() {
+                self.experts = self.download_experts.deserialize_when_got(ui);
+            }
+            if self.alternatives.is_none() {
+                self.alternatives = self.download_alternatives.deserialize_when_got(ui);
+            }
+            if self.criteria.is_none() {
+                self.criteria = self.download_criteria.deserialize_when_got(ui);
+            }
+            if self.participation.is_none() {
+                self.download_participation.run_when_downloaded(
+                    ui,
+                    |response, ui| match response.ok {
+                        true => {
+                            self.participation =
+                                response.text().and_then(Participation::from_export);
+                            if self.participation.is_none() {
+                                ui.label(tr("Failed to read the answers of the ranking"));
+                            }
+                        }
+                        false => {
+                            ui.label(tr_args(
+                                "Failed to get the answers, server responded with {status} status code",
+                                &[("status", &response.status)],
+                            ));
+                        }
+                    },
+                );
+            }
+            if self.variables.is_none() {
+                self.download_variables
+                    .run_when_downloaded(ui, |response, ui| match response.status {
+                        200 => match response.text().map(serde_json::from_str::<Variables>) {
+                            Some(Ok(variables)) => self.variables = Some(variables),
+                            _ => {
+                                ui.label(tr("Failed to parse the variables of the ranking"));
+                            }
+                        },
+                        // Without variables every pair is asked
+                        404 => self.variables = Some(Variables::default()),
+                        code => {
+                            ui.label(tr_args(
+                                "Failed to get variables, server responded with {status} status code",
+                                &[("status", &code)],
+                            ));
+                        }
+                    });
+            }
+
+            ui.heading(self.title());
+            ui.horizontal(|ui| {
+                ui.label(tr("Show:"));
+                ui.selectable_value(&mut self.filter, Filter::All, tr("All"));
+                ui.selectable_value(&mut self.filter, Filter::NotStarted, tr("Not started"));
+                ui.selectable_value(&mut self.filter, Filter::Incomplete, tr("Incomplete"));
+            });
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                self.show_table(ui);
+            });
+            None
+        }
+
+        fn get_request(&self, _base_url: &String, _session: &Session) -> Option<Request> {
+            None
+        }
+
+        fn populate_from_json(&mut self, _json: &String) {
+            // Nothing
+        }
+
+        fn title(&self) -> String {
+            tr_args(
+                "Participation in {name}",
+                &[("name", &self.ranking.description)],
+            )
+        }
+
+        fn route(&self) -> Option<Route> {
+            Some(Route::Participation(self.ranking.ranking_id))
+        }
+
+        fn refresh(&mut self, _ctx: &egui::Context, _base_url: &String, _session: &Session) {
+            self.requested = false;
+        }
+    }
+}
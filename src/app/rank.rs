@@ -1,17 +1,23 @@
 use std::borrow::Borrow;
 
-use crate::app::view::View;
-use combinations::Combinations;
-use egui::{Response, Ui};
+use crate::app::view::{Navigation, Route, View};
+use egui::{Color32, Response, RichText, Ui};
 use ehttp::Request;
 use serde::{Deserialize, Serialize};
 
 use super::{
+    accessibility::accessibility::describe,
+    ahp::ahp::{self, Judgement},
     data::Data,
     download::download::Download,
+    hierarchy::hierarchy,
+    i18n::i18n::{language, tr, tr_args, tr_plural},
     login::login::Session,
-    ranking_list::ranking_list::RankingList,
-    schema::schema::{Alternative, Criterion, Ranking, Scale},
+    schedule::schedule::{criteria_pairs, schedule, Question},
+    schema::schema::{
+        ABInput, Alternative, CriteriaWeighting, Criterion, CriterionWeight, Ranking, Scale,
+        Variables, Weights,
+    },
 };
 
 // #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,27 +85,11 @@ use super::{
 //     CriterionChoice(CriterionChoice),
 // }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct ABInput {
-    alternativeA_id: i64,
-    alternativeB_id: i64,
-    winner_id: i64,
-    expert_id: u64,
-    criteria_id: i64,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct Weights {
-    weights_id: i64,
-    ranking_id: i64,
-    expert_id: u64,
-    criteria_id: i64,
-    scale_id: i64,
-}
-
 enum RankMode {
     Alternative,
     Criterion,
+    /// Weighing the criteria by comparing them pairwise
+    CriteriaPairs,
     Finshed,
 }
 
@@ -108,6 +98,7 @@ pub struct RankView {
     alternatives_list: Option<Vec<Alternative>>,
     criteria: Option<Vec<Criterion>>,
     scale: Option<Vec<Scale>>,
+    variables: Option<Variables>,
     // TODO:
     // Scale
     error: String,
@@ -116,13 +107,19 @@ pub struct RankView {
     download_alternatives: Download,
     download_criteria: Download,
     download_scale: Download,
+    download_variables: Download,
 
-    alternative_combinations: Option<Combinations<Alternative>>,
-    current_combination_pair: Vec<Alternative>,
-    next_combination: bool,
+    questions: Option<Vec<Question>>,
+    current_question: usize,
     current_criterion: usize,
     rank_mode: RankMode,
     scale_ix: usize,
+    /// Justification sent along with the current answer
+    comment: String,
+
+    criteria_pairs: Option<Vec<(usize, usize)>>,
+    criteria_judgements: Vec<Judgement>,
+    intensity_ix: usize,
 }
 
 impl RankView {
@@ -132,17 +129,22 @@ impl RankView {
             alternatives_list: None,
             criteria: None,
             scale: None,
+            variables: None,
             error: String::new(),
             download: Download::default(),
             download_alternatives: Download::default(),
             download_criteria: Download::default(),
             download_scale: Download::default(),
-            alternative_combinations: None,
-            current_combination_pair: Vec::new(),
-            next_combination: true,
+            download_variables: Download::default(),
+            questions: None,
+            current_question: 0,
             current_criterion: 0,
             rank_mode: RankMode::Alternative,
             scale_ix: 0,
+            comment: String::new(),
+            criteria_pairs: None,
+            criteria_judgements: Vec::new(),
+            intensity_ix: 1,
         };
 
         let downloader_utility = |middle_url: &str| -> Request {
@@ -158,10 +160,345 @@ impl RankView {
 
         edit.download_scale
             .download(ctx, downloader_utility("get_scale"));
+
+        edit.download_variables
+            .download(ctx, downloader_utility("variables"));
         edit
     }
 }
 
+/// What a key pressed while answering asks for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Shortcut {
+    Left,
+    Right,
+    Equal,
+    Skip,
+    Undo,
+    Submit,
+    /// A number key, 1 to 9
+    Number(usize),
+}
+
+impl Shortcut {
+    fn from_key(key: egui::Key) -> Option<Self> {
+        use egui::Key;
+        let number = |n| Some(Shortcut::Number(n));
+        match key {
+            Key::ArrowLeft | Key::A => Some(Shortcut::Left),
+            Key::ArrowRight | Key::L => Some(Shortcut::Right),
+            Key::E => Some(Shortcut::Equal),
+            Key::S => Some(Shortcut::Skip),
+            Key::Backspace => Some(Shortcut::Undo),
+            Key::Enter => Some(Shortcut::Submit),
+            Key::Num1 => number(1),
+            Key::Num2 => number(2),
+            Key::Num3 => number(3),
+            Key::Num4 => number(4),
+            Key::Num5 => number(5),
+            Key::Num6 => number(6),
+            Key::Num7 => number(7),
+            Key::Num8 => number(8),
+            Key::Num9 => number(9),
+            _ => None,
+        }
+    }
+
+    /// The shortcut pressed this frame. Keys typed into a text field, like
+    /// the comment, are left alone; Escape moves the focus out of it. A held
+    /// key answers once, not again with every key-repeat.
+    fn pressed(ctx: &egui::Context) -> Option<Self> {
+        if ctx.wants_keyboard_input() {
+            return None;
+        }
+        ctx.input(|input| {
+            input.events.iter().find_map(|event| match event {
+                egui::Event::Key {
+                    key,
+                    pressed: true,
+                    repeat: false,
+                    modifiers,
+                } if !modifiers.command && !modifiers.alt => Self::from_key(*key),
+                _ => None,
+            })
+        })
+    }
+}
+
+fn show_shortcut_hint(ui: &mut egui::Ui, hint: &str) {
+    ui.label(RichText::new(hint).small().weak());
+}
+
+/// How long before the deadline a countdown is shown.
+const COUNTDOWN_FROM: i64 = 60 * 60;
+/// How long before the deadline experts are warned to finish up.
+const WARNING_FROM: i64 = 5 * 60;
+
+/// Optional free-text justification of the answer being given.
+fn show_comment(ui: &mut egui::Ui, comment: &mut String) {
+    let label = ui.label(tr("Comment (optional):"));
+    ui.add(
+        egui::TextEdit::multiline(comment)
+            .desired_rows(2)
+            .hint_text(tr("Why did you answer this way?")),
+    )
+    .labelled_by(label.id);
+}
+
+fn format_remaining(remaining: chrono::Duration) -> String {
+    let seconds = remaining.num_seconds().max(0);
+    let (days, seconds) = (seconds / 86_400, seconds % 86_400);
+    let clock = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    );
+    match days {
+        0 => clock,
+        days => tr_plural(
+            "{count} day {clock}",
+            "{count} days {clock}",
+            days as u64,
+            &[("clock", &clock)],
+        ),
+    }
+}
+
+impl RankView {
+    /// Shows the countdown or warning when the deadline is near, or the
+    /// "closed" screen once it passed. Returns whether answers are still accepted.
+    fn show_deadline(&self, ui: &mut egui::Ui, ctx: &egui::Context) -> bool {
+        let Some(remaining) = self.ranking.remaining_at(chrono::Utc::now()) else {
+            return true;
+        };
+        let seconds = remaining.num_seconds();
+        if seconds <= 0 {
+            let deadline =
+                chrono::DateTime::<chrono::Local>::from(self.ranking.expires_at().unwrap());
+            ui.vertical_centered(|ui| {
+                ui.heading(&self.ranking.description);
+                ui.label(tr_args(
+                    "This ranking closed on {deadline}, answers are no longer accepted.",
+                    &[("deadline", &deadline.format("%Y-%m-%d %H:%M"))],
+                ));
+            });
+            return false;
+        }
+        if seconds <= COUNTDOWN_FROM {
+            ui.vertical_centered(|ui| {
+                let text = tr_args(
+                    "Closes in {remaining}",
+                    &[("remaining", &format_remaining(remaining))],
+                );
+                match seconds <= WARNING_FROM {
+                    true => {
+                        ui.label(RichText::new(text).strong().color(Color32::RED));
+                        ui.label(tr(
+                            "Answers sent after the deadline are rejected, finish up soon.",
+                        ));
+                    }
+                    false => {
+                        ui.label(text);
+                    }
+                }
+            });
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        } else {
+            // Wake up in time to start the countdown
+            ctx.request_repaint_after(std::time::Duration::from_secs(
+                (seconds - COUNTDOWN_FROM) as u64,
+            ));
+        }
+        true
+    }
+}
+
+impl RankView {
+    /// Asks the expert to compare every pair of criteria, then shows the
+    /// resulting weights and posts them.
+    fn show_criteria_pairs(
+        &mut self,
+        ui: &mut egui::Ui,
+        ctx: &egui::Context,
+        base_url: &String,
+        session: &Session,
+        criteria: &[Criterion],
+        shortcut: Option<Shortcut>,
+    ) {
+        if shortcut == Some(Shortcut::Undo) && self.download.promise.is_none() {
+            self.criteria_judgements.pop();
+        }
+        let variables = self.variables.as_ref().unwrap();
+        let groups = hierarchy::sibling_groups(criteria);
+        let pairs = self.criteria_pairs.get_or_insert_with(|| {
+            criteria_pairs(
+                &groups,
+                &variables.presentation,
+                session.user_info.expert_id,
+            )
+        });
+
+        if let Some(&(a, b)) = pairs.get(self.criteria_judgements.len()) {
+            let (criterion_a, criterion_b) = (&criteria[a], &criteria[b]);
+            if let Some(Shortcut::Number(n)) = shortcut {
+                // The number is the intensity itself, 1 is "Equally" which E is for
+                let level =
+                    (1..ahp::INTENSITIES.len()).find(|&i| ahp::INTENSITIES[i].0 == n as f64);
+                if let Some(level) = level {
+                    self.intensity_ix = level;
+                }
+            }
+            ui.vertical_centered(|ui| {
+                ui.heading(tr("Which criterion is more important?"));
+                ui.horizontal(|ui| {
+                    ui.label(tr("By how much:"));
+                    for (i, (_, name)) in ahp::INTENSITIES.iter().enumerate().skip(1) {
+                        ui.selectable_value(&mut self.intensity_ix, i, tr(name));
+                    }
+                });
+                let intensity = ahp::INTENSITIES[self.intensity_ix].0;
+                let mut judgement = match shortcut {
+                    Some(Shortcut::Left) => Some(Judgement {
+                        preferred: a,
+                        other: b,
+                        intensity,
+                    }),
+                    Some(Shortcut::Right) => Some(Judgement {
+                        preferred: b,
+                        other: a,
+                        intensity,
+                    }),
+                    Some(Shortcut::Equal) => Some(Judgement {
+                        preferred: a,
+                        other: b,
+                        intensity: 1.0,
+                    }),
+                    _ => None,
+                };
+                ui.columns(2, |columns| {
+                    let button = columns[0].button(&criterion_a.name);
+                    let more_important =
+                        |name: &str| tr_args("{name} is more important", &[("name", &name)]);
+                    describe(&button, more_important(&criterion_a.name));
+                    if button.clicked() {
+                        judgement = Some(Judgement {
+                            preferred: a,
+                            other: b,
+                            intensity,
+                        });
+                    }
+                    columns[0].label(&criterion_a.description);
+                    let button = columns[1].button(&criterion_b.name);
+                    describe(&button, more_important(&criterion_b.name));
+                    if button.clicked() {
+                        judgement = Some(Judgement {
+                            preferred: b,
+                            other: a,
+                            intensity,
+                        });
+                    }
+                    columns[1].label(&criterion_b.description);
+                });
+                if ui.button(tr("Equally important")).clicked() {
+                    judgement = Some(Judgement {
+                        preferred: a,
+                        other: b,
+                        intensity: 1.0,
+                    });
+                }
+                if let Some(judgement) = judgement {
+                    self.criteria_judgements.push(judgement);
+                }
+                show_shortcut_hint(
+                    ui,
+                    &tr("Keys: ← or A left, → or L right, E equally, 3 5 7 9 by how much, Backspace undo"),
+                );
+            });
+            return;
+        }
+
+        let (weights, consistency) =
+            ahp::grouped_weights(criteria.len(), &groups, &self.criteria_judgements);
+        let global = hierarchy::global_weights(criteria, &weights);
+        ui.vertical_centered(|ui| {
+            ui.heading(tr("Your weights of the criteria"));
+            egui::Grid::new("Criteria weights")
+                .num_columns(3)
+                .show(ui, |ui| {
+                    ui.strong(tr("Criterion"));
+                    ui.strong(tr("Local"));
+                    ui.strong(tr("Global"));
+                    ui.end_row();
+                    for (index, depth) in hierarchy::tree_order(criteria) {
+                        ui.horizontal(|ui| {
+                            ui.add_space(depth as f32 * 16.0);
+                            ui.label(&criteria[index].name);
+                        });
+                        ui.label(format!("{:.1}%", weights[index] * 100.0));
+                        ui.label(format!("{:.1}%", global[index] * 100.0));
+                        ui.end_row();
+                    }
+                });
+            if consistency > ahp::ACCEPTABLE_CONSISTENCY {
+                ui.label(
+                    RichText::new(tr_args(
+                        "Your comparisons contradict each other (consistency ratio {ratio}), consider starting over",
+                        &[("ratio", &format!("{:.2}", consistency))],
+                    ))
+                    .color(Color32::YELLOW),
+                );
+            }
+            show_comment(ui, &mut self.comment);
+            ui.horizontal(|ui| {
+                if ui.button(tr("Start over")).clicked() {
+                    self.criteria_judgements.clear();
+                }
+                let submit = ui.button(tr("Submit")).clicked() || shortcut == Some(Shortcut::Submit);
+                if submit && self.download.promise.is_none() {
+                    let data: Vec<CriterionWeight> = criteria
+                        .iter()
+                        .zip(&weights)
+                        .map(|(criterion, weight)| CriterionWeight {
+                            ranking_id: self.ranking.ranking_id,
+                            expert_id: session.user_info.expert_id,
+                            criteria_id: criterion.criteria_id,
+                            weight: *weight,
+                            comment: self.comment.trim().to_string(),
+                        })
+                        .collect();
+                    let url = format!("{}/criteria_weights/{}", base_url, self.ranking.ranking_id);
+                    if let Err(error) = self.download.post_schema(&data, url, ctx, session) {
+                        self.error = error;
+                    }
+                }
+            });
+            show_shortcut_hint(
+                ui,
+                &tr("Keys: Enter submits, Backspace undoes the last comparison"),
+            );
+            ui.label(&self.error);
+
+            let mut got_response = false;
+            self.download
+                .run_when_downloaded(ui, |response, ui| match response.ok {
+                    true => got_response = true,
+                    false => {
+                        ui.label(tr_args(
+                            "Failed, server responded with: {status} {text}",
+                            &[("status", &response.status), ("text", &response.status_text)],
+                        ));
+                    }
+                });
+            if got_response {
+                self.download.promise = None;
+                self.comment.clear();
+                self.rank_mode = RankMode::Finshed;
+            }
+        });
+    }
+}
+
 impl View for RankView {
     fn show(
         &mut self,
@@ -169,13 +506,23 @@ impl View for RankView {
         ctx: &egui::Context,
         base_url: &String,
         session: &Session,
-    ) -> Option<Box<dyn View>> {
-        let mut ret: Option<Box<dyn View>> = None;
+    ) -> Option<Navigation> {
+        let mut ret: Option<Navigation> = None;
         if session.user_info.admin {
-            if ui.button("Back to ranking_list").clicked() {
-                return Some(Box::new(RankingList::default()));
+            if ui.button(tr("Back to ranking_list")).clicked() {
+                return Some(Navigation::Back);
             }
         }
+        if let Some(reason) = self.ranking.state.closed_reason() {
+            ui.vertical_centered(|ui| {
+                ui.heading(&self.ranking.description);
+                ui.label(tr(reason));
+            });
+            return None;
+        }
+        if !self.show_deadline(ui, ctx) {
+            return None;
+        }
         if let Some(alternatives) = self.download_alternatives.deserialize_when_got(ui) {
             self.alternatives_list = Some(alternatives);
         }
@@ -184,127 +531,207 @@ impl View for RankView {
             self.criteria = Some(criteria);
         }
 
-        if let Some(scale) = self.download_scale.deserialize_when_got(ui) {
+        if let Some(mut scale) = self.download_scale.deserialize_when_got::<Vec<Scale>>(ui) {
+            // Least important first, whatever order the server keeps them in
+            scale.sort_by(|a, b| a.value.total_cmp(&b.value));
             self.scale = Some(scale);
         }
 
-        if self.alternatives_list.is_none() || self.criteria.is_none() || self.scale.is_none() {
+        if self.variables.is_none() {
+            self.download_variables
+                .run_when_downloaded(ui, |response, ui| match response.status {
+                    200 => match response.text().map(serde_json::from_str::<Variables>) {
+                        Some(Ok(variables)) => self.variables = Some(variables),
+                        _ => {
+                            ui.label(tr("Failed to parse the variables of the ranking"));
+                        }
+                    },
+                    // Rankings without variables are asked in the default order
+                    404 => self.variables = Some(Variables::default()),
+                    code => {
+                        ui.label(tr_args(
+                            "Failed to get variables, server responded with {code} status code",
+                            &[("code", &code)],
+                        ));
+                    }
+                });
+        }
+
+        if self.alternatives_list.is_none()
+            || self.criteria.is_none()
+            || self.scale.is_none()
+            || self.variables.is_none()
+        {
             ui.spinner();
             return None;
         }
         // Now we know all data is avaiable and can be unwrapped
 
+        let shortcut = Shortcut::pressed(ctx);
         let alternatives = self.alternatives_list.as_ref()?;
-        let criteria = self.criteria.as_ref().unwrap();
+        // In the expert's language, where the admins translated them
+        let criteria: Vec<Criterion> = self
+            .criteria
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|criterion| criterion.localized(language()))
+            .collect();
+        let criteria = &criteria;
         let scale = self.scale.as_ref().unwrap();
 
         if alternatives.len() < 3 {
-            ui.label(format!(
-                "There are only {} alternatives, nothing to compare :(",
-                alternatives.len()
+            ui.label(tr_plural(
+                "There is only {count} alternative, nothing to compare",
+                "There are only {count} alternatives, nothing to compare",
+                alternatives.len() as u64,
+                &[],
             ));
             return None;
         }
 
         if criteria.len() < 2 {
-            ui.label(format!(
-                "There are only {} criteria, nothing to compare:)",
-                criteria.len()
+            ui.label(tr_plural(
+                "There is only {count} criterion, nothing to compare",
+                "There are only {count} criteria, nothing to compare",
+                criteria.len() as u64,
+                &[],
             ));
             return None;
         }
         if scale.len() < 2 {
-            ui.label(format!(
-                "There are only {} scales, nothing to compare:)",
-                scale.len()
+            ui.label(tr_plural(
+                "There is only {count} scale entry, nothing to compare",
+                "There are only {count} scale entries, nothing to compare",
+                scale.len() as u64,
+                &[],
             ));
             return None;
         }
 
         match self.rank_mode {
             RankMode::Alternative => {
-                if self.alternative_combinations.is_none() {
-                    self.alternative_combinations =
-                        Some(Combinations::new(alternatives.clone(), 2));
-                }
-
-                let altenative_combinations = self.alternative_combinations.as_mut().unwrap();
-
-                if self.next_combination {
-                    if altenative_combinations.next_combination(&mut self.current_combination_pair)
-                    {
-                        self.next_combination = false;
-                    } else {
-                        self.current_criterion += 1;
-                        self.alternative_combinations =
-                            Some(Combinations::new(alternatives.clone(), 2));
-                    }
-                }
-                if self.current_criterion >= criteria.len() {
-                    ui.spinner();
-                    ui.label("Not implemented");
+                let variables = self.variables.as_ref().unwrap();
+                let questions = self.questions.get_or_insert_with(|| {
+                    schedule(
+                        alternatives,
+                        &hierarchy::leaves(criteria),
+                        &variables.design,
+                        &variables.presentation,
+                        session.user_info.expert_id,
+                    )
+                });
+                let Some(question) = questions.get(self.current_question) else {
                     self.current_criterion = 0;
-                    self.rank_mode = RankMode::Criterion;
+                    self.rank_mode = match variables.criteria_weighting {
+                        CriteriaWeighting::Pairwise if cfg!(feature = "pairwise-criteria") => {
+                            RankMode::CriteriaPairs
+                        }
+                        _ => RankMode::Criterion,
+                    };
                     return None;
-                    // TODO: go to rank criteria
+                };
+                let idle = self.download.promise.is_none();
+                match shortcut {
+                    Some(Shortcut::Skip) if idle => {
+                        self.current_question += 1;
+                        self.comment.clear();
+                        return None;
+                    }
+                    Some(Shortcut::Undo) if idle && self.current_question > 0 => {
+                        // The backend replaces the earlier answer when the pair is answered again
+                        self.current_question -= 1;
+                        return None;
+                    }
+                    _ => {}
                 }
-
-                let criterion = &criteria[self.current_criterion];
-
-                println!("{:?}", self.current_combination_pair);
-                let alternative_a = &self.current_combination_pair[0];
-                let alternative_b = &self.current_combination_pair[1];
+                // The questions are kept, the language may have changed since
+                let criterion = &question.criterion.localized(language());
+                let alternative_a = &question.left.localized(language());
+                let alternative_b = &question.right.localized(language());
 
                 ui.vertical_centered(|ui| {
-                    ui.heading(format!("Which is better based on: {}", &criterion.name));
+                    ui.heading(tr_args(
+                        "Which is better based on: {criterion}",
+                        &[("criterion", &criterion.name)],
+                    ));
+                    let left = questions.len() - self.current_question;
+                    ui.label(tr_plural(
+                        "{count} comparison left",
+                        "{count} comparisons left",
+                        left as u64,
+                        &[],
+                    ));
                     ui.end_row();
                     ui.spacing();
 
-                    let mut winner_id = None;
+                    // One answer at a time, a held arrow key repeats while the request is in flight
+                    let mut winner_id = match shortcut {
+                        Some(Shortcut::Left) if idle => Some(alternative_a.alternative_id),
+                        Some(Shortcut::Right) if idle => Some(alternative_b.alternative_id),
+                        _ => None,
+                    };
+                    let better = |name: &str| {
+                        tr_args(
+                            "{name} is better based on {criterion}",
+                            &[("name", &name), ("criterion", &criterion.name)],
+                        )
+                    };
                     ui.columns(2, |columns| {
-                        if columns[0].button(&alternative_a.name).clicked() {
+                        let button = columns[0].button(&alternative_a.name);
+                        describe(&button, better(&alternative_a.name));
+                        if button.clicked() && idle {
                             winner_id = Some(alternative_a.alternative_id);
                         }
                         columns[0].end_row();
-                        columns[0].label(&alternative_a.description);
+                        alternative_a.show_details(&mut columns[0]);
 
-                        if columns[1].button(&alternative_b.name).clicked() {
+                        let button = columns[1].button(&alternative_b.name);
+                        describe(&button, better(&alternative_b.name));
+                        if button.clicked() && idle {
                             winner_id = Some(alternative_b.alternative_id);
                         }
                         columns[1].end_row();
-                        columns[1].label(&alternative_b.description);
+                        alternative_b.show_details(&mut columns[1]);
                     });
+                    show_comment(ui, &mut self.comment);
+                    show_shortcut_hint(
+                        ui,
+                        &tr("Keys: ← or A left, → or L right, S skip, Backspace undo"),
+                    );
 
                     if let Some(winner_id) = winner_id {
                         let ab_result = ABInput {
-                            alternativeA_id: alternative_a.alternative_id,
-                            alternativeB_id: alternative_b.alternative_id,
+                            alternative_a_id: alternative_a.alternative_id,
+                            alternative_b_id: alternative_b.alternative_id,
                             winner_id: winner_id,
                             expert_id: session.user_info.expert_id,
                             criteria_id: criterion.criteria_id,
+                            comment: self.comment.trim().to_string(),
+                            answered_at: None,
                         };
-                        // if self.download.promise.is_none() {
-                        if true {
-                            ui.label(&self.error);
-                            let url = format!("{}/rankAB/{}", base_url, self.ranking.ranking_id);
-                            if let Err(error) =
-                                self.download.post_schema(&ab_result, url, ctx, session)
-                            {
-                                self.error = error;
-                            }
+                        let url = format!("{}/rankAB/{}", base_url, self.ranking.ranking_id);
+                        if let Err(error) = self.download.post_schema(&ab_result, url, ctx, session)
+                        {
+                            self.error = error;
                         }
                     }
+                    ui.label(&self.error);
                     let mut got_response = false;
                     self.download
                         .run_when_downloaded(ui, |response, ui| match response.ok {
                             true => {
-                                self.next_combination = true;
+                                self.current_question += 1;
+                                self.comment.clear();
                                 got_response = true;
                             }
                             false => {
-                                ui.label(format!(
-                                    "Failed, server responed with: {} {}",
-                                    response.status, response.status_text
+                                ui.label(tr_args(
+                                    "Failed, server responded with: {status} {text}",
+                                    &[
+                                        ("status", &response.status),
+                                        ("text", &response.status_text),
+                                    ],
                                 ));
                             }
                         });
@@ -314,26 +741,49 @@ impl View for RankView {
                 });
             }
             RankMode::Criterion => {
-                if self.current_criterion >= criteria.len() {
+                // Level by level, so main criteria are rated before their sub-criteria
+                let order = hierarchy::sibling_groups(criteria).concat();
+                let Some(&index) = order.get(self.current_criterion) else {
                     self.rank_mode = RankMode::Finshed;
                     return None;
+                };
+                let criterion = &criteria[index];
+                let idle = self.download.promise.is_none();
+                match shortcut {
+                    Some(Shortcut::Number(n)) if n <= scale.len() => self.scale_ix = n - 1,
+                    Some(Shortcut::Skip) if idle => {
+                        self.current_criterion += 1;
+                        self.comment.clear();
+                        return None;
+                    }
+                    Some(Shortcut::Undo) if idle && self.current_criterion > 0 => {
+                        self.current_criterion -= 1;
+                        return None;
+                    }
+                    _ => {}
                 }
-                let criterion = &criteria[self.current_criterion];
 
                 ui.vertical_centered(|ui| {
-                    ui.heading(&criterion.name);
+                    ui.heading(hierarchy::path(criteria, index));
                     ui.label(&criterion.description);
                     ui.separator();
                     // println!("{:#?}", criterion_choice);
-                    egui::ComboBox::from_label("How important is this criterion?")
+                    egui::ComboBox::from_label(tr("How important is this criterion?"))
                         .selected_text(format!("{}", &scale[self.scale_ix].description))
                         .show_ui(ui, |ui| {
                             for (i, s) in scale.iter().enumerate() {
                                 ui.selectable_value(&mut self.scale_ix, i, &s.description);
                             }
                         });
+                    show_comment(ui, &mut self.comment);
+                    show_shortcut_hint(
+                        ui,
+                        &tr("Keys: 1–9 importance, Enter submit, S skip, Backspace undo"),
+                    );
                     ui.separator();
-                    if ui.button("Submit").clicked() {
+                    let submit =
+                        ui.button(tr("Submit")).clicked() || shortcut == Some(Shortcut::Submit);
+                    if submit && idle {
                         let url = format!("{}/weight/{}", base_url, self.ranking.ranking_id);
                         let data = Weights {
                             criteria_id: criterion.criteria_id,
@@ -341,6 +791,8 @@ impl View for RankView {
                             ranking_id: self.ranking.ranking_id,
                             expert_id: session.user_info.expert_id,
                             scale_id: scale[self.scale_ix].scale_id,
+                            comment: self.comment.trim().to_string(),
+                            answered_at: None,
                         };
 
                         ui.label(&self.error);
@@ -355,12 +807,16 @@ impl View for RankView {
                         .run_when_downloaded(ui, |response, ui| match response.ok {
                             true => {
                                 self.current_criterion += 1;
+                                self.comment.clear();
                                 got_response = true;
                             }
                             false => {
-                                ui.label(format!(
-                                    "Failed, server responed with: {} {}",
-                                    response.status, response.status_text
+                                ui.label(tr_args(
+                                    "Failed, server responded with: {status} {text}",
+                                    &[
+                                        ("status", &response.status),
+                                        ("text", &response.status_text),
+                                    ],
                                 ));
                             }
                         });
@@ -369,11 +825,15 @@ impl View for RankView {
                     }
                 });
             }
+            RankMode::CriteriaPairs => {
+                let criteria = criteria.clone();
+                self.show_criteria_pairs(ui, ctx, base_url, session, &criteria, shortcut);
+            }
             RankMode::Finshed => {
                 ui.centered_and_justified(|ui| {
-                    ui.heading(format!(
-                        "Thank you, {} for taking part in our ranking",
-                        &session.user_info.name
+                    ui.heading(tr_args(
+                        "Thank you, {name}, for taking part in our ranking",
+                        &[("name", &session.user_info.name)],
                     ))
                 });
             }
@@ -487,4 +947,12 @@ impl View for RankView {
         // }
         // self.choice = Some(serde_json::from_slice(json.as_bytes()).unwrap());
     }
+
+    fn title(&self) -> String {
+        self.ranking.description.clone()
+    }
+
+    fn route(&self) -> Option<Route> {
+        Some(Route::Rank(self.ranking.ranking_id))
+    }
 }
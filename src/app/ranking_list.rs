@@ -14,18 +14,33 @@ pub mod ranking_list {
     use poll_promise::Promise;
     use serde::{Deserialize, Serialize};
 
+    use crate::app::accessibility::accessibility::describe;
+    use crate::app::algorithm::algorithm::{AlgorithmRunner, Readiness};
+    use crate::app::api::api;
     use crate::app::data::Data;
     use crate::app::download;
+    use crate::app::experts::experts::ExpertDirectory;
+    use crate::app::hierarchy::hierarchy;
+    use crate::app::i18n::i18n::{tr, tr_args};
+    use crate::app::images::images;
     use crate::app::login::login::{AccessToken, Session};
+    use crate::app::participation::participation::ParticipationView;
     use crate::app::rank::{self, RankView};
+    use crate::app::scale_editor::scale_editor::ScaleEditor;
     use crate::app::schema::schema::{
-        Alternative, Criterion, Expert, Ranking, Scale, Showable, Variables,
+        show_field_errors, Alternative, Criterion, Expert, Participation, Ranking, RankingState,
+        Scale, Showable, Validate, ValidationContext, Variables,
+    };
+    use crate::app::sensitivity::sensitivity::{set_weight, Model, Stretch};
+    #[cfg(feature = "content-updates")]
+    use crate::app::translations::translations::TranslationEditor;
+    use crate::app::{
+        download::download::Download,
+        view::{Navigation, Route, View},
     };
-    use crate::app::{download::download::Download, view::View};
 
     pub struct NewRanking {
         pub ranking: Ranking,
-        pub expiring_date: chrono::naive::NaiveDate,
         pub download: Download,
         pub error: String,
     }
@@ -33,8 +48,7 @@ pub mod ranking_list {
     impl Default for NewRanking {
         fn default() -> Self {
             Self {
-                ranking: Ranking::default(),
-                expiring_date: chrono::NaiveDate::default(),
+                ranking: Ranking::draft("Superheroes ranking".to_string(), 0),
                 download: Download::default(),
                 error: String::new(),
             }
@@ -47,31 +61,27 @@ pub mod ranking_list {
             ctx: &egui::Context,
             base_url: &String,
             session: &Session,
-        ) -> Option<Box<dyn View>> {
-            if ui.button("Back to ranking_list").clicked() {
-                return Some(Box::new(RankingList::default()));
+        ) -> Option<Navigation> {
+            if ui.button(tr("Back to ranking_list")).clicked() {
+                return Some(Navigation::Back);
             }
-            let mut ret: Option<Box<dyn View>> = None;
+            let mut ret: Option<Navigation> = None;
+            let errors = self.ranking.validate(&ValidationContext::now(&[]));
             egui::Grid::new("New ranking grid")
                 .num_columns(2)
                 .show(ui, |ui| {
-                    ui.label("Description: ");
-                    ui.text_edit_singleline(&mut self.ranking.description);
+                    let label = ui.label(tr("Description: "));
+                    ui.text_edit_singleline(&mut self.ranking.description)
+                        .labelled_by(label.id);
+                    show_field_errors(ui, &errors, "description");
                     ui.end_row();
 
-                    ui.label("Expiring: ");
-                    //TODO: implement human readable date
-                    ui.add(
-                        egui::DragValue::new(&mut self.ranking.expiring)
-                            .speed(1000)
-                            .clamp_range(0..=100_000_000),
-                    );
-                    // ui.(&mut self.ranking.desc);
-                    // egui_ex
+                    self.ranking.show_expiring(ui, &errors);
                     ui.end_row();
 
                     ui.label(&self.error);
-                    if ui.button("Submit").clicked() {
+                    let submit = egui::Button::new(tr("Submit"));
+                    if ui.add_enabled(errors.is_empty(), submit).clicked() {
                         let json = serde_json::to_vec(&self.ranking);
                         match json {
                             Ok(json) => {
@@ -97,12 +107,15 @@ pub mod ranking_list {
                             match result {
                                 Ok(response) => match response.ok {
                                     true => {
-                                        ui.label("Success");
-                                        ret = Some(Box::new(EditRanking::new(
-                                            self.ranking.clone(),
-                                            &session,
-                                            &ctx,
-                                            &base_url,
+                                        ui.label(tr("Success"));
+                                        // The server answers with the created ranking,
+                                        // which unlike ours knows its id
+                                        let ranking = response
+                                            .text()
+                                            .and_then(|json| serde_json::from_str(json).ok())
+                                            .unwrap_or_else(|| self.ranking.clone());
+                                        ret = Some(Navigation::Replace(Box::new(
+                                            EditRanking::new(ranking, session, ctx, base_url),
                                         )));
                                     }
                                     false => match response.text() {
@@ -110,7 +123,7 @@ pub mod ranking_list {
                                             ui.label(err);
                                         }
                                         None => {
-                                            ui.label("Unknown error");
+                                            ui.label(tr("Unknown error"));
                                         }
                                     },
                                 },
@@ -136,20 +149,27 @@ pub mod ranking_list {
         fn populate_from_json(&mut self, json: &String) {
             // No-op
         }
+
+        fn title(&self) -> String {
+            tr("New ranking")
+        }
     }
 
     pub struct NewExpert {
         expert: Expert,
         ranking: Ranking,
+        /// E-mails of the experts of the ranking already
+        taken: Vec<String>,
         error: String,
         download: Download,
     }
 
     impl NewExpert {
-        fn new(ranking: Ranking) -> Self {
+        fn new(ranking: Ranking, taken: Vec<String>) -> Self {
             Self {
                 expert: Expert::default(),
                 ranking,
+                taken,
                 error: String::new(),
                 download: Download::default(),
             }
@@ -162,20 +182,26 @@ pub mod ranking_list {
             ctx: &egui::Context,
             base_url: &String,
             session: &Session,
-        ) -> Option<Box<dyn View>> {
-            if ui.button("Back to ranking editing").clicked() {
-                return Some(Box::new(EditRanking::new(
-                    self.ranking.clone(),
-                    session,
-                    ctx,
-                    base_url,
-                )));
-            }
-            let mut ret: Option<Box<dyn View>> = None;
-            self.expert.show_editable(ui, ctx, base_url, session);
+        ) -> Option<Navigation> {
+            let mut ret: Option<Navigation> = None;
+            let errors = self.expert.validate(&ValidationContext::now(&self.taken));
+            self.expert
+                .show_editable(ui, ctx, base_url, session, &errors);
 
             ui.label(&self.error);
-            if ui.button("Submit").clicked() {
+            let (submit, cancel) = ui
+                .horizontal(|ui| {
+                    let submit = egui::Button::new(tr("Submit"));
+                    (
+                        ui.add_enabled(errors.is_empty(), submit).clicked(),
+                        ui.button(tr("Cancel")).clicked(),
+                    )
+                })
+                .inner;
+            if cancel {
+                return Some(Navigation::Back);
+            }
+            if submit {
                 let url = format!("{}/create_expert/{}", base_url, self.ranking.ranking_id);
                 if let Err(error) = self.download.post_schema(&self.expert, url, ctx, session) {
                     self.error = error;
@@ -185,19 +211,14 @@ pub mod ranking_list {
             self.download
                 .run_when_downloaded(ui, |response, ui| match response.ok {
                     true => {
-                        ret = Some(Box::new(EditRanking::new(
-                            self.ranking.clone(),
-                            session,
-                            ctx,
-                            base_url,
-                        )));
+                        ret = Some(Navigation::Back);
                     }
                     false => match response.text() {
                         Some(err) => {
                             ui.label(err);
                         }
                         None => {
-                            ui.label("Unknown error");
+                            ui.label(tr("Unknown error"));
                         }
                     },
                 });
@@ -212,11 +233,18 @@ pub mod ranking_list {
         fn populate_from_json(&mut self, json: &String) {
             // Noop
         }
+
+        fn title(&self) -> String {
+            tr("New expert")
+        }
     }
 
     pub struct NewThing<T> {
         thing: T,
+        title: String,
         ranking: Ranking,
+        /// Names of the others of its kind in the ranking
+        taken: Vec<String>,
         error: String,
         download: Download,
         submit_url_generator: fn(&Ranking, &T, &str) -> String,
@@ -226,10 +254,17 @@ pub mod ranking_list {
     where
         T: Default,
     {
-        fn new(ranking: Ranking, submit_url_generator: fn(&Ranking, &T, &str) -> String) -> Self {
+        fn new(
+            title: String,
+            ranking: Ranking,
+            taken: Vec<String>,
+            submit_url_generator: fn(&Ranking, &T, &str) -> String,
+        ) -> Self {
             Self {
                 thing: T::default(),
+                title,
                 ranking,
+                taken,
                 error: String::new(),
                 download: Download::default(),
                 submit_url_generator,
@@ -239,7 +274,7 @@ pub mod ranking_list {
 
     impl<T> View for NewThing<T>
     where
-        T: Serialize + Showable,
+        T: Serialize + Showable + Validate,
     {
         fn show(
             &mut self,
@@ -247,20 +282,26 @@ pub mod ranking_list {
             ctx: &egui::Context,
             base_url: &String,
             session: &Session,
-        ) -> Option<Box<dyn View>> {
-            if ui.button("Back to ranking editing").clicked() {
-                return Some(Box::new(EditRanking::new(
-                    self.ranking.clone(),
-                    session,
-                    ctx,
-                    base_url,
-                )));
-            }
-            let mut ret: Option<Box<dyn View>> = None;
-            self.thing.show_editable(ui, ctx, base_url, session);
+        ) -> Option<Navigation> {
+            let mut ret: Option<Navigation> = None;
+            let errors = self.thing.validate(&ValidationContext::now(&self.taken));
+            self.thing
+                .show_editable(ui, ctx, base_url, session, &errors);
             ui.label(&self.error);
 
-            if ui.button("Submit").clicked() {
+            let (submit, cancel) = ui
+                .horizontal(|ui| {
+                    let submit = egui::Button::new(tr("Submit"));
+                    (
+                        ui.add_enabled(errors.is_empty(), submit).clicked(),
+                        ui.button(tr("Cancel")).clicked(),
+                    )
+                })
+                .inner;
+            if cancel {
+                return Some(Navigation::Back);
+            }
+            if submit {
                 let url = (self.submit_url_generator)(&self.ranking, &self.thing, &base_url);
                 if let Err(error) = self.download.post_schema(&self.thing, url, ctx, session) {
                     self.error = error;
@@ -270,19 +311,14 @@ pub mod ranking_list {
             self.download
                 .run_when_downloaded(ui, |response, ui| match response.ok {
                     true => {
-                        ret = Some(Box::new(EditRanking::new(
-                            self.ranking.clone(),
-                            session,
-                            ctx,
-                            base_url,
-                        )));
+                        ret = Some(Navigation::Back);
                     }
                     false => match response.text() {
                         Some(err) => {
                             ui.label(err);
                         }
                         None => {
-                            ui.label("Unknown error");
+                            ui.label(tr("Unknown error"));
                         }
                     },
                 });
@@ -296,22 +332,83 @@ pub mod ranking_list {
         fn populate_from_json(&mut self, json: &String) {
             // noop
         }
+
+        fn title(&self) -> String {
+            self.title.clone()
+        }
     }
 
+    /// Biggest picture that can be uploaded, as it is stored inside the
+    /// alternative. The README's backend contract depends on it.
+    const MAX_IMAGE_BYTES: usize = 512 * 1024;
+
     pub struct NewAlternative {
         alternative: Alternative,
         ranking: Ranking,
+        /// Names of the alternatives of the ranking already
+        taken: Vec<String>,
         error: String,
         download: Download,
+        /// Picked picture as a `data:` URI, or why it can't be used
+        upload: Option<Receiver<Result<String, String>>>,
     }
 
     impl NewAlternative {
-        fn new(ranking: Ranking) -> Self {
+        fn new(ranking: Ranking, taken: Vec<String>) -> Self {
             Self {
                 alternative: Alternative::default(),
                 ranking,
+                taken,
                 error: String::new(),
                 download: Download::default(),
+                upload: None,
+            }
+        }
+
+        fn show_upload(&mut self, ui: &mut Ui, ctx: &egui::Context) {
+            if let Some(upload) = &self.upload {
+                match upload.try_recv() {
+                    Ok(Ok(uri)) => {
+                        self.alternative.image_url = uri;
+                        self.error.clear();
+                        self.upload = None;
+                    }
+                    Ok(Err(error)) => {
+                        self.error = error;
+                        self.upload = None;
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Empty) => {
+                        ui.spinner();
+                    }
+                    Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                        self.upload = None;
+                    }
+                }
+            }
+            if self.upload.is_none() && ui.button(tr("Upload image")).clicked() {
+                let (sender, receiver) = channel();
+                self.upload = Some(receiver);
+                let task = rfd::AsyncFileDialog::new()
+                    .add_filter("PNG", &["png"])
+                    .pick_file();
+                let ctx = ctx.clone();
+                // The language is set per thread, the message can't be translated in the task
+                let too_big = tr_args(
+                    "The picture is too big, at most {size} KiB are allowed",
+                    &[("size", &(MAX_IMAGE_BYTES / 1024))],
+                );
+                execute(async move {
+                    if let Some(file) = task.await {
+                        let bytes = file.read().await;
+                        let picture = if bytes.len() > MAX_IMAGE_BYTES {
+                            Err(too_big)
+                        } else {
+                            Ok(images::data_uri("image/png", &bytes))
+                        };
+                        _ = sender.send(picture);
+                    }
+                    ctx.request_repaint();
+                });
             }
         }
     }
@@ -323,20 +420,29 @@ pub mod ranking_list {
             ctx: &egui::Context,
             base_url: &String,
             session: &Session,
-        ) -> Option<Box<dyn View>> {
-            if ui.button("Back to ranking editing").clicked() {
-                return Some(Box::new(EditRanking::new(
-                    self.ranking.clone(),
-                    session,
-                    ctx,
-                    base_url,
-                )));
-            }
-            let mut ret: Option<Box<dyn View>> = None;
-            self.alternative.show_editable(ui, ctx, base_url, session);
+        ) -> Option<Navigation> {
+            let mut ret: Option<Navigation> = None;
+            let errors = self
+                .alternative
+                .validate(&ValidationContext::now(&self.taken));
+            self.alternative
+                .show_editable(ui, ctx, base_url, session, &errors);
+            self.show_upload(ui, ctx);
             ui.label(&self.error);
 
-            if ui.button("Submit").clicked() {
+            let (submit, cancel) = ui
+                .horizontal(|ui| {
+                    let submit = egui::Button::new(tr("Submit"));
+                    (
+                        ui.add_enabled(errors.is_empty(), submit).clicked(),
+                        ui.button(tr("Cancel")).clicked(),
+                    )
+                })
+                .inner;
+            if cancel {
+                return Some(Navigation::Back);
+            }
+            if submit {
                 let url = format!(
                     "{}/create_alternative/{}",
                     base_url, self.ranking.ranking_id
@@ -352,19 +458,14 @@ pub mod ranking_list {
             self.download
                 .run_when_downloaded(ui, |response, ui| match response.ok {
                     true => {
-                        ret = Some(Box::new(EditRanking::new(
-                            self.ranking.clone(),
-                            session,
-                            ctx,
-                            base_url,
-                        )));
+                        ret = Some(Navigation::Back);
                     }
                     false => match response.text() {
                         Some(err) => {
                             ui.label(err);
                         }
                         None => {
-                            ui.label("Unknown error");
+                            ui.label(tr("Unknown error"));
                         }
                     },
                 });
@@ -378,12 +479,38 @@ pub mod ranking_list {
         fn populate_from_json(&mut self, json: &String) {
             // No-op
         }
+
+        fn title(&self) -> String {
+            tr("New alternative")
+        }
+    }
+
+    /// Justification an expert gave with one of their answers.
+    struct Comment {
+        /// "A vs B" for comparisons, `None` for criteria importance
+        pair: Option<String>,
+        criterion: String,
+        expert: String,
+        /// What the expert answered, like "Superman is better"
+        answer: String,
+        text: String,
+    }
+
+    #[derive(PartialEq)]
+    enum CommentGrouping {
+        Pair,
+        Criterion,
     }
 
     pub struct DownloadResults {
         ranking_id: i64,
         download: Download,
         results: String,
+        /// Criteria of the ranking and their global weights, when the results have them
+        criteria_weights: Option<(Vec<Criterion>, Vec<f64>)>,
+        comments: Vec<Comment>,
+        comment_grouping: CommentGrouping,
+        sensitivity: Option<Model>,
     }
 
     impl DownloadResults {
@@ -392,8 +519,187 @@ pub mod ranking_list {
                 ranking_id,
                 download: Download::default(),
                 results: String::new(),
+                criteria_weights: None,
+                comments: Vec::new(),
+                comment_grouping: CommentGrouping::Pair,
+                sensitivity: None,
             }
         }
+
+        /// Comments left with the comparisons and criteria weights of the export.
+        fn parse_comments(results: &str) -> Vec<Comment> {
+            let Ok(export) = serde_json::from_str::<serde_json::Value>(results) else {
+                return Vec::new();
+            };
+            let list = |key: &str| export[key].as_array().cloned().unwrap_or_default();
+            let name_of = |key: &str, id_key: &str, id: Option<i64>, name_key: &str| {
+                list(key)
+                    .iter()
+                    .find(|item| item[id_key].as_i64() == id)
+                    .and_then(|item| item[name_key].as_str().map(str::to_string))
+                    .unwrap_or_else(|| format!("#{}", id.unwrap_or_default()))
+            };
+            let alternative = |id| name_of("alternatives", "alternative_id", id, "name");
+            let criterion = |id| name_of("criteria", "criteria_id", id, "name");
+            let expert = |id| name_of("experts", "expert_id", id, "name");
+
+            let mut comments = Vec::new();
+            for comparison in list("comparisons") {
+                let text = comparison["comment"].as_str().unwrap_or_default();
+                if text.is_empty() {
+                    continue;
+                }
+                let (a, b) = (
+                    comparison["alternativeA_id"].as_i64(),
+                    comparison["alternativeB_id"].as_i64(),
+                );
+                // The same pair whichever side each alternative was shown on
+                let (first, second) = (a.min(b), a.max(b));
+                comments.push(Comment {
+                    pair: Some(format!("{} vs {}", alternative(first), alternative(second))),
+                    criterion: criterion(comparison["criteria_id"].as_i64()),
+                    expert: expert(comparison["expert_id"].as_i64()),
+                    answer: tr_args(
+                        "{name} is better",
+                        &[("name", &alternative(comparison["winner_id"].as_i64()))],
+                    ),
+                    text: text.to_string(),
+                });
+            }
+            for weight in list("weights") {
+                let text = weight["comment"].as_str().unwrap_or_default();
+                if text.is_empty() {
+                    continue;
+                }
+                comments.push(Comment {
+                    pair: None,
+                    criterion: criterion(weight["criteria_id"].as_i64()),
+                    expert: expert(weight["expert_id"].as_i64()),
+                    answer: name_of(
+                        "scale",
+                        "scale_id",
+                        weight["scale_id"].as_i64(),
+                        "description",
+                    ),
+                    text: text.to_string(),
+                });
+            }
+            for weight in list("criteria_weights") {
+                let text = weight["comment"].as_str().unwrap_or_default();
+                if text.is_empty() {
+                    continue;
+                }
+                comments.push(Comment {
+                    pair: None,
+                    criterion: criterion(weight["criteria_id"].as_i64()),
+                    expert: expert(weight["expert_id"].as_i64()),
+                    answer: format!(
+                        "{:.1}%",
+                        weight["weight"].as_f64().unwrap_or_default() * 100.0
+                    ),
+                    text: text.to_string(),
+                });
+            }
+            comments
+        }
+
+        fn show_comments(&mut self, ui: &mut Ui) {
+            if self.comments.is_empty() {
+                return;
+            }
+            ui.heading(tr("Comments"));
+            ui.horizontal(|ui| {
+                ui.radio_value(
+                    &mut self.comment_grouping,
+                    CommentGrouping::Pair,
+                    tr("By pair"),
+                );
+                ui.radio_value(
+                    &mut self.comment_grouping,
+                    CommentGrouping::Criterion,
+                    tr("By criterion"),
+                );
+            });
+            let mut groups: std::collections::BTreeMap<String, Vec<&Comment>> = Default::default();
+            for comment in &self.comments {
+                let group = match (&self.comment_grouping, &comment.pair) {
+                    (CommentGrouping::Pair, Some(pair)) => pair.clone(),
+                    (CommentGrouping::Pair, None) => tr_args(
+                        "Importance of {criterion}",
+                        &[("criterion", &comment.criterion)],
+                    ),
+                    (CommentGrouping::Criterion, _) => comment.criterion.clone(),
+                };
+                groups.entry(group).or_default().push(comment);
+            }
+            for (group, comments) in groups {
+                egui::CollapsingHeader::new(format!("{} ({})", group, comments.len()))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        for comment in comments {
+                            let context = match (&self.comment_grouping, &comment.pair) {
+                                (CommentGrouping::Pair, Some(_)) => comment.criterion.clone(),
+                                (CommentGrouping::Criterion, Some(pair)) => pair.clone(),
+                                (_, None) => tr("Importance"),
+                            };
+                            ui.label(format!(
+                                "{} ({}: {}): {}",
+                                comment.expert, context, comment.answer, comment.text
+                            ));
+                        }
+                    });
+            }
+            ui.separator();
+        }
+
+        fn parse_criteria_weights(results: &str) -> Option<(Vec<Criterion>, Vec<f64>)> {
+            let export: serde_json::Value = serde_json::from_str(results).ok()?;
+            let criteria: Vec<Criterion> =
+                serde_json::from_value(export["criteria"].clone()).ok()?;
+            let weights = export["results"]["criteria_weights"].as_array()?;
+            let global = criteria
+                .iter()
+                .map(|criterion| {
+                    weights
+                        .iter()
+                        .find(|w| w["criteria_id"].as_i64() == Some(criterion.criteria_id))
+                        .and_then(|w| w["weight"].as_f64())
+                        .unwrap_or(0.0)
+                })
+                .collect();
+            Some((criteria, global))
+        }
+
+        fn show_criteria_weights(&self, ui: &mut Ui) {
+            let Some((criteria, global)) = &self.criteria_weights else {
+                return;
+            };
+            ui.heading(tr("Criteria weights"));
+            egui::Grid::new("Result criteria weights")
+                .striped(true)
+                .num_columns(3)
+                .show(ui, |ui| {
+                    ui.strong(tr("Criterion"));
+                    ui.strong(tr("Local"));
+                    ui.strong(tr("Global"));
+                    ui.end_row();
+                    for (index, depth) in hierarchy::tree_order(criteria) {
+                        ui.horizontal(|ui| {
+                            ui.add_space(depth as f32 * 16.0);
+                            ui.label(&criteria[index].name);
+                        });
+                        // Share of the parent's weight
+                        let local = match hierarchy::parent(criteria, index) {
+                            Some(parent) if global[parent] > 0.0 => global[index] / global[parent],
+                            _ => global[index],
+                        };
+                        ui.label(format!("{:.1}%", local * 100.0));
+                        ui.label(format!("{:.1}%", global[index] * 100.0));
+                        ui.end_row();
+                    }
+                });
+            ui.separator();
+        }
     }
     #[cfg(not(target_arch = "wasm32"))]
     fn execute<F: std::future::Future<Output = ()> + Send + 'static>(f: F) {
@@ -413,13 +719,13 @@ pub mod ranking_list {
             ctx: &egui::Context,
             base_url: &String,
             session: &Session,
-        ) -> Option<Box<dyn View>> {
-            if ui.button("Back to ranking_list").clicked() {
-                return Some(Box::new(RankingList::default()));
+        ) -> Option<Navigation> {
+            if ui.button(tr("Back to ranking_list")).clicked() {
+                return Some(Navigation::Back);
             }
-            let ret: Option<Box<dyn View>> = None;
+            let mut ret: Option<Navigation> = None;
 
-            if ui.button("Download").clicked() {
+            if ui.button(tr("Download")).clicked() {
                 let task = rfd::AsyncFileDialog::new().set_file_name(format!("Results_{}.json", self.ranking_id)).save_file();
                 let contents = self.results.clone();
                 execute(async move {
@@ -429,6 +735,16 @@ pub mod ranking_list {
                     }
                 });
             }
+            let sensitivity = ui.add_enabled(
+                self.sensitivity.is_some(),
+                egui::Button::new(tr("Sensitivity analysis")),
+            );
+            if let (true, Some(model)) = (sensitivity.clicked(), &self.sensitivity) {
+                ret = Some(Navigation::Push(Box::new(SensitivityView::new(
+                    self.ranking_id,
+                    model.clone(),
+                ))));
+            }
 
             if self.download.promise.is_none() {
                 let url = format!("{}/export_data/{}", base_url, self.ranking_id);
@@ -440,13 +756,22 @@ pub mod ranking_list {
             self.download
                 .run_when_downloaded(ui, |response, ui| match response.text() {
                     Some(text) => {
+                        if self.results != text {
+                            self.criteria_weights = Self::parse_criteria_weights(text);
+                            self.comments = Self::parse_comments(text);
+                            self.sensitivity = Model::from_export(text);
+                        }
                         self.results = text.to_string();
                     }
                     None => {
-                        ui.label("Failed to download data");
+                        ui.label(tr("Failed to download data"));
                     }
                 });
-            egui::Label::new(&self.results).wrap(true).ui(ui);
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                self.show_criteria_weights(ui);
+                self.show_comments(ui);
+                egui::Label::new(&self.results).wrap(true).ui(ui);
+            });
             ret
         }
 
@@ -458,121 +783,770 @@ pub mod ranking_list {
         fn populate_from_json(&mut self, _json: &String) {
             // Nothing
         }
+
+        fn title(&self) -> String {
+            tr_args("Results of ranking {id}", &[("id", &self.ranking_id)])
+        }
+
+        fn route(&self) -> Option<Route> {
+            Some(Route::Results(self.ranking_id))
+        }
+    }
+
+    /// Sliders for the weights of the criteria from the results, showing how
+    /// the alternatives would score with other weights and from what weight
+    /// another alternative would win.
+    struct SensitivityView {
+        ranking_id: i64,
+        model: Model,
+        weights: Vec<f64>,
+    }
+
+    impl SensitivityView {
+        fn new(ranking_id: i64, model: Model) -> Self {
+            Self {
+                ranking_id,
+                weights: model.weights.clone(),
+                model,
+            }
+        }
+
+        /// The weight slider of `criterion` with a mark wherever the leader changes.
+        fn show_slider(&mut self, ui: &mut Ui, criterion: usize, stretches: &[Stretch]) {
+            let mut percent = self.weights[criterion] * 100.0;
+            let response = ui.add(
+                egui::Slider::new(&mut percent, 0.0..=100.0)
+                    .step_by(0.1)
+                    .suffix("%")
+                    .text(&self.model.criteria[criterion]),
+            );
+            if response.changed() {
+                self.weights = set_weight(&self.weights, criterion, percent / 100.0);
+            }
+
+            // The rail is the first slider_width of the widget, the handle
+            // stops half its size before both ends
+            let rect = response.rect;
+            let handle_radius = rect.height() / 2.5;
+            let rail = egui::Rangef::new(
+                rect.left() + handle_radius,
+                rect.left() + ui.spacing().slider_width - handle_radius,
+            );
+            let stroke = egui::Stroke::new(2.0, ui.visuals().warn_fg_color);
+            for stretch in &stretches[1..] {
+                let x = rail.min + stretch.from as f32 * rail.span();
+                ui.painter().vline(x, rect.y_range(), stroke);
+            }
+        }
+
+        /// Like "0–54.5%: Batman, 54.5–100%: Superman".
+        fn describe_stretches(&self, stretches: &[Stretch]) -> String {
+            if let [only] = stretches {
+                return tr_args(
+                    "{alternative} wins at any weight",
+                    &[("alternative", &self.model.alternatives[only.leader])],
+                );
+            }
+            stretches
+                .iter()
+                .map(|stretch| {
+                    format!(
+                        "{}–{}%: {}",
+                        percent(stretch.from),
+                        percent(stretch.to),
+                        self.model.alternatives[stretch.leader]
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+    }
+
+    /// 0.545 as "54.5", without the decimals of whole numbers.
+    fn percent(fraction: f64) -> String {
+        let text = format!("{:.1}", fraction * 100.0);
+        text.strip_suffix(".0").map(str::to_string).unwrap_or(text)
+    }
+
+    impl View for SensitivityView {
+        fn show(
+            &mut self,
+            ui: &mut Ui,
+            _ctx: &egui::Context,
+            _base_url: &String,
+            _session: &Session,
+        ) -> Option<Navigation> {
+            if ui.button(tr("Back to results")).clicked() {
+                return Some(Navigation::Back);
+            }
+            ui.label(tr(
+                "Move the weight of a criterion to see whether the winner changes, \
+                 the other criteria keep their proportions. Marks show where another \
+                 alternative takes the lead.",
+            ));
+
+            egui::Grid::new("Sensitivity weights")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for criterion in 0..self.model.criteria.len() {
+                        let stretches = self.model.leaders(&self.weights, criterion);
+                        self.show_slider(ui, criterion, &stretches);
+                        ui.label(self.describe_stretches(&stretches));
+                        ui.end_row();
+                    }
+                });
+            if ui.button(tr("Reset weights")).clicked() {
+                self.weights = self.model.weights.clone();
+            }
+            ui.separator();
+
+            let totals = self.model.totals(&self.weights);
+            let computed = self.model.totals(&self.model.weights);
+            let mut order: Vec<usize> = (0..totals.len()).collect();
+            order.sort_by(|a, b| totals[*b].total_cmp(&totals[*a]));
+            egui::Grid::new("Sensitivity scores")
+                .striped(true)
+                .num_columns(3)
+                .show(ui, |ui| {
+                    ui.strong(tr("Alternative"));
+                    ui.strong(tr("Score"));
+                    ui.strong(tr("With the computed weights"));
+                    ui.end_row();
+                    for (place, alternative) in order.into_iter().enumerate() {
+                        let name = &self.model.alternatives[alternative];
+                        match place {
+                            0 => ui.strong(name),
+                            _ => ui.label(name),
+                        };
+                        ui.label(format!("{}%", percent(totals[alternative])));
+                        ui.label(format!("{}%", percent(computed[alternative])));
+                        ui.end_row();
+                    }
+                });
+            None
+        }
+
+        fn get_request(&self, _base_url: &String, _session: &Session) -> Option<Request> {
+            None
+        }
+
+        fn populate_from_json(&mut self, _json: &String) {}
+
+        fn title(&self) -> String {
+            tr_args("Sensitivity of ranking {id}", &[("id", &self.ranking_id)])
+        }
+    }
+
+    /// Tabs of the ranking editor, one per part of the ranking.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Section {
+        Parameters,
+        Variables,
+        Alternatives,
+        Experts,
+        Criteria,
+        Scale,
+        #[cfg(feature = "content-updates")]
+        Translations,
+    }
+
+    impl Section {
+        const ALL: &'static [Section] = &[
+            Section::Parameters,
+            Section::Variables,
+            Section::Alternatives,
+            Section::Experts,
+            Section::Criteria,
+            Section::Scale,
+            #[cfg(feature = "content-updates")]
+            Section::Translations,
+        ];
+
+        fn name(&self) -> &'static str {
+            match self {
+                Section::Parameters => "Parameters",
+                Section::Variables => "Variables",
+                Section::Alternatives => "Alternatives",
+                Section::Experts => "Experts",
+                Section::Criteria => "Criteria",
+                Section::Scale => "Scale",
+                #[cfg(feature = "content-updates")]
+                Section::Translations => "Translations",
+            }
+        }
     }
 
     pub struct EditRanking {
         ranking: Ranking,
+        section: Section,
+        /// Creation form shown in a window over the current section
+        form: Option<Box<dyn View>>,
+        /// All rankings, for switching to another one from the side panel
+        rankings: Option<Vec<Ranking>>,
         experts_list: Option<Vec<Expert>>,
         alternatives_list: Option<Vec<Alternative>>,
         variables: Option<Variables>,
         criteria: Option<Vec<Criterion>>,
         scale: Option<Vec<Scale>>,
-        // TODO:
-        // Scale
         error: String,
-        download: Download,
 
+        download_rankings: Download,
         download_variables: Download,
         download_alternatives: Download,
         download_experts: Download,
         download_criteria: Download,
         download_scale: Download,
-        download_trigger_algorithm: Download,
-        // alternative_list: Vec<>
+        download_participation: Download,
+        participation: Option<Vec<Participation>>,
+        algorithm: AlgorithmRunner,
+        #[cfg(feature = "content-updates")]
+        translations: TranslationEditor,
+        scale_editor: ScaleEditor,
     }
 
     impl EditRanking {
         fn new(ranking: Ranking, session: &Session, ctx: &Context, base_url: &String) -> Self {
+            let ranking_id = ranking.ranking_id;
             let mut edit = EditRanking {
-                ranking: ranking.clone(),
+                ranking,
+                section: Section::Parameters,
+                form: None,
+                rankings: None,
                 experts_list: None,
                 alternatives_list: None,
                 variables: None,
                 criteria: None,
                 scale: None,
                 error: String::new(),
-                download: Download::default(),
+                download_rankings: Download::default(),
                 download_variables: Download::default(),
                 download_alternatives: Download::default(),
                 download_experts: Download::default(),
                 download_criteria: Download::default(),
                 download_scale: Download::default(),
-                download_trigger_algorithm: Download::default(),
+                download_participation: Download::default(),
+                participation: None,
+                algorithm: AlgorithmRunner::new(ranking_id),
+                #[cfg(feature = "content-updates")]
+                translations: TranslationEditor::new(ranking_id),
+                scale_editor: ScaleEditor::new(ranking_id),
             };
+            edit.download_all(session, ctx, base_url);
+            edit
+        }
+
+        /// (Re)downloads every section of the ranking.
+        fn download_all(&mut self, session: &Session, ctx: &Context, base_url: &String) {
+            for &section in Section::ALL {
+                self.download_section(section, session, ctx, base_url);
+            }
+        }
+
+        /// (Re)downloads what the forms of `section` change. The scale and
+        /// translation drafts survive a download with the same entries, the
+        /// variables are replaced.
+        fn download_section(
+            &mut self,
+            section: Section,
+            session: &Session,
+            ctx: &Context,
+            base_url: &String,
+        ) {
+            let ranking_id = self.ranking.ranking_id;
             let downloader_utility = |middle_url: &str| -> Request {
-                let url = format!("{}/{}/{}", &base_url, middle_url, &ranking.ranking_id);
+                let url = format!("{}/{}/{}", &base_url, middle_url, ranking_id);
                 let request = Request::get(url);
                 session.access_token.add_authorization_header(request)
             };
-            edit.download_variables
-                .download(ctx, downloader_utility("variables"));
+            match section {
+                Section::Parameters => {
+                    self.rankings = None;
+                    self.download_rankings.get_schema(
+                        format!("{}/all_rankings", base_url),
+                        ctx,
+                        session,
+                    );
+                }
+                Section::Variables => {
+                    self.variables = None;
+                    self.download_variables
+                        .download(ctx, downloader_utility("variables"));
+                }
+                Section::Alternatives => {
+                    self.alternatives_list = None;
+                    self.download_alternatives
+                        .download(ctx, downloader_utility("all_alternatives"));
+                }
+                Section::Experts => {
+                    // Participation is counted for the experts of the ranking
+                    self.experts_list = None;
+                    self.participation = None;
+                    self.download_experts
+                        .download(ctx, downloader_utility("experts"));
+                    self.download_participation
+                        .download(ctx, downloader_utility("export_data"));
+                }
+                Section::Criteria => {
+                    self.criteria = None;
+                    self.download_criteria
+                        .download(ctx, downloader_utility("criteria"));
+                }
+                Section::Scale => {
+                    self.scale = None;
+                    self.download_scale
+                        .download(ctx, downloader_utility("get_scale"));
+                }
+                // Translations are saved with the alternatives and criteria
+                #[cfg(feature = "content-updates")]
+                Section::Translations => {}
+            }
+        }
 
-            edit.download_alternatives
-                .download(ctx, downloader_utility("all_alternatives"));
+        /// Takes whatever finished downloading, whichever tab is open: the
+        /// readiness of the ranking and the translations need the other
+        /// sections too. Failures are left for the tab of the section to show.
+        fn receive_downloads(&mut self) {
+            receive(&mut self.rankings, &mut self.download_rankings);
+            receive(&mut self.alternatives_list, &mut self.download_alternatives);
+            receive(&mut self.experts_list, &mut self.download_experts);
+            receive(&mut self.criteria, &mut self.download_criteria);
+            receive(&mut self.scale, &mut self.download_scale);
+            receive_with(
+                &mut self.participation,
+                &mut self.download_participation,
+                Participation::from_export,
+            );
+            if self.variables.is_none() {
+                let missing = self
+                    .download_variables
+                    .promise
+                    .as_ref()
+                    .and_then(|promise| promise.ready())
+                    .and_then(|result| result.as_ref().ok())
+                    .map_or(false, |response| response.status == 404);
+                if missing {
+                    self.variables = Some(Variables::default());
+                    self.download_variables.promise = None;
+                }
+                receive(&mut self.variables, &mut self.download_variables);
+                if let Some(variables) = &mut self.variables {
+                    variables.exists_in_ranking = !missing;
+                }
+            }
+        }
 
-            edit.download_experts
-                .download(ctx, downloader_utility("experts"));
+        /// The other rankings, a click opens one of them in the editor instead.
+        fn show_rankings(
+            &self,
+            ui: &mut Ui,
+            ctx: &egui::Context,
+            base_url: &String,
+            session: &Session,
+        ) -> Option<Navigation> {
+            ui.heading(tr("Rankings"));
+            let Some(rankings) = &self.rankings else {
+                ui.spinner();
+                return None;
+            };
+            let mut ret: Option<Navigation> = None;
+            for ranking in rankings {
+                let current = ranking.ranking_id == self.ranking.ranking_id;
+                if ui.selectable_label(current, &ranking.description).clicked() && !current {
+                    ret = Some(Navigation::Replace(Box::new(EditRanking::new(
+                        ranking.clone(),
+                        session,
+                        ctx,
+                        base_url,
+                    ))));
+                }
+            }
+            ret
+        }
 
-            edit.download_criteria
-                .download(ctx, downloader_utility("criteria"));
+        fn show_parameters(
+            &mut self,
+            ui: &mut Ui,
+            ctx: &egui::Context,
+            base_url: &String,
+            session: &Session,
+        ) -> Option<Navigation> {
+            let mut ret: Option<Navigation> = None;
+            if ui.button(tr("Participation")).clicked() {
+                ret = Some(Navigation::Push(Box::new(ParticipationView::new(
+                    self.ranking.clone(),
+                ))));
+            }
+            let readiness = Readiness {
+                variables: self.variables.as_ref(),
+                alternatives: self.alternatives_list.as_ref().map(Vec::len),
+                criteria: self.criteria.as_ref().map(Vec::len),
+                leaf_criteria: self
+                    .criteria
+                    .as_ref()
+                    .map(|criteria| hierarchy::leaves(criteria).len()),
+                scales: self.scale.as_ref().map(Vec::len),
+                participation: self.participation.as_deref(),
+            };
+            if let Some(navigation) = self.algorithm.show(ui, ctx, base_url, session, &readiness) {
+                ret = Some(navigation);
+            }
 
-            edit.download_scale
-                .download(ctx, downloader_utility("get_scale"));
+            self.ranking.show_editable(ui, ctx, base_url, session);
+            ret
+        }
 
-            edit
+        fn show_variables(
+            &mut self,
+            ui: &mut Ui,
+            ctx: &egui::Context,
+            base_url: &String,
+            session: &Session,
+        ) {
+            if let Some(variables) = &mut self.variables {
+                variables.show(ui, ctx, base_url, session, self.ranking.ranking_id);
+            } else {
+                self.download_variables
+                    .run_when_downloaded(ui, |response, ui| match response.text() {
+                        Some(json) if response.ok => {
+                            if let Err(error) = serde_json::from_str::<Variables>(json) {
+                                ui.label(tr_args(
+                                    "Failed to parse response due to: {error}",
+                                    &[("error", &error)],
+                                ));
+                            }
+                        }
+                        _ => {
+                            ui.label(tr_args(
+                                "Failed to get variables, server responded with {status} status code",
+                                &[("status", &response.status)],
+                            ));
+                        }
+                    });
+            }
+        }
+
+        fn show_alternatives(
+            &mut self,
+            ui: &mut Ui,
+            ctx: &egui::Context,
+            base_url: &String,
+            session: &Session,
+        ) {
+            if ui.button(tr("Create new alternative")).clicked() {
+                let taken = names(&self.alternatives_list, |a| a.name.clone());
+                self.form = Some(Box::new(NewAlternative::new(self.ranking.clone(), taken)));
+            }
+            if let Some(alternatives_list) = &self.alternatives_list {
+                if alternatives_list.is_empty() {
+                    ui.label(tr("No alternatives!"));
+                } else {
+                    egui::Grid::new("Alternatives")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for alternative in alternatives_list {
+                                alternative.show(ui, &ctx, &base_url, &session);
+                                let delete = ui.button(tr("Delete alternative"));
+                                describe(
+                                    &delete,
+                                    tr_args("Delete {name}", &[("name", &alternative.name)]),
+                                );
+                                if delete.clicked() {
+                                    let url = format!(
+                                        "{}/alternative/{}/{}",
+                                        base_url,
+                                        self.ranking.ranking_id,
+                                        alternative.alternative_id
+                                    );
+                                    self.download_alternatives.delete_schema(url, ctx, session);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                }
+            }
+            let url = format!("{}/all_alternatives/{}", base_url, self.ranking.ranking_id);
+            sync_section_list(
+                ui,
+                ctx,
+                session,
+                &mut self.alternatives_list,
+                &mut self.download_alternatives,
+                url,
+            );
+        }
+
+        fn show_experts(
+            &mut self,
+            ui: &mut Ui,
+            ctx: &egui::Context,
+            base_url: &String,
+            session: &Session,
+        ) {
+            if ui.button(tr("Create new expert")).clicked() {
+                let taken = names(&self.experts_list, |e| e.email.clone());
+                self.form = Some(Box::new(NewExpert::new(self.ranking.clone(), taken)));
+            }
+            if let Some(experts_list) = &self.experts_list {
+                if experts_list.is_empty() {
+                    ui.label(tr("No experts!"));
+                } else {
+                    egui::Grid::new("Experts").striped(true).show(ui, |ui| {
+                        for expert in experts_list {
+                            expert.show(ui, &ctx, &base_url, &session);
+                            let delete = ui.button(tr("Delete expert"));
+                            describe(
+                                &delete,
+                                tr_args("Delete expert {name}", &[("name", &expert.name)]),
+                            );
+                            if delete.clicked() {
+                                let url = format!(
+                                    "{}/experts/{}/{}",
+                                    base_url, self.ranking.ranking_id, expert.expert_id
+                                );
+                                self.download_experts.delete_schema(url, ctx, session);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                }
+            }
+            let url = format!("{}/experts/{}", base_url, self.ranking.ranking_id);
+            sync_section_list(
+                ui,
+                ctx,
+                session,
+                &mut self.experts_list,
+                &mut self.download_experts,
+                url,
+            );
+        }
+
+        fn show_scale(
+            &mut self,
+            ui: &mut Ui,
+            ctx: &egui::Context,
+            base_url: &String,
+            session: &Session,
+        ) {
+            let saved = self
+                .scale_editor
+                .show(ui, ctx, base_url, session, self.scale.as_ref());
+            if saved {
+                self.scale = None;
+                self.download_scale.get_schema(
+                    format!("{}/get_scale/{}", base_url, self.ranking.ranking_id),
+                    ctx,
+                    session,
+                );
+            }
+        }
+
+        #[cfg(feature = "content-updates")]
+        fn show_translations(
+            &mut self,
+            ui: &mut Ui,
+            ctx: &egui::Context,
+            base_url: &String,
+            session: &Session,
+        ) {
+            let saved = self.translations.show(
+                ui,
+                ctx,
+                base_url,
+                session,
+                self.alternatives_list.as_ref(),
+                self.criteria.as_ref(),
+            );
+            if saved {
+                self.alternatives_list = None;
+                self.download_alternatives.get_schema(
+                    format!("{}/all_alternatives/{}", base_url, self.ranking.ranking_id),
+                    ctx,
+                    session,
+                );
+                self.criteria = None;
+                self.download_criteria.get_schema(
+                    format!("{}/criteria/{}", base_url, self.ranking.ranking_id),
+                    ctx,
+                    session,
+                );
+            }
+        }
+
+        /// The creation form in a window over the editor. Once it's closed
+        /// everything is downloaded again, like when coming back to the editor.
+        fn show_form(&mut self, ctx: &egui::Context, base_url: &String, session: &Session) {
+            let Some(form) = &mut self.form else {
+                return;
+            };
+            let mut open = true;
+            let mut done = false;
+            egui::Window::new(form.title())
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+                .open(&mut open)
+                .show(ctx, |ui| {
+                    if let Some(Navigation::Back) = form.show(ui, ctx, base_url, session) {
+                        done = true;
+                    }
+                });
+            // Forms are opened from the current section and only add to it
+            if done || !open {
+                self.form = None;
+                self.download_section(self.section, session, ctx, base_url);
+            }
         }
     }
-    fn show_section_list<'a, T>(
-        id_source: &str,
+
+    /// What new items of a section must not be called, nothing while it loads.
+    fn names<T>(list: &Option<Vec<T>>, name: fn(&T) -> String) -> Vec<String> {
+        list.iter().flatten().map(name).collect()
+    }
+
+    /// Moves a successfully downloaded value into `value` while it's missing.
+    fn receive<T>(value: &mut Option<T>, download: &mut Download)
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        receive_with(value, download, |json| serde_json::from_str(json).ok());
+    }
+
+    /// Like [`receive`] for responses that need more than deserializing.
+    fn receive_with<T>(
+        value: &mut Option<T>,
+        download: &mut Download,
+        parse: fn(&str) -> Option<T>,
+    ) {
+        if value.is_some() {
+            return;
+        }
+        let downloaded = download
+            .promise
+            .as_ref()
+            .and_then(|promise| promise.ready())
+            .and_then(|result| result.as_ref().ok())
+            .filter(|response| response.ok)
+            .and_then(|response| response.text())
+            .and_then(parse);
+        if let Some(downloaded) = downloaded {
+            *value = Some(downloaded);
+            download.promise = None;
+        }
+    }
+
+    /// Criteria as a tree, each one with buttons to add a sub-criterion below it or delete it.
+    /// Returns the form for a new criterion once one of the add buttons is clicked.
+    fn show_criteria_tree(
         ranking: &Ranking,
         ui: &mut Ui,
         ctx: &egui::Context,
         base_url: &String,
         session: &Session,
-        value: &mut Option<Vec<T>>,
+        criteria: &mut Option<Vec<Criterion>>,
         download: &mut Download,
-        delete_url: fn(&Ranking, &T, &str) -> String,
-        create_url: fn(&Ranking, &T, &str) -> String,
-        get_url: fn(&Ranking, &str) -> String,
-    ) -> Option<Box<dyn View + 'a>>
-    where
-        T: Default + Serialize + serde::de::DeserializeOwned + Showable + Clone + 'a,
-    {
+    ) -> Option<Box<dyn View>> {
         let mut ret: Option<Box<dyn View>> = None;
-
-        if ui.button("Create new").clicked() {
-            ret = Some(Box::new(NewThing::<T>::new(ranking.clone(), create_url)));
+        let taken = names(criteria, |c| c.name.clone());
+        let new_criterion = |title: String, parent_id: Option<i64>| -> Box<dyn View> {
+            let mut view = NewThing::<Criterion>::new(
+                title,
+                ranking.clone(),
+                taken.clone(),
+                |ranking: &Ranking, _: &Criterion, base_url: &str| {
+                    format!("{}/create_criteria/{}", base_url, ranking.ranking_id)
+                },
+            );
+            view.thing.parent_id = parent_id;
+            Box::new(view)
+        };
+
+        if ui.button(tr("Create new")).clicked() {
+            ret = Some(new_criterion(tr("New criteria"), None));
         }
-        if let Some(list) = &value {
+        if let Some(list) = &criteria {
             if list.is_empty() {
-                ui.label("No items!");
+                ui.label(tr("No items!"));
             } else {
-                egui::Grid::new(id_source).striped(true).show(ui, |ui| {
-                    for value in list {
-                        value.show(ui, &ctx, &base_url, &session);
-                        if ui.button("Delete item").clicked() {
-                            let url = delete_url(&ranking, &value, &base_url);
+                egui::Grid::new("Criteria").striped(true).show(ui, |ui| {
+                    for (index, depth) in hierarchy::tree_order(list) {
+                        let criterion = &list[index];
+                        ui.horizontal(|ui| {
+                            ui.add_space(depth as f32 * 16.0);
+                            ui.label(&criterion.name);
+                        });
+                        ui.label(&criterion.description);
+                        let add = ui.button(tr("Add sub-criterion"));
+                        describe(
+                            &add,
+                            tr_args(
+                                "Add a sub-criterion to {name}",
+                                &[("name", &criterion.name)],
+                            ),
+                        );
+                        if add.clicked() {
+                            ret = Some(new_criterion(
+                                tr_args(
+                                    "New sub-criterion of {name}",
+                                    &[("name", &criterion.name)],
+                                ),
+                                Some(criterion.criteria_id),
+                            ));
+                        }
+                        let delete = ui.button(tr("Delete item"));
+                        describe(
+                            &delete,
+                            tr_args("Delete criterion {name}", &[("name", &criterion.name)]),
+                        );
+                        if delete.clicked() {
+                            let url = format!(
+                                "{}/criteria/{}/{}",
+                                base_url, ranking.ranking_id, criterion.criteria_id
+                            );
                             download.delete_schema(url, ctx, session);
                         }
                         ui.end_row();
                     }
                 });
             }
+        }
+        let url = format!("{}/criteria/{}", base_url, ranking.ranking_id);
+        sync_section_list(ui, ctx, session, criteria, download, url);
+
+        ret
+    }
+
+    /// Downloads the list of a section from `url`, and again once `download`
+    /// finished changing it.
+    fn sync_section_list<T>(
+        ui: &mut Ui,
+        ctx: &egui::Context,
+        session: &Session,
+        value: &mut Option<Vec<T>>,
+        download: &mut Download,
+        url: String,
+    ) where
+        T: serde::de::DeserializeOwned + Clone,
+    {
+        if value.is_some() {
             let mut downloaded = false;
             download.run_when_downloaded(ui, |response, ui| match response.ok {
                 true => {
                     downloaded = true;
                 }
                 false => {
-                    ui.label(format!(
-                        "Got response code {} {}",
-                        response.status, response.status_text
+                    ui.label(tr_args(
+                        "Got response code {status} {text}",
+                        &[
+                            ("status", &response.status),
+                            ("text", &response.status_text),
+                        ],
                     ));
                 }
             });
             if downloaded {
                 *value = None;
-                download.get_schema(get_url(&ranking, &base_url), ctx, session);
+                download.get_schema(url, ctx, session);
             }
         } else {
             let optional_value: Option<Vec<T>> = download.deserialize_when_got(ui);
@@ -581,8 +1555,6 @@ pub mod ranking_list {
                 download.promise = None;
             }
         }
-
-        ret
     }
 
     impl View for EditRanking {
@@ -592,332 +1564,64 @@ pub mod ranking_list {
             ctx: &egui::Context,
             base_url: &String,
             session: &Session,
-        ) -> Option<Box<dyn View>> {
-            if ui.button("Back to ranking_list").clicked() {
-                return Some(Box::new(RankingList::default()));
+        ) -> Option<Navigation> {
+            if ui.button(tr("Back to ranking_list")).clicked() {
+                return Some(Navigation::Back);
             }
-            let mut ret: Option<Box<dyn View>> = None;
-            egui::ScrollArea::vertical().show(ui, |ui| {
-                ui.separator();
-                ui.heading("Basic ranking parameters");
-                if ui.button("Trigger algorithm").clicked() {
-                    let url = format!("{}/trigger_algorithm/{}", base_url, self.ranking.ranking_id);
-                    let request = Request::post(url, [].to_vec());
-                    self.download_trigger_algorithm.download(ctx, session.access_token.add_authorization_header(request));
-                }
-                self.download_trigger_algorithm.run_when_downloaded(ui, |response, ui|{
-                    ui.label(format!("Server responded with {}", response.status_text));
-                });
-
-                self.ranking.show_editable(ui, ctx, base_url, session);
-
-                ui.separator();
-                ui.heading("Variables");
-                egui::Grid::new("Variables grid").show(ui, |ui| {
-                    if let Some(variables) = &mut self.variables {
-                        variables.show(ui);
-                        ui.end_row();
-                        if ui.button("Create").clicked() {
-                            let json = serde_json::to_vec(&variables);
-                            match json {
-                                Ok(json) => {
-                                    let url = format!(
-                                        "{}/create_variables/{}",
-                                        base_url, self.ranking.ranking_id
-                                    );
-                                    let mut request = Request::post(url, json);
-                                    request.headers.insert(
-                                        "Content-Type".to_string(),
-                                        "application/json".to_string(),
-                                    );
-                                    self.download_variables.download(
-                                        ctx,
-                                        session.access_token.add_authorization_header(request),
-                                    );
-                                }
-                                Err(err) => {
-                                    println!("Failed to serialize variable due to: {}", err);
-                                }
-                            }
-                        }
-                        self.download
-                            .run_when_downloaded(ui, |response, ui| match response.ok {
-                                true => {
-                                    ui.label("Success");
-                                    variables.exists_in_ranking = true;
-                                }
-                                false => match response.text() {
-                                    Some(err) => {
-                                        ui.label(err);
-                                    }
-                                    None => {
-                                        ui.label("Unknown error");
-                                    }
-                                },
-                            });
-                    } else {
-                        self.download_variables
-                            .run_when_downloaded(ui, |response, ui| match response.status {
-                                200 => match response.text() {
-                                    Some(json) => {
-                                        let variables =
-                                            serde_json::from_slice::<Variables>(json.as_bytes());
-                                        match variables {
-                                            Ok(variables) => {
-                                                let mut variables = variables;
-                                                variables.exists_in_ranking = true;
-                                                self.variables = Some(variables);
-                                            }
-                                            Err(error) => {
-                                                ui.label(format!(
-                                                    "Failed to parse response due to: {}",
-                                                    error
-                                                ));
-                                            }
-                                        }
-                                    }
-                                    None => {
-                                        ui.label("Got empty response from server");
-                                    }
-                                },
-                                404 => {
-                                    if self.variables.is_none() {
-                                        self.variables = Some(Variables::default());
-                                    }
-                                }
-                                code => {
-                                    ui.label(format!(
-                                    "Failed to get variables, server responded with {} status code",
-                                    code
-                                ));
-                                }
-                            });
+            self.receive_downloads();
+            let mut ret: Option<Navigation> = None;
+            // Everything but the form stays disabled while it is open
+            let editing = self.form.is_none();
+
+            egui::SidePanel::left("Edit ranking side panel").show_inside(ui, |ui| {
+                ui.add_enabled_ui(editing, |ui| {
+                    if let Some(navigation) = self.show_rankings(ui, ctx, base_url, session) {
+                        ret = Some(navigation);
                     }
                 });
+            });
 
-                ui.separator();
-                ui.heading("Alternatives");
-                if ui.button("Create new alternative").clicked() {
-                    ret = Some(Box::new(NewAlternative::new(self.ranking.clone())));
-                }
-                if let Some(alternatives_list) = &self.alternatives_list {
-                    if alternatives_list.is_empty() {
-                        ui.label("No alternatives!");
-                    } else {
-                        egui::Grid::new("Alternatives")
-                            .striped(true)
-                            .show(ui, |ui| {
-                                for alternative in alternatives_list {
-                                    alternative.show(ui, &ctx, &base_url, &session);
-                                    if ui.button("Delete alternative").clicked() {
-                                        let url = format!(
-                                            "{}/alternative/{}/{}",
-                                            base_url,
-                                            self.ranking.ranking_id,
-                                            alternative.alternative_id
-                                        );
-                                        self.download_alternatives.delete_schema(url, ctx, session);
-                                    }
-                                    ui.end_row();
-                                }
-                            });
-                    }
-                    let mut downloaded = false;
-                    self.download_alternatives.run_when_downloaded(
-                        ui,
-                        |response, ui| match response.ok {
-                            true => {
-                                downloaded = true;
-                            }
-                            false => {
-                                ui.label(format!(
-                                    "Got response code {} {}",
-                                    response.status, response.status_text
-                                ));
-                            }
-                        },
-                    );
-                    if downloaded {
-                        self.alternatives_list = None;
-                        self.download_alternatives.get_schema(
-                            format!("{}/all_alternatives/{}", base_url, self.ranking.ranking_id),
-                            ctx,
-                            session,
-                        );
-                    }
-                } else {
-                    if let Some(value) = self.download_alternatives.deserialize_when_got(ui) {
-                        self.alternatives_list = Some(value);
-                        self.download_alternatives.promise = None;
-                    }
-                }
-
-                ui.separator();
-                ui.heading("Experts");
-
-                if ui.button("Create new expert").clicked() {
-                    ret = Some(Box::new(NewExpert::new(self.ranking.clone())));
-                }
-                if let Some(experts_list) = &self.experts_list {
-                    if experts_list.is_empty() {
-                        ui.label("No experts!");
-                    } else {
-                        egui::Grid::new("Experts").striped(true).show(ui, |ui| {
-                            for expert in experts_list {
-                                expert.show(ui, &ctx, &base_url, &session);
-                                if ui.button("Delete expert").clicked() {
-                                    let url = format!(
-                                        "{}/experts/{}/{}",
-                                        base_url, self.ranking.ranking_id, expert.expert_id
-                                    );
-                                    self.download_experts.delete_schema(url, ctx, session);
-                                }
-                                ui.end_row();
-                            }
-                        });
-                    }
-                    let mut downloaded = false;
-                    self.download_experts
-                        .run_when_downloaded(ui, |response, ui| match response.ok {
-                            true => {
-                                downloaded = true;
+            egui::CentralPanel::default().show_inside(ui, |ui| {
+                ui.add_enabled_ui(editing, |ui| {
+                    ui.horizontal(|ui| {
+                        for &section in Section::ALL {
+                            ui.selectable_value(&mut self.section, section, tr(section.name()));
+                        }
+                    });
+                    ui.separator();
+                    egui::ScrollArea::vertical().show(ui, |ui| match self.section {
+                        Section::Parameters => {
+                            if let Some(navigation) =
+                                self.show_parameters(ui, ctx, base_url, session)
+                            {
+                                ret = Some(navigation);
                             }
-                            false => {
-                                ui.label(format!(
-                                    "Got response code {} {}",
-                                    response.status, response.status_text
-                                ));
+                        }
+                        Section::Variables => self.show_variables(ui, ctx, base_url, session),
+                        Section::Alternatives => self.show_alternatives(ui, ctx, base_url, session),
+                        Section::Experts => self.show_experts(ui, ctx, base_url, session),
+                        Section::Criteria => {
+                            let form = show_criteria_tree(
+                                &self.ranking,
+                                ui,
+                                ctx,
+                                base_url,
+                                session,
+                                &mut self.criteria,
+                                &mut self.download_criteria,
+                            );
+                            if form.is_some() {
+                                self.form = form;
                             }
-                        });
-                    if downloaded {
-                        self.experts_list = None;
-                        self.download_experts.get_schema(
-                            format!("{}/experts/{}", base_url, self.ranking.ranking_id),
-                            ctx,
-                            session,
-                        );
-                    }
-                } else {
-                    if let Some(value) = self.download_experts.deserialize_when_got(ui) {
-                        self.experts_list = Some(value);
-                        self.download_experts.promise = None;
-                    }
-                }
-
-                ui.separator();
-                ui.heading("Criteria");
-
-                // if ui.button("Create new criterion").clicked() {
-                //     ret = Some(Box::new(NewThing::<Criterion>::new(
-                //         self.ranking.clone(),
-                //         |ranking, _criterion, base_url| {
-                //             format!("{}/create_criteria/{}", base_url, ranking.ranking_id)
-                //         },
-                //     )));
-                // }
-                // if let Some(list) = &self.criteria {
-                //     if list.is_empty() {
-                //         ui.label("No criteria!");
-                //     } else {
-                //         egui::Grid::new("Criteria").striped(true).show(ui, |ui| {
-                //             for value in list {
-                //                 value.show(ui, &ctx, &base_url, &session);
-                //                 if ui.button("Delete criterion").clicked() {
-                //                     let url = format!(
-                //                         "{}/criteria/{}/{}",
-                //                         base_url, self.ranking.ranking_id, value.criteria_id
-                //                     );
-                //                     self.download_criteria.delete_schema(url, ctx, session);
-                //                 }
-                //                 ui.end_row();
-                //             }
-                //         });
-                //     }
-                //     let mut downloaded = false;
-                //     self.download_criteria
-                //         .run_when_downloaded(ui, |response, ui| match response.ok {
-                //             true => {
-                //                 downloaded = true;
-                //             }
-                //             false => {
-                //                 ui.label(format!(
-                //                     "Got response code {} {}",
-                //                     response.status, response.status_text
-                //                 ));
-                //             }
-                //         });
-                //     if downloaded {
-                //         self.criteria = None;
-                //         self.download_criteria.get_schema(
-                //             format!("{}/criteria/{}", base_url, self.ranking.ranking_id),
-                //             ctx,
-                //             session,
-                //         );
-                //     }
-                // } else {
-                //     if let Some(value) = self.download_criteria.deserialize_when_got(ui) {
-                //         self.criteria = Some(value);
-                //         self.download_criteria.promise = None;
-                //     }
-                // }
-
-                let out = show_section_list(
-                    "Criteria",
-                    &self.ranking,
-                    ui,
-                    ctx,
-                    base_url,
-                    session,
-                    &mut self.criteria,
-                    &mut self.download_criteria,
-                    |ranking: &Ranking, criterion: &Criterion, base_url: &str| {
-                        format!(
-                            "{}/criteria/{}/{}",
-                            base_url, ranking.ranking_id, criterion.criteria_id
-                        )
-                    },
-                    |ranking: &Ranking, _: &Criterion, base_url: &str| {
-                        format!("{}/create_criteria/{}", base_url, ranking.ranking_id)
-                    },
-                    |ranking: &Ranking, base_url: &str| {
-                        format!("{}/criteria/{}", base_url, ranking.ranking_id)
-                    },
-                );
-                if let Some(v) = out {
-                    ret = Some(v);
-                }
-
-                ui.separator();
-                ui.heading("Scale");
-
-                let out = show_section_list(
-                    "Scale",
-                    &self.ranking,
-                    ui,
-                    ctx,
-                    base_url,
-                    session,
-                    &mut self.scale,
-                    &mut self.download_scale,
-                    |ranking: &Ranking, scale: &Scale, base_url: &str| {
-                        format!(
-                            "{}/scale/{}/{}",
-                            base_url, ranking.ranking_id, scale.scale_id
-                        )
-                    },
-                    |ranking: &Ranking, _: &Scale, base_url: &str| {
-                        format!("{}/create_scale/{}", base_url, ranking.ranking_id)
-                    },
-                    |ranking: &Ranking, base_url: &str| {
-                        format!("{}/get_scale/{}", base_url, ranking.ranking_id)
-                    },
-                );
-
-                if let Some(v) = out {
-                    ret = Some(v);
-                }
+                        }
+                        Section::Scale => self.show_scale(ui, ctx, base_url, session),
+                        #[cfg(feature = "content-updates")]
+                        Section::Translations => self.show_translations(ui, ctx, base_url, session),
+                    });
+                });
             });
 
+            self.show_form(ctx, base_url, session);
             ret
         }
 
@@ -928,6 +1632,24 @@ pub mod ranking_list {
         fn populate_from_json(&mut self, json: &String) {
             // No-op
         }
+
+        fn title(&self) -> String {
+            tr_args("Edit {name}", &[("name", &self.ranking.description)])
+        }
+
+        fn route(&self) -> Option<Route> {
+            Some(Route::EditRanking(self.ranking.ranking_id))
+        }
+
+        /// Downloads everything again but the variables, which would lose
+        /// their unsaved changes.
+        fn refresh(&mut self, ctx: &egui::Context, base_url: &String, session: &Session) {
+            for &section in Section::ALL {
+                if section != Section::Variables {
+                    self.download_section(section, session, ctx, base_url);
+                }
+            }
+        }
     }
 
     pub struct DeleteRanking {
@@ -951,18 +1673,18 @@ pub mod ranking_list {
             ctx: &egui::Context,
             base_url: &String,
             session: &Session,
-        ) -> Option<Box<dyn View>> {
-            let mut ret: Option<Box<dyn View>> = None;
+        ) -> Option<Navigation> {
+            let mut ret: Option<Navigation> = None;
             ui.vertical_centered_justified(|ui| {
                 ui.label(
-                    RichText::new(format!(
-                        "Are you sure you want to delete ranking '{}'?",
-                        self.ranking.description
+                    RichText::new(tr_args(
+                        "Are you sure you want to delete ranking '{name}'?",
+                        &[("name", &self.ranking.description)],
                     ))
                     .size(20.0),
                 );
                 ui.columns(2, |columns| {
-                    if columns[0].button("Yes, delete").clicked() {
+                    if columns[0].button(tr("Yes, delete")).clicked() {
                         let mut request = Request::get(format!(
                             "{}/ranking/{}",
                             base_url, self.ranking.ranking_id
@@ -971,8 +1693,11 @@ pub mod ranking_list {
                         self.download
                             .download(ctx, session.access_token.add_authorization_header(request));
                     }
-                    if columns[1].button("No, go back to ranking list").clicked() {
-                        ret = Some(Box::new(RankingList::default()));
+                    if columns[1]
+                        .button(tr("No, go back to ranking list"))
+                        .clicked()
+                    {
+                        ret = Some(Navigation::Back);
                     }
                 });
             });
@@ -980,15 +1705,15 @@ pub mod ranking_list {
             self.download
                 .run_when_downloaded(ui, |response, ui| match response.ok {
                     true => {
-                        ui.label("Success");
-                        ret = Some(Box::new(RankingList::default()));
+                        ui.label(tr("Success"));
+                        ret = Some(Navigation::Back);
                     }
                     false => match response.text() {
                         Some(err) => {
                             ui.label(err);
                         }
                         None => {
-                            ui.label("Unknown error");
+                            ui.label(tr("Unknown error"));
                         }
                     },
                 });
@@ -1002,19 +1727,130 @@ pub mod ranking_list {
         fn populate_from_json(&mut self, json: &String) {
             // no-op
         }
+
+        fn title(&self) -> String {
+            tr_args("Delete {name}", &[("name", &self.ranking.description)])
+        }
     }
 
     #[derive(serde::Deserialize, serde::Serialize)]
     pub struct RankingList {
         pub ranking_list: Vec<Ranking>,
+        /// Deep link to open once the list is downloaded
+        #[serde(skip)]
+        pending_route: Option<Route>,
+        /// Only rankings in this state are listed, all of them if `None`
+        #[serde(skip)]
+        state_filter: Option<RankingState>,
+        /// Ranking being moved to another state
+        #[serde(skip)]
+        changing_state: Option<(i64, RankingState)>,
+        /// Why the last state change failed
+        #[serde(skip)]
+        state_error: String,
+        #[serde(skip)]
+        download_state: Download,
     }
 
     impl Default for RankingList {
         fn default() -> Self {
             Self {
                 ranking_list: Vec::new(),
+                pending_route: None,
+                state_filter: None,
+                changing_state: None,
+                state_error: String::new(),
+                download_state: Download::default(),
+            }
+        }
+    }
+
+    impl RankingList {
+        /// Ranking list that opens `route` as soon as the rankings are known.
+        #[cfg(target_arch = "wasm32")]
+        pub fn with_route(route: Route) -> Self {
+            Self {
+                pending_route: Some(route),
+                ..Self::default()
             }
         }
+
+        /// Applies a finished state change to the list, or shows why it failed.
+        fn show_state_change(&mut self, ui: &mut Ui) {
+            if let Some((ranking_id, next)) = self.changing_state {
+                let finished = self
+                    .download_state
+                    .promise
+                    .as_ref()
+                    .and_then(|promise| promise.ready());
+                match finished {
+                    None => {
+                        ui.spinner();
+                    }
+                    Some(Ok(response)) if response.ok => {
+                        if let Some(ranking) = self
+                            .ranking_list
+                            .iter_mut()
+                            .find(|ranking| ranking.ranking_id == ranking_id)
+                        {
+                            ranking.state = next;
+                        }
+                    }
+                    Some(Ok(response)) => {
+                        self.state_error =
+                            response.text().unwrap_or(&response.status_text).to_string();
+                    }
+                    Some(Err(error)) => self.state_error = error.clone(),
+                }
+                if finished.is_some() {
+                    self.changing_state = None;
+                    self.download_state.promise = None;
+                }
+            }
+            if !self.state_error.is_empty() {
+                ui.label(RichText::new(&self.state_error).color(Color32::RED));
+            }
+        }
+
+        fn open_route(
+            &self,
+            route: Route,
+            ctx: &egui::Context,
+            base_url: &String,
+            session: &Session,
+        ) -> Option<Navigation> {
+            let ranking_id = match route {
+                Route::RankingList => return None,
+                Route::Experts if session.user_info.admin => {
+                    return Some(Navigation::Push(Box::new(ExpertDirectory::new())));
+                }
+                Route::Experts => return None,
+                Route::Rank(id)
+                | Route::EditRanking(id)
+                | Route::Results(id)
+                | Route::Participation(id) => id,
+            };
+            // Links to rankings we can't see lead to the list
+            let ranking = self
+                .ranking_list
+                .iter()
+                .find(|r| r.ranking_id == ranking_id)?
+                .clone();
+            let view: Box<dyn View> = match route {
+                Route::Rank(_) => Box::new(RankView::new(ranking, base_url, ctx, session)),
+                Route::EditRanking(_) if session.user_info.admin => {
+                    Box::new(EditRanking::new(ranking, session, ctx, base_url))
+                }
+                Route::Results(_) if session.user_info.admin => {
+                    Box::new(DownloadResults::new(ranking_id))
+                }
+                Route::Participation(_) if session.user_info.admin => {
+                    Box::new(ParticipationView::new(ranking))
+                }
+                _ => return None,
+            };
+            Some(Navigation::Push(view))
+        }
     }
 
     impl View for RankingList {
@@ -1024,58 +1860,109 @@ pub mod ranking_list {
             ctx: &egui::Context,
             base_url: &std::string::String,
             session: &Session,
-        ) -> std::option::Option<Box<dyn View>> {
-            let mut ret: Option<Box<dyn View>> = None;
+        ) -> Option<Navigation> {
+            if let Some(route) = self.pending_route.take() {
+                if let Some(navigation) = self.open_route(route, ctx, base_url, session) {
+                    return Some(navigation);
+                }
+            }
+            let mut ret: Option<Navigation> = None;
             ui.horizontal(|ui| {
-                ui.heading("Available rankings");
+                ui.heading(tr("Available rankings"));
                 if session.user_info.admin {
-                    if ui.button("Create new ranking").clicked() {
-                        ret = Some(Box::new(NewRanking::default()));
+                    if ui.button(tr("Create new ranking")).clicked() {
+                        ret = Some(Navigation::Push(Box::new(NewRanking::default())));
+                    }
+                    if ui.button(tr("Expert directory")).clicked() {
+                        ret = Some(Navigation::Push(Box::new(ExpertDirectory::new())));
                     }
                 }
             });
+            if session.user_info.admin {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Show:"));
+                    ui.selectable_value(&mut self.state_filter, None, tr("All"));
+                    for state in RankingState::ALL {
+                        ui.selectable_value(&mut self.state_filter, Some(state), tr(state.name()));
+                    }
+                });
+            }
+            self.show_state_change(ui);
+            let changing_state = self.changing_state.is_some();
+            let mut change_state = None;
+            let rankings = self.ranking_list.iter().filter(|ranking| {
+                let visible = session.user_info.admin || ranking.state.visible_to_experts();
+                visible
+                    && self
+                        .state_filter
+                        .map_or(true, |state| ranking.state == state)
+            });
             egui::Grid::new("ranking_list")
                 .striped(true)
                 .show(ui, |ui| {
-                    for e in &self.ranking_list {
+                    for e in rankings {
                         let timeout = chrono::DateTime::<chrono::Utc>::UNIX_EPOCH
                             + chrono::Duration::seconds(e.expiring);
 
                         if ui.button(e.description.clone()).clicked() {
                             println!("User wants to go to ranking {}", e.ranking_id);
-                            ret = Some(Box::new(RankView::new(
+                            ret = Some(Navigation::Push(Box::new(RankView::new(
                                 e.clone(),
                                 &base_url,
                                 &ctx,
                                 &session,
-                            )));
+                            ))));
                         }
                         ui.spacing();
                         // TODO: color based on urgency, present in local time
-                        ui.label(format!("Expiring: {}", timeout));
+                        ui.label(tr_args("Expiring: {date}", &[("date", &timeout)]));
+
+                        ui.label(tr(e.state.name()));
 
                         if session.user_info.admin {
-                            ui.label(format!("Id: {}", e.ranking_id));
-                            if ui.button("Delete").clicked() {
-                                ret = Some(Box::new(DeleteRanking::new(e.clone())));
+                            ui.label(tr_args("Id: {id}", &[("id", &e.ranking_id)]));
+                            for (next, action) in e.state.transitions() {
+                                let button = egui::Button::new(tr(action));
+                                if ui.add_enabled(!changing_state, button).clicked() {
+                                    change_state = Some((e.clone(), *next));
+                                }
                             }
-                            if ui.button("Edit").clicked() {
-                                ret = Some(Box::new(EditRanking::new(
+                            if ui.button(tr("Delete")).clicked() {
+                                ret =
+                                    Some(Navigation::Push(Box::new(DeleteRanking::new(e.clone()))));
+                            }
+                            if ui.button(tr("Edit")).clicked() {
+                                ret = Some(Navigation::Push(Box::new(EditRanking::new(
                                     e.clone(),
                                     session,
                                     ctx,
                                     base_url,
-                                )));
+                                ))));
                             }
-                            if ui.button("Download results").clicked() {
+                            if ui.button(tr("Download results")).clicked() {
                                 println!("Client wants to download results");
-                                ret = Some(Box::new(DownloadResults::new(e.ranking_id)));
+                                ret = Some(Navigation::Push(Box::new(DownloadResults::new(
+                                    e.ranking_id,
+                                ))));
                             }
                         }
                         ui.end_row();
                     }
                 });
 
+            if let Some((mut ranking, next)) = change_state {
+                ranking.state = next;
+                let url = format!("{}/ranking/{}", base_url, ranking.ranking_id);
+                match api::put_json(url, &ranking, &session.access_token) {
+                    Ok(request) => {
+                        self.download_state.download(ctx, request);
+                        self.changing_state = Some((ranking.ranking_id, next));
+                        self.state_error.clear();
+                    }
+                    Err(error) => println!("Failed to serialize ranking due to: {}", error),
+                }
+            }
+
             ret
         }
 
@@ -1100,5 +1987,13 @@ pub mod ranking_list {
                 }
             }
         }
+
+        fn title(&self) -> String {
+            tr("Rankings")
+        }
+
+        fn route(&self) -> Option<Route> {
+            Some(Route::RankingList)
+        }
     }
 }
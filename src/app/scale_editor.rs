@@ -0,0 +1,332 @@
+pub mod scale_editor {
+    use egui::{Color32, RichText, Ui};
+    use ehttp::Request;
+
+    use crate::app::{
+        accessibility::accessibility::describe,
+        ahp::ahp::SCALE_PRESETS,
+        api::api,
+        download::download::Download,
+        i18n::i18n::{tr, tr_args},
+        login::login::Session,
+        schema::schema::{scale_warnings, show_field_errors, Scale, Validate, ValidationContext},
+    };
+
+    /// The whole scale of a ranking edited at once: presets, reordering and
+    /// warnings, saved as the entries to create and delete. Once experts have
+    /// weighted criteria with the scale it can't be saved any more, the
+    /// weights point at its entries.
+    pub struct ScaleEditor {
+        ranking_id: i64,
+        /// Ids of the scale last passed to `show`, a new download replaces the draft
+        downloaded: Vec<i64>,
+        /// The scale on the server in value order, what the draft is saved against
+        original: Vec<Scale>,
+        draft: Vec<Scale>,
+        /// The scale downloaded again after a failed save, part of which may
+        /// have changed it
+        download_original: Download,
+        /// The export of the ranking, for its weights
+        download_answers: Download,
+        /// Whether experts have picked entries of the scale, `None` until known
+        answered: Option<bool>,
+        /// One request per created entry, then one per deleted entry
+        saves: Vec<Download>,
+        /// The deletes, sent once all entries are created so a failure doesn't
+        /// leave the scale with entries missing
+        deletes: Vec<Request>,
+        error: String,
+    }
+
+    impl ScaleEditor {
+        pub fn new(ranking_id: i64) -> Self {
+            Self {
+                ranking_id,
+                downloaded: Vec::new(),
+                original: Vec::new(),
+                draft: Vec::new(),
+                download_original: Download::default(),
+                download_answers: Download::default(),
+                answered: None,
+                saves: Vec::new(),
+                deletes: Vec::new(),
+                error: String::new(),
+            }
+        }
+
+        /// Returns true once all changes are saved, the scale should then be
+        /// downloaded again. After a failure the error and the draft are kept.
+        pub fn show(
+            &mut self,
+            ui: &mut Ui,
+            ctx: &egui::Context,
+            base_url: &String,
+            session: &Session,
+            scale: Option<&Vec<Scale>>,
+        ) -> bool {
+            let Some(scale) = scale else {
+                ui.spinner();
+                return false;
+            };
+            // A fresh download replaces the draft, unsaved edits are lost then
+            let ids = |list: &[Scale]| list.iter().map(|s| s.scale_id).collect::<Vec<_>>();
+            let mut downloaded = scale.clone();
+            downloaded.sort_by(|a, b| a.value.total_cmp(&b.value));
+            if self.downloaded != ids(&downloaded) {
+                self.downloaded = ids(&downloaded);
+                self.original = downloaded.clone();
+                self.draft = downloaded;
+                self.answered = None;
+                self.download_answers.promise = None;
+            }
+            if self.answered.is_none() {
+                let url = format!("{}/export_data/{}", base_url, self.ranking_id);
+                self.download_answers
+                    .download_if_needed(ctx, api::get(url, &session.access_token));
+                let export = self
+                    .download_answers
+                    .promise
+                    .as_ref()
+                    .and_then(|promise| promise.ready());
+                let Some(export) = export else {
+                    ui.spinner();
+                    return false;
+                };
+                self.answered = export
+                    .as_ref()
+                    .ok()
+                    .filter(|response| response.ok)
+                    .and_then(|response| response.text())
+                    .and_then(answered);
+                // A failed download is kept, so it isn't retried every frame
+                if self.answered.is_none() {
+                    let error = RichText::new(tr("Failed to download data")).color(Color32::RED);
+                    ui.label(error);
+                    return false;
+                }
+            }
+            if let Some(mut original) = self
+                .download_original
+                .deserialize_when_got::<Vec<Scale>>(ui)
+            {
+                original.sort_by(|a, b| a.value.total_cmp(&b.value));
+                self.original = original;
+                self.download_original.promise = None;
+            }
+
+            ui.horizontal(|ui| {
+                ui.label(tr("Presets:"));
+                for preset in &SCALE_PRESETS {
+                    let button = ui.button(tr(preset.name));
+                    describe(&button, tr("Replaces all entries of the scale"));
+                    if button.clicked() {
+                        self.draft = preset
+                            .entries
+                            .iter()
+                            .map(|(description, value)| Scale {
+                                scale_id: 0,
+                                description: description.to_string(),
+                                value: *value,
+                                ranking_id: self.ranking_id,
+                            })
+                            .collect();
+                    }
+                }
+            });
+            ui.label(tr("From least to most important:"));
+
+            let mut errors = Vec::new();
+            let mut moved = None;
+            let mut removed = None;
+            let last = self.draft.len().saturating_sub(1);
+            egui::Grid::new("Scale editor")
+                .striped(true)
+                .show(ui, |ui| {
+                    for (row, entry) in self.draft.iter_mut().enumerate() {
+                        let up = ui.add_enabled(row > 0, egui::Button::new("⬆"));
+                        describe(
+                            &up,
+                            tr_args("Move {name} up", &[("name", &entry.description)]),
+                        );
+                        if up.clicked() {
+                            moved = Some((row, row - 1));
+                        }
+                        let down = ui.add_enabled(row < last, egui::Button::new("⬇"));
+                        describe(
+                            &down,
+                            tr_args("Move {name} down", &[("name", &entry.description)]),
+                        );
+                        if down.clicked() {
+                            moved = Some((row, row + 1));
+                        }
+                        let field = egui::TextEdit::singleline(&mut entry.description)
+                            .id_source(("scale entry", row))
+                            .hint_text(tr("Description"));
+                        let field = ui.add(field);
+                        describe(
+                            &field,
+                            tr_args("Description of entry {row}", &[("row", &(row + 1))]),
+                        );
+                        let value = ui.add(egui::DragValue::new(&mut entry.value).speed(0.1));
+                        describe(
+                            &value,
+                            tr_args("Value of {name}", &[("name", &entry.description)]),
+                        );
+                        let remove = ui.button(tr("Remove"));
+                        describe(
+                            &remove,
+                            tr_args("Remove {name}", &[("name", &entry.description)]),
+                        );
+                        if remove.clicked() {
+                            removed = Some(row);
+                        }
+                        let entry_errors = entry.validate(&ValidationContext::default());
+                        show_field_errors(ui, &entry_errors, "description");
+                        show_field_errors(ui, &entry_errors, "value");
+                        errors.extend(entry_errors);
+                        ui.end_row();
+                    }
+                });
+            // The values stay where they are, only the descriptions move
+            if let Some((from, to)) = moved {
+                self.draft.swap(from, to);
+                let value = self.draft[from].value;
+                self.draft[from].value = self.draft[to].value;
+                self.draft[to].value = value;
+            }
+            if let Some(row) = removed {
+                self.draft.remove(row);
+            }
+            if ui.button(tr("Add entry")).clicked() {
+                let value = self.draft.last().map_or(1.0, |last| last.value + 1.0);
+                self.draft.push(Scale {
+                    scale_id: 0,
+                    description: String::new(),
+                    value,
+                    ranking_id: self.ranking_id,
+                });
+            }
+            for warning in scale_warnings(&self.draft) {
+                ui.label(RichText::new(warning).color(Color32::YELLOW));
+            }
+
+            if self.answered == Some(true) {
+                ui.label(
+                    RichText::new(tr(
+                        "Experts have weighted criteria with this scale already, it can't be changed any more",
+                    ))
+                    .color(Color32::YELLOW),
+                );
+            }
+
+            let (deleted, created) = changes(&self.original, &self.draft);
+            let saving = !(self.saves.is_empty() && self.deletes.is_empty());
+            let save = ui.add_enabled(
+                !(deleted.is_empty() && created.is_empty())
+                    && errors.is_empty()
+                    && !saving
+                    && self.answered == Some(false),
+                egui::Button::new(tr("Save scale")),
+            );
+            if save.clicked() {
+                self.error.clear();
+                for entry in created {
+                    let url = format!("{}/create_scale/{}", base_url, self.ranking_id);
+                    match api::post_json(url, entry, &session.access_token) {
+                        Ok(request) => self.saves.push(send(ctx, request)),
+                        Err(error) => self.error = error,
+                    }
+                }
+                self.deletes = deleted
+                    .iter()
+                    .map(|entry| {
+                        let url =
+                            format!("{}/scale/{}/{}", base_url, self.ranking_id, entry.scale_id);
+                        api::delete(url, &session.access_token)
+                    })
+                    .collect();
+            }
+            if !self.error.is_empty() {
+                ui.label(RichText::new(&self.error).color(Color32::RED));
+            }
+            if !saving {
+                return false;
+            }
+
+            let mut finished = true;
+            for download in &self.saves {
+                match download
+                    .promise
+                    .as_ref()
+                    .and_then(|promise| promise.ready())
+                {
+                    None => finished = false,
+                    Some(Ok(response)) if !response.ok => {
+                        self.error = tr_args(
+                            "Failed, server responded with: {status} {text}",
+                            &[
+                                ("status", &response.status),
+                                ("text", &response.status_text),
+                            ],
+                        );
+                    }
+                    Some(Err(error)) => self.error = error.clone(),
+                    Some(Ok(_)) => {}
+                }
+            }
+            if !finished {
+                ui.spinner();
+                return false;
+            }
+            self.saves.clear();
+            if !self.error.is_empty() {
+                self.deletes.clear();
+                // Compare the next attempt with what did get saved
+                let url = format!("{}/get_scale/{}", base_url, self.ranking_id);
+                self.download_original.get_schema(url, ctx, session);
+                return false;
+            }
+            if !self.deletes.is_empty() {
+                self.saves = self.deletes.drain(..).map(|r| send(ctx, r)).collect();
+                return false;
+            }
+            true
+        }
+    }
+
+    fn send(ctx: &egui::Context, request: Request) -> Download {
+        let mut download = Download::default();
+        download.download(ctx, request);
+        download
+    }
+
+    /// Whether the ranking of `export` has weights, which refer to scale entries.
+    fn answered(export: &str) -> Option<bool> {
+        let export: serde_json::Value = serde_json::from_str(export).ok()?;
+        let weights = export["weights"].as_array().map_or(0, Vec::len);
+        Some(weights > 0)
+    }
+
+    /// Entries of `original` missing from `draft` and the other way round.
+    /// Entries can't be edited on the server, changed ones are in both.
+    fn changes<'a>(original: &'a [Scale], draft: &'a [Scale]) -> (Vec<&'a Scale>, Vec<&'a Scale>) {
+        let mut kept = vec![false; original.len()];
+        let mut created = Vec::new();
+        for entry in draft {
+            let same = original.iter().enumerate().position(|(i, other)| {
+                !kept[i] && other.description == entry.description && other.value == entry.value
+            });
+            match same {
+                Some(i) => kept[i] = true,
+                None => created.push(entry),
+            }
+        }
+        let deleted = original
+            .iter()
+            .zip(kept)
+            .filter(|(_, kept)| !kept)
+            .map(|(entry, _)| entry)
+            .collect();
+        (deleted, created)
+    }
+}
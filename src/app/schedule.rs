@@ -0,0 +1,112 @@
+pub mod schedule {
+    use crate::app::schema::schema::{Alternative, ComparisonDesign, Criterion, Presentation};
+
+    /// One question put to an expert: which of `left` and `right` is better
+    /// based on `criterion`.
+    #[derive(Clone)]
+    pub struct Question {
+        pub criterion: Criterion,
+        pub left: Alternative,
+        pub right: Alternative,
+    }
+
+    /// Seed of the shuffling for one expert, so experts see different but
+    /// reproducible orders.
+    pub fn expert_seed(presentation: &Presentation, expert_id: u64) -> u64 {
+        presentation.seed ^ expert_id.wrapping_mul(0x9E37_79B9_7F4A_7C15)
+    }
+
+    /// Every question of a ranking, the pairs `design` picks in the order
+    /// `presentation` asks for. Without any randomization the pairs come in the
+    /// order of `Combinations`, one criterion after another.
+    pub fn schedule(
+        alternatives: &[Alternative],
+        criteria: &[Criterion],
+        design: &ComparisonDesign,
+        presentation: &Presentation,
+        expert_id: u64,
+    ) -> Vec<Question> {
+        let mut alternatives = alternatives.to_vec();
+        alternatives.sort();
+        let mut rng = fastrand::Rng::with_seed(expert_seed(presentation, expert_id));
+
+        // With a reduced design, shuffling the circle gives every expert different
+        // pairs, so together they still cover most of them.
+        let mut positions: Vec<usize> = (0..alternatives.len()).collect();
+        if presentation.random_order {
+            rng.shuffle(&mut positions);
+        }
+        let mut design_pairs: Vec<(usize, usize)> = design
+            .pairs(alternatives.len())
+            .into_iter()
+            .map(|(a, b)| {
+                let (a, b) = (positions[a], positions[b]);
+                (a.min(b), a.max(b))
+            })
+            .collect();
+        design_pairs.sort();
+
+        let mut per_criterion: Vec<Vec<Question>> = Vec::new();
+        for criterion in criteria {
+            let mut pairs = design_pairs.clone();
+            if presentation.random_order {
+                rng.shuffle(&mut pairs);
+            }
+            let questions = pairs
+                .into_iter()
+                .map(|(a, b)| {
+                    let (a, b) = match presentation.random_sides && rng.bool() {
+                        true => (b, a),
+                        false => (a, b),
+                    };
+                    Question {
+                        criterion: criterion.clone(),
+                        left: alternatives[a].clone(),
+                        right: alternatives[b].clone(),
+                    }
+                })
+                .collect();
+            per_criterion.push(questions);
+        }
+
+        if !presentation.interleave_criteria {
+            return per_criterion.into_iter().flatten().collect();
+        }
+        let mut iters: Vec<_> = per_criterion.into_iter().map(|q| q.into_iter()).collect();
+        let mut questions = Vec::new();
+        loop {
+            let before = questions.len();
+            questions.extend(iters.iter_mut().filter_map(|iter| iter.next()));
+            if questions.len() == before {
+                return questions;
+            }
+        }
+    }
+
+    /// Pairs of criteria indices an expert compares when weighing the criteria
+    /// pairwise, first the one shown on the left. Criteria are only compared
+    /// within their group of siblings, one group after another.
+    pub fn criteria_pairs(
+        groups: &[Vec<usize>],
+        presentation: &Presentation,
+        expert_id: u64,
+    ) -> Vec<(usize, usize)> {
+        // A different stream than the alternatives, so both aren't shuffled alike
+        let seed = expert_seed(presentation, expert_id).rotate_left(32);
+        let mut rng = fastrand::Rng::with_seed(seed);
+        let mut all_pairs = Vec::new();
+        for group in groups {
+            let mut pairs = ComparisonDesign::Complete.pairs(group.len());
+            if presentation.random_order {
+                rng.shuffle(&mut pairs);
+            }
+            for (a, b) in pairs {
+                match presentation.random_sides && rng.bool() {
+                    true => all_pairs.push((group[b], group[a])),
+                    false => all_pairs.push((group[a], group[b])),
+                }
+            }
+        }
+        all_pairs
+    }
+}
@@ -1,19 +1,89 @@
 pub mod schema {
+    use std::collections::BTreeMap;
+
     use egui::{Color32, RichText, Ui};
     use ehttp::Request;
 
     use crate::app::{
-        download::download::Download, login::login::Session,
-        ranking_list::ranking_list::RankingList, view::View,
+        api::api,
+        download::download::Download,
+        i18n::i18n::{tr, tr_args, tr_plural, Language},
+        login::login::Session,
+        markdown::markdown,
+        study::study::parse_date,
+        view::Navigation,
     };
 
+    /// Name and description of an alternative or criterion in another language.
+    #[derive(
+        serde::Deserialize, serde::Serialize, Debug, Clone, Default, PartialEq, PartialOrd, Ord, Eq,
+    )]
+    pub struct Translation {
+        pub name: String,
+        #[serde(default)]
+        pub description: String,
+    }
+
+    /// Translations by language code, see [`Language::code`]. The plain
+    /// `name` and `description` are the default, used where one is missing.
+    pub type Translations = BTreeMap<String, Translation>;
+
+    /// `name` and `description` in `language`, falling back to the default
+    /// for whichever of them isn't translated.
+    fn translate<'a>(
+        translations: &'a Translations,
+        language: Language,
+        name: &'a str,
+        description: &'a str,
+    ) -> (&'a str, &'a str) {
+        match translations.get(language.code()) {
+            Some(translation) => (
+                Some(translation.name.as_str())
+                    .filter(|name| !name.trim().is_empty())
+                    .unwrap_or(name),
+                Some(translation.description.as_str())
+                    .filter(|description| !description.trim().is_empty())
+                    .unwrap_or(description),
+            ),
+            None => (name, description),
+        }
+    }
+
+    /// Reference link shown with an alternative.
+    #[derive(
+        serde::Deserialize, serde::Serialize, Debug, Clone, Default, PartialEq, PartialOrd, Ord, Eq,
+    )]
+    pub struct Link {
+        pub title: String,
+        pub url: String,
+    }
+
     #[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq, PartialOrd, Ord, Eq)]
     pub struct Alternative {
         pub alternative_id: i64,
         pub name: String,
+        /// Markdown
         pub description: String,
+        /// `http(s)://` URL of a PNG picture, or an uploaded one as a `data:` URI. Empty for none.
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        pub image_url: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub links: Vec<Link>,
+        #[serde(default)]
+        pub translations: Translations,
     }
     impl Alternative {
+        /// This alternative with its name and description in `language`.
+        pub fn localized(&self, language: Language) -> Self {
+            let (name, description) =
+                translate(&self.translations, language, &self.name, &self.description);
+            Self {
+                name: name.to_string(),
+                description: description.to_string(),
+                ..self.clone()
+            }
+        }
+
         pub fn show(
             &self,
             ui: &mut egui::Ui,
@@ -22,25 +92,99 @@ pub mod schema {
             session: &Session,
         ) {
             ui.label(&self.name);
-            ui.label(&self.description);
+            ui.vertical(|ui| {
+                markdown::show(ui, &self.description);
+                self.show_links(ui);
+            });
+            match self.image_url.is_empty() {
+                true => ui.label(""),
+                false => ui.add(egui::Image::new(&self.image_url).max_size(egui::vec2(64.0, 64.0))),
+            };
             ui.label(self.alternative_id.to_string());
         }
 
+        /// Picture, description and links, as shown to experts next to the alternative.
+        pub fn show_details(&self, ui: &mut Ui) {
+            if !self.image_url.is_empty() {
+                ui.add(egui::Image::new(&self.image_url).max_size(egui::vec2(240.0, 180.0)));
+            }
+            markdown::show(ui, &self.description);
+            self.show_links(ui);
+        }
+
+        fn show_links(&self, ui: &mut Ui) {
+            if self.links.is_empty() {
+                return;
+            }
+            ui.horizontal_wrapped(|ui| {
+                for link in &self.links {
+                    let title = match link.title.is_empty() {
+                        true => &link.url,
+                        false => &link.title,
+                    };
+                    ui.hyperlink_to(title, &link.url);
+                }
+            });
+        }
+
+        /// Fields of the alternative, with `errors` next to the ones they are about.
         pub fn show_editable(
             &mut self,
             ui: &mut Ui,
             ctx: &egui::Context,
             base_url: &String,
             session: &Session,
-        ) -> Option<Box<dyn View>> {
+            errors: &[FieldError],
+        ) -> Option<Navigation> {
             egui::Grid::new("Alternative editable")
                 .num_columns(2)
                 .show(ui, |ui| {
-                    ui.label("Name: ");
-                    ui.text_edit_singleline(&mut self.name);
+                    let label = ui.label(tr("Name: "));
+                    ui.text_edit_singleline(&mut self.name)
+                        .labelled_by(label.id);
+                    show_field_errors(ui, errors, "name");
+                    ui.end_row();
+                    let label = ui.label(tr("Description: "));
+                    ui.text_edit_multiline(&mut self.description)
+                        .labelled_by(label.id)
+                        .on_hover_text(tr("Markdown: # headings, - lists, **bold**, *italic*, [links](https://...)"));
+                    ui.end_row();
+                    ui.label(tr("Preview: "));
+                    ui.vertical(|ui| markdown::show(ui, &self.description));
                     ui.end_row();
-                    ui.label("Description: ");
-                    ui.text_edit_singleline(&mut self.description);
+                    let label = ui.label(tr("Image URL: "));
+                    ui.text_edit_singleline(&mut self.image_url)
+                        .labelled_by(label.id)
+                        .on_hover_text(tr("A PNG picture, leave empty for none"));
+                    show_field_errors(ui, errors, "image_url");
+                    ui.end_row();
+                    if !self.image_url.is_empty() {
+                        ui.label("");
+                        ui.add(egui::Image::new(&self.image_url).max_size(egui::vec2(240.0, 180.0)));
+                        ui.end_row();
+                    }
+                    ui.label(tr("Links: "));
+                    ui.vertical(|ui| {
+                        let mut removed = None;
+                        for (i, link) in self.links.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(tr("Title"));
+                                ui.text_edit_singleline(&mut link.title);
+                                ui.label(tr("URL"));
+                                ui.text_edit_singleline(&mut link.url);
+                                if ui.button(tr("Remove link")).clicked() {
+                                    removed = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = removed {
+                            self.links.remove(i);
+                        }
+                        if ui.button(tr("Add link")).clicked() {
+                            self.links.push(Link::default());
+                        }
+                    });
+                    show_field_errors(ui, errors, "links");
                 });
             None
         }
@@ -52,11 +196,16 @@ pub mod schema {
                 alternative_id: 0,
                 name: "Batman".to_string(),
                 description: "Do people still read comic books?".to_string(),
+                image_url: String::new(),
+                links: Vec::new(),
+                translations: Translations::new(),
             }
         }
     }
 
-    #[derive(serde::Deserialize, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, Clone)]
+    #[derive(
+        serde::Deserialize, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, Clone, Debug,
+    )]
     pub struct Expert {
         pub expert_id: u64,
         pub name: String,
@@ -92,22 +241,30 @@ pub mod schema {
             ui.label(self.expert_id.to_string());
         }
 
+        /// Fields of the expert, with `errors` next to the ones they are about.
         pub fn show_editable(
             &mut self,
             ui: &mut Ui,
             ctx: &egui::Context,
             base_url: &String,
             session: &Session,
-        ) -> Option<Box<dyn View>> {
+            errors: &[FieldError],
+        ) -> Option<Navigation> {
             egui::Grid::new("Expert editable")
                 .num_columns(2)
                 .show(ui, |ui| {
-                    ui.label("Name: ");
-                    ui.text_edit_singleline(&mut self.name);
+                    let label = ui.label(tr("Name: "));
+                    ui.text_edit_singleline(&mut self.name)
+                        .labelled_by(label.id);
+                    show_field_errors(ui, errors, "name");
+                    ui.end_row();
+                    let label = ui.label(tr("email: "));
+                    ui.text_edit_singleline(&mut self.email)
+                        .labelled_by(label.id);
+                    show_field_errors(ui, errors, "email");
                     ui.end_row();
-                    ui.label("email: ");
-                    ui.text_edit_singleline(&mut self.email);
-                    ui.checkbox(&mut self.admin, "Admin");
+                    ui.label("");
+                    ui.checkbox(&mut self.admin, tr("Admin"));
                 });
             None
         }
@@ -118,9 +275,25 @@ pub mod schema {
         pub description: String,
         pub ranking_id: i64,
         pub expiring: i64,
+        #[serde(default)]
+        pub state: RankingState,
 
         #[serde(skip)]
         download: Download,
+        /// The deadline as typed, `None` until the field is first shown
+        #[serde(skip)]
+        expiring_text: Option<String>,
+        /// The ranking as the server has it, state changes are sent from it
+        /// so they don't carry along unsaved edits
+        #[serde(skip)]
+        saved: Option<Box<Ranking>>,
+        /// State being changed to, the state is only updated once the server agrees
+        #[serde(skip)]
+        transition: Option<RankingState>,
+        #[serde(skip)]
+        download_transition: Download,
+        #[serde(skip)]
+        transition_error: String,
     }
 
     impl Default for Ranking {
@@ -129,66 +302,263 @@ pub mod schema {
                 description: "Superheroes ranking".to_string(),
                 ranking_id: Default::default(),
                 expiring: Default::default(),
+                state: RankingState::default(),
                 download: Default::default(),
+                expiring_text: None,
+                saved: None,
+                transition: None,
+                download_transition: Default::default(),
+                transition_error: String::new(),
+            }
+        }
+    }
+
+    /// Where a ranking is in its life, experts can only answer open rankings.
+    #[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+    #[serde(rename_all = "lowercase")]
+    pub enum RankingState {
+        /// Being set up by the admins, hidden from experts
+        Draft,
+        /// Rankings from before the lifecycle have no state and count as open
+        #[default]
+        Open,
+        /// No longer accepting answers, results can be computed
+        Closed,
+        /// Kept for reference, hidden from experts
+        Archived,
+    }
+
+    impl RankingState {
+        pub const ALL: [RankingState; 4] = [
+            RankingState::Draft,
+            RankingState::Open,
+            RankingState::Closed,
+            RankingState::Archived,
+        ];
+
+        pub fn name(&self) -> &'static str {
+            match self {
+                RankingState::Draft => "Draft",
+                RankingState::Open => "Open",
+                RankingState::Closed => "Closed",
+                RankingState::Archived => "Archived",
+            }
+        }
+
+        /// States an admin can move a ranking to from this one, with the button text.
+        pub fn transitions(&self) -> &'static [(RankingState, &'static str)] {
+            match self {
+                RankingState::Draft => &[(RankingState::Open, "Open for answers")],
+                RankingState::Open => &[(RankingState::Closed, "Close")],
+                RankingState::Closed => &[
+                    (RankingState::Open, "Reopen"),
+                    (RankingState::Archived, "Archive"),
+                ],
+                RankingState::Archived => &[(RankingState::Closed, "Unarchive")],
+            }
+        }
+
+        pub fn can_become(&self, next: RankingState) -> bool {
+            self.transitions().iter().any(|(state, _)| *state == next)
+        }
+
+        /// Whether experts get to see rankings in this state.
+        pub fn visible_to_experts(&self) -> bool {
+            matches!(self, RankingState::Open | RankingState::Closed)
+        }
+
+        /// Why experts can't answer a ranking in this state, `None` if they can.
+        pub fn closed_reason(&self) -> Option<&'static str> {
+            match self {
+                RankingState::Draft => {
+                    Some("This ranking is still being prepared and does not accept answers yet.")
+                }
+                RankingState::Open => None,
+                RankingState::Closed => {
+                    Some("This ranking is closed and no longer accepts answers.")
+                }
+                RankingState::Archived => {
+                    Some("This ranking is archived and no longer accepts answers.")
+                }
             }
         }
     }
 
     impl Ranking {
+        pub fn new(ranking_id: i64, description: String, expiring: i64) -> Self {
+            Self {
+                description,
+                ranking_id,
+                expiring,
+                ..Default::default()
+            }
+        }
+
+        /// A ranking yet to be created, hidden from experts until the admins open it.
+        pub fn draft(description: String, expiring: i64) -> Self {
+            Self {
+                state: RankingState::Draft,
+                ..Self::new(0, description, expiring)
+            }
+        }
+
+        /// Deadline for answers, `None` when the ranking never expires.
+        pub fn expires_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+            match self.expiring > 0 {
+                true => Some(
+                    chrono::DateTime::<chrono::Utc>::UNIX_EPOCH
+                        + chrono::Duration::seconds(self.expiring),
+                ),
+                false => None,
+            }
+        }
+
+        /// The deadline as a `YYYY-MM-DD` field, left blank for rankings
+        /// that never expire.
+        pub fn show_expiring(&mut self, ui: &mut Ui, errors: &[FieldError]) {
+            let expires_at = self.expires_at();
+            let text = self.expiring_text.get_or_insert_with(|| {
+                expires_at
+                    .map(|deadline| deadline.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default()
+            });
+            let label = ui.label(tr("Expiring: "));
+            let field =
+                egui::TextEdit::singleline(text).hint_text(tr("YYYY-MM-DD, blank for never"));
+            // Only a typed date replaces the deadline, which can be any time of day
+            if ui.add(field).labelled_by(label.id).changed() {
+                match text.trim() {
+                    "" => self.expiring = 0,
+                    date => {
+                        if let Ok(timestamp) = parse_date(date) {
+                            self.expiring = timestamp;
+                        }
+                    }
+                }
+            }
+            show_field_errors(ui, errors, "expiring");
+        }
+
+        /// Time left to answer at `now`, negative once expired.
+        pub fn remaining_at(&self, now: chrono::DateTime<chrono::Utc>) -> Option<chrono::Duration> {
+            self.expires_at().map(|deadline| deadline - now)
+        }
+
+        /// Asks the server to move the ranking to state `next`, leaving
+        /// everything else as the server has it.
+        fn send_transition(
+            &mut self,
+            next: RankingState,
+            ctx: &egui::Context,
+            base_url: &String,
+            session: &Session,
+        ) {
+            let mut ranking = match &self.saved {
+                Some(saved) => (**saved).clone(),
+                None => self.clone(),
+            };
+            ranking.state = next;
+            let url = format!("{}/ranking/{}", base_url, self.ranking_id);
+            match api::put_json(url, &ranking, &session.access_token) {
+                Ok(request) => {
+                    self.download_transition.download(ctx, request);
+                    self.transition = Some(next);
+                    self.transition_error.clear();
+                }
+                Err(error) => self.transition_error = error,
+            }
+        }
+
+        /// Takes the state over once the server accepted it, or shows why it didn't.
+        fn show_transition(&mut self, ui: &mut Ui) {
+            if let Some(next) = self.transition {
+                let finished = self
+                    .download_transition
+                    .promise
+                    .as_ref()
+                    .and_then(|promise| promise.ready());
+                match finished {
+                    None => {
+                        ui.spinner();
+                    }
+                    Some(Ok(response)) if response.ok => {
+                        self.state = next;
+                        if let Some(saved) = &mut self.saved {
+                            saved.state = next;
+                        }
+                    }
+                    Some(Ok(response)) => {
+                        self.transition_error =
+                            response.text().unwrap_or(&response.status_text).to_string();
+                    }
+                    Some(Err(error)) => self.transition_error = error.clone(),
+                }
+                if finished.is_some() {
+                    self.transition = None;
+                    self.download_transition.promise = None;
+                }
+            }
+            if !self.transition_error.is_empty() {
+                ui.label(RichText::new(&self.transition_error).color(Color32::RED));
+            }
+        }
+
+        /// Sends the ranking to the server, replacing what it had.
+        fn send_update(&mut self, ctx: &egui::Context, base_url: &String, session: &Session) {
+            let url = format!("{}/ranking/{}", base_url, self.ranking_id);
+            match api::put_json(url, &*self, &session.access_token) {
+                Ok(request) => self.download.download(ctx, request),
+                Err(error) => println!("Failed to serialize ranking due to: {}", error),
+            }
+        }
+
         pub fn show_editable(
             &mut self,
             ui: &mut Ui,
             ctx: &egui::Context,
             base_url: &String,
             session: &Session,
-        ) -> Option<Box<dyn View>> {
-            let mut ret: Option<Box<dyn View>> = None;
+        ) -> Option<Navigation> {
+            let mut ret: Option<Navigation> = None;
+            if self.saved.is_none() {
+                self.saved = Some(Box::new(self.clone()));
+            }
+            let errors = self.validate(&ValidationContext::now(&[]));
             egui::Grid::new("Ranking grid")
                 .num_columns(2)
                 .show(ui, |ui| {
-                    ui.label("Description: ");
-                    ui.text_edit_singleline(&mut self.description);
+                    let label = ui.label(tr("Description: "));
+                    ui.text_edit_singleline(&mut self.description)
+                        .labelled_by(label.id);
+                    show_field_errors(ui, &errors, "description");
                     ui.end_row();
 
-                    ui.label("Expiring: ");
-                    //TODO: implement human readable date
-                    ui.add(
-                        egui::DragValue::new(&mut self.expiring)
-                            .speed(1000)
-                            .clamp_range(0..=100_000_000),
-                    );
+                    self.show_expiring(ui, &errors);
                     ui.end_row();
 
-                    if ui.button("Update").clicked() {
-                        let json = serde_json::to_vec(&self);
-                        match json {
-                            Ok(json) => {
-                                let url = format!(
-                                    "{}/ranking/{}",
-                                    &base_url, &self.ranking_id
-                                );
-                                let mut request = Request::post(url, json);
-                                request.headers.insert(
-                                    "Content-Type".to_string(),
-                                    "application/json".to_string(),
-                                );
-                                request.method = "PUT".to_string();
-                                self.download.download(
-                                    ctx,
-                                    session.access_token.add_authorization_header(request),
-                                );
-                            }
-                            Err(error) => {
-                                // self.error = error.to_string();
+                    ui.label(tr("State: "));
+                    ui.horizontal(|ui| {
+                        ui.strong(tr(self.state.name()));
+                        for (next, action) in self.state.transitions() {
+                            let button = egui::Button::new(tr(action));
+                            if ui.add_enabled(self.transition.is_none(), button).clicked() {
+                                self.send_transition(*next, ctx, base_url, session);
                             }
                         }
+                        self.show_transition(ui);
+                    });
+                    ui.end_row();
+
+                    let update = egui::Button::new(tr("Update"));
+                    if ui.add_enabled(errors.is_empty(), update).clicked() {
+                        self.send_update(ctx, base_url, session);
                     }
 
                     self.download
                         .run_when_downloaded(ui, |response, ui| match response.ok {
                             true => {
-                                ui.label("Success");
-                                ret = Some(Box::new(RankingList::default()));
+                                ui.label(tr("Success"));
+                                ret = Some(Navigation::Back);
                             }
                             false => match response.text() {
                                 Some(err) => {
@@ -206,59 +576,471 @@ pub mod schema {
         }
     }
 
-    #[derive(serde::Deserialize, serde::Serialize)]
+    /// How a single expert's comparison matrix is turned into weights.
+    #[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq, Eq)]
+    #[serde(from = "String", into = "String")]
+    pub enum RankingMethod {
+        Evm,
+        Gmm,
+        /// Sent by the server but not known to this client
+        Unknown(String),
+    }
+
+    impl RankingMethod {
+        pub const ALL: [RankingMethod; 2] = [RankingMethod::Evm, RankingMethod::Gmm];
+
+        pub fn name(&self) -> &str {
+            match self {
+                RankingMethod::Evm => "EVM",
+                RankingMethod::Gmm => "GMM",
+                RankingMethod::Unknown(name) => name,
+            }
+        }
+
+        pub fn description(&self) -> &str {
+            match self {
+                RankingMethod::Evm => {
+                    "Eigenvector method: weights are the principal eigenvector of the comparison matrix"
+                }
+                RankingMethod::Gmm => {
+                    "Geometric mean method: weights are the normalized geometric means of the matrix rows"
+                }
+                RankingMethod::Unknown(_) => "Not supported by this client",
+            }
+        }
+    }
+
+    impl From<String> for RankingMethod {
+        fn from(name: String) -> Self {
+            match name.as_str() {
+                "EVM" => RankingMethod::Evm,
+                "GMM" => RankingMethod::Gmm,
+                _ => RankingMethod::Unknown(name),
+            }
+        }
+    }
+
+    impl From<RankingMethod> for String {
+        fn from(method: RankingMethod) -> Self {
+            method.name().to_string()
+        }
+    }
+
+    /// How the answers of several experts are combined.
+    #[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq, Eq)]
+    #[serde(from = "String", into = "String")]
+    pub enum AggregationMethod {
+        Aij,
+        Aip,
+        /// Sent by the server but not known to this client
+        Unknown(String),
+    }
+
+    impl AggregationMethod {
+        pub const ALL: [AggregationMethod; 2] = [AggregationMethod::Aij, AggregationMethod::Aip];
+
+        pub fn name(&self) -> &str {
+            match self {
+                AggregationMethod::Aij => "AIJ",
+                AggregationMethod::Aip => "AIP",
+                AggregationMethod::Unknown(name) => name,
+            }
+        }
+
+        pub fn description(&self) -> &str {
+            match self {
+                AggregationMethod::Aij => {
+                    "Aggregation of individual judgments: experts' comparisons are combined into one matrix before computing weights"
+                }
+                AggregationMethod::Aip => {
+                    "Aggregation of individual priorities: weights are computed per expert and then combined"
+                }
+                AggregationMethod::Unknown(_) => "Not supported by this client",
+            }
+        }
+    }
+
+    impl From<String> for AggregationMethod {
+        fn from(name: String) -> Self {
+            match name.as_str() {
+                "AIJ" => AggregationMethod::Aij,
+                "AIP" => AggregationMethod::Aip,
+                _ => AggregationMethod::Unknown(name),
+            }
+        }
+    }
+
+    impl From<AggregationMethod> for String {
+        fn from(method: AggregationMethod) -> Self {
+            method.name().to_string()
+        }
+    }
+
+    /// In which order the questions are put to the experts. Stored with the
+    /// variables so the order every expert saw can be reproduced later.
+    #[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+    #[serde(default)]
+    pub struct Presentation {
+        /// Shuffle the pairs instead of asking them in a fixed order
+        pub random_order: bool,
+        /// Shuffle which alternative of a pair is shown on the left
+        pub random_sides: bool,
+        /// Alternate between the criteria instead of asking all pairs of one criterion first
+        pub interleave_criteria: bool,
+        /// Seed of the shuffling, combined with the expert id so every expert gets their own order
+        pub seed: u64,
+    }
+
+    /// Which pairs of alternatives the experts are asked to compare.
+    #[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+    #[serde(tag = "kind", rename_all = "snake_case")]
+    pub enum ComparisonDesign {
+        /// Every pair, n(n-1)/2 comparisons per criterion
+        #[default]
+        Complete,
+        /// The alternatives are put on a circle and each is compared with its
+        /// nearest neighbours, which keeps all of them connected with far fewer comparisons.
+        Cyclic { comparisons_per_alternative: usize },
+    }
+
+    impl ComparisonDesign {
+        /// Pairs of alternative indices to compare out of `alternatives`, each as
+        /// `(lower, higher)`, in ascending order.
+        pub fn pairs(&self, alternatives: usize) -> Vec<(usize, usize)> {
+            let n = alternatives;
+            let mut pairs = std::collections::BTreeSet::new();
+            match *self {
+                ComparisonDesign::Cyclic {
+                    comparisons_per_alternative,
+                } if comparisons_per_alternative < n.saturating_sub(1) => {
+                    // Fewer than two neighbours would not even close the circle
+                    let k = comparisons_per_alternative.max(2);
+                    for offset in 1..=k / 2 {
+                        for i in 0..n {
+                            let j = (i + offset) % n;
+                            pairs.insert((i.min(j), i.max(j)));
+                        }
+                    }
+                    if k % 2 == 1 {
+                        // The opposite alternative gives everyone one more comparison
+                        // when n is even, otherwise round up to the next neighbour.
+                        let offset = match n % 2 {
+                            0 => n / 2,
+                            _ => k / 2 + 1,
+                        };
+                        for i in 0..n {
+                            let j = (i + offset) % n;
+                            pairs.insert((i.min(j), i.max(j)));
+                        }
+                    }
+                }
+                _ => {
+                    for a in 0..n {
+                        for b in a + 1..n {
+                            pairs.insert((a, b));
+                        }
+                    }
+                }
+            }
+            pairs.into_iter().collect()
+        }
+
+        /// Comparisons per criterion an expert is asked for.
+        pub fn pair_count(&self, alternatives: usize) -> usize {
+            match self {
+                ComparisonDesign::Complete => alternatives * alternatives.saturating_sub(1) / 2,
+                ComparisonDesign::Cyclic { .. } => self.pairs(alternatives).len(),
+            }
+        }
+    }
+
+    /// How experts weigh the criteria.
+    #[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+    #[serde(rename_all = "snake_case")]
+    pub enum CriteriaWeighting {
+        /// Every criterion is rated on the ranking's scale
+        #[default]
+        DirectRating,
+        /// Criteria are compared pairwise like the alternatives
+        Pairwise,
+    }
+
+    impl CriteriaWeighting {
+        /// The ways this build can ask experts for, see the `pairwise-criteria` feature.
+        #[cfg(feature = "pairwise-criteria")]
+        pub const ALL: [CriteriaWeighting; 2] =
+            [CriteriaWeighting::DirectRating, CriteriaWeighting::Pairwise];
+        #[cfg(not(feature = "pairwise-criteria"))]
+        pub const ALL: [CriteriaWeighting; 1] = [CriteriaWeighting::DirectRating];
+
+        pub fn name(&self) -> &str {
+            match self {
+                CriteriaWeighting::DirectRating => "Direct rating",
+                CriteriaWeighting::Pairwise => "Pairwise",
+            }
+        }
+
+        pub fn description(&self) -> &str {
+            match self {
+                CriteriaWeighting::DirectRating => {
+                    "Experts pick an entry of the scale for every criterion"
+                }
+                CriteriaWeighting::Pairwise => {
+                    "Experts compare the criteria pairwise, weights are the geometric means of the comparison matrix"
+                }
+            }
+        }
+    }
+
+    /// Weight of a criterion computed from one expert's pairwise comparisons of
+    /// the criteria, relative to its siblings: the weights below every parent sum to 1.
+    #[derive(serde::Deserialize, serde::Serialize, Clone, Debug, PartialEq)]
+    pub struct CriterionWeight {
+        pub ranking_id: i64,
+        pub expert_id: u64,
+        pub criteria_id: i64,
+        pub weight: f64,
+        /// Why the expert weighed the criteria this way, may be empty
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        pub comment: String,
+    }
+
+    #[derive(serde::Deserialize, serde::Serialize, Clone)]
     pub struct Variables {
-        pub ranking_method: String,
-        pub aggregation_method: String,
+        pub ranking_method: RankingMethod,
+        pub aggregation_method: AggregationMethod,
         pub completness_required: bool,
+        #[serde(default)]
+        pub presentation: Presentation,
+        #[serde(default)]
+        pub design: ComparisonDesign,
+        #[serde(default)]
+        pub criteria_weighting: CriteriaWeighting,
         #[serde(skip)]
         pub exists_in_ranking: bool,
         #[serde(skip)]
         download: Download,
     }
 
-    impl Variables {
-        pub fn default() -> Self {
+    impl Default for Variables {
+        fn default() -> Self {
             Self {
-                ranking_method: "EVM".to_string(),
-                aggregation_method: "AIP".to_string(),
+                ranking_method: RankingMethod::Evm,
+                aggregation_method: AggregationMethod::Aip,
                 completness_required: true,
+                presentation: Presentation::default(),
+                design: ComparisonDesign::default(),
+                criteria_weighting: CriteriaWeighting::default(),
                 exists_in_ranking: false,
                 download: Download::default(),
             }
         }
+    }
 
-        pub fn show(&mut self, ui: &mut egui::Ui) {
+    impl Variables {
+        /// Problems that keep the variables from being sent to the server.
+        pub fn warnings(&self) -> Vec<String> {
+            let errors = self.validate(&ValidationContext::default());
+            errors.into_iter().map(|error| error.message).collect()
+        }
+
+        /// Shows the variables of ranking `ranking_id` and creates or updates them on the server.
+        pub fn show(
+            &mut self,
+            ui: &mut egui::Ui,
+            ctx: &egui::Context,
+            base_url: &String,
+            session: &Session,
+            ranking_id: i64,
+        ) {
             egui::Grid::new("Variables grid")
                 .num_columns(3)
                 .show(ui, |ui| {
-                    let mut create_label = |target_str: &mut String, text: &str, ui: &mut Ui| {
-                        if ui.selectable_label(target_str == text, text).clicked() {
-                            *target_str = text.to_string();
+                    ui.label(tr("Ranking method:"));
+                    for method in RankingMethod::ALL {
+                        let selected = self.ranking_method == method;
+                        if ui
+                            .selectable_label(selected, method.name())
+                            .on_hover_text(tr(method.description()))
+                            .clicked()
+                        {
+                            self.ranking_method = method;
                         }
-                    };
-                    ui.label("Ranking method:");
-                    create_label(&mut self.ranking_method, "EVM", ui);
-                    create_label(&mut self.ranking_method, "GMM", ui);
+                    }
+                    ui.end_row();
+                    ui.label(tr("Aggregation method:"));
+                    for method in AggregationMethod::ALL {
+                        let selected = self.aggregation_method == method;
+                        if ui
+                            .selectable_label(selected, method.name())
+                            .on_hover_text(tr(method.description()))
+                            .clicked()
+                        {
+                            self.aggregation_method = method;
+                        }
+                    }
+
+                    ui.end_row();
+                    let label = ui.label(tr("Completness required:"));
+                    ui.checkbox(&mut self.completness_required, "")
+                        .labelled_by(label.id);
+                    ui.end_row();
+
+                    ui.label(tr("Criteria weighting:"));
+                    for weighting in CriteriaWeighting::ALL {
+                        let selected = self.criteria_weighting == weighting;
+                        if ui
+                            .selectable_label(selected, tr(weighting.name()))
+                            .on_hover_text(tr(weighting.description()))
+                            .clicked()
+                        {
+                            self.criteria_weighting = weighting;
+                        }
+                    }
+                    ui.end_row();
+
+                    ui.label(tr("Comparisons:"));
+                    let complete = self.design == ComparisonDesign::Complete;
+                    if ui
+                        .selectable_label(complete, tr("Every pair"))
+                        .on_hover_text(tr("Every expert compares every pair of alternatives"))
+                        .clicked()
+                    {
+                        self.design = ComparisonDesign::Complete;
+                    }
+                    if ui
+                        .selectable_label(!complete, tr("Cyclic"))
+                        .on_hover_text(tr(
+                            "Every alternative is compared with only a few others, for large studies",
+                        ))
+                        .clicked()
+                        && complete
+                    {
+                        self.design = ComparisonDesign::Cyclic {
+                            comparisons_per_alternative: 4,
+                        };
+                    }
                     ui.end_row();
-                    ui.label("Aggregation method:");
-                    create_label(&mut self.aggregation_method, "AIJ", ui);
-                    create_label(&mut self.aggregation_method, "AIP", ui);
+                    if let ComparisonDesign::Cyclic {
+                        comparisons_per_alternative,
+                    } = &mut self.design
+                    {
+                        let label = ui.label(tr("Comparisons per alternative:"));
+                        ui.add(
+                            egui::DragValue::new(comparisons_per_alternative).clamp_range(2..=50),
+                        )
+                        .labelled_by(label.id);
+                        ui.end_row();
+                    }
 
+                    let presentation = &mut self.presentation;
+                    ui.label(tr("Question order:"));
+                    ui.checkbox(&mut presentation.random_order, tr("Random pair order"));
+                    ui.checkbox(&mut presentation.random_sides, tr("Random left/right"));
                     ui.end_row();
-                    ui.label("Completness required:");
-                    ui.checkbox(&mut self.completness_required, "");
+                    ui.label("");
+                    ui.checkbox(&mut presentation.interleave_criteria, tr("Interleave criteria"));
+                    ui.end_row();
+                    let label = ui.label(tr("Seed:"));
+                    ui.add(egui::DragValue::new(&mut presentation.seed))
+                        .labelled_by(label.id);
+                    if ui.button(tr("New seed")).clicked() {
+                        // Kept within 32 bits so it survives a round trip through a JavaScript number
+                        presentation.seed = fastrand::u32(..) as u64;
+                    }
                     ui.end_row();
 
                     if !self.exists_in_ranking {
                         ui.label(
-                            RichText::new(
+                            RichText::new(tr(
                                 "There are no variables in the ranking, consider adding them",
-                            )
+                            ))
                             .color(Color32::RED),
                         );
+                        ui.end_row();
                     }
                 });
+
+            let warnings = self.warnings();
+            for warning in &warnings {
+                ui.label(RichText::new(warning).color(Color32::YELLOW));
+            }
+
+            let text = match self.exists_in_ranking {
+                true => tr("Update"),
+                false => tr("Create"),
+            };
+            if ui
+                .add_enabled(warnings.is_empty(), egui::Button::new(text))
+                .clicked()
+            {
+                // Creating them again replaces the ones the ranking has
+                let url = format!("{}/create_variables/{}", base_url, ranking_id);
+                match api::post_json(url, &*self, &session.access_token) {
+                    Ok(request) => self.download.download(ctx, request),
+                    Err(err) => println!("Failed to serialize variables due to: {}", err),
+                }
+            }
+
+            let mut created = false;
+            self.download
+                .run_when_downloaded(ui, |response, ui| match response.ok {
+                    true => {
+                        ui.label(tr("Success"));
+                        created = true;
+                    }
+                    false => match response.text() {
+                        Some(err) => {
+                            ui.label(err);
+                        }
+                        None => {
+                            ui.label(tr("Unknown error"));
+                        }
+                    },
+                });
+            if created {
+                self.exists_in_ranking = true;
+            }
+        }
+    }
+
+    impl Validate for Variables {
+        fn validate(&self, _context: &ValidationContext<'_>) -> Vec<FieldError> {
+            let mut errors = Vec::new();
+            if let RankingMethod::Unknown(name) = &self.ranking_method {
+                errors.push(field_error(
+                    "ranking_method",
+                    tr_args(
+                        "Unknown ranking method '{name}', pick a supported one",
+                        &[("name", name)],
+                    ),
+                ));
+            }
+            if let AggregationMethod::Unknown(name) = &self.aggregation_method {
+                errors.push(field_error(
+                    "aggregation_method",
+                    tr_args(
+                        "Unknown aggregation method '{name}', pick a supported one",
+                        &[("name", name)],
+                    ),
+                ));
+            }
+            if self.design != ComparisonDesign::Complete && self.completness_required {
+                errors.push(field_error(
+                    "design",
+                    tr("A reduced comparison design never asks every pair, turn off completness required"),
+                ));
+            }
+            if !CriteriaWeighting::ALL.contains(&self.criteria_weighting) {
+                errors.push(field_error(
+                    "criteria_weighting",
+                    tr_args(
+                        "{weighting} criteria weighting isn't supported by this build, pick another one",
+                        &[("weighting", &tr(self.criteria_weighting.name()))],
+                    ),
+                ));
+            }
+            errors
         }
     }
 
@@ -268,6 +1050,24 @@ pub mod schema {
         pub ranking_id: i64,
         pub name: String,
         pub description: String,
+        /// The criterion this one is a sub-criterion of, `None` for main criteria
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub parent_id: Option<i64>,
+        #[serde(default)]
+        pub translations: Translations,
+    }
+
+    impl Criterion {
+        /// This criterion with its name and description in `language`.
+        pub fn localized(&self, language: Language) -> Self {
+            let (name, description) =
+                translate(&self.translations, language, &self.name, &self.description);
+            Self {
+                name: name.to_string(),
+                description: description.to_string(),
+                ..self.clone()
+            }
+        }
     }
 
     impl Default for Criterion {
@@ -277,6 +1077,8 @@ pub mod schema {
                 ranking_id: Default::default(),
                 name: "Strength".to_string(),
                 description: "How strong is the character".to_string(),
+                parent_id: None,
+                translations: Translations::new(),
             }
         }
     }
@@ -288,15 +1090,19 @@ pub mod schema {
             ctx: &egui::Context,
             base_url: &String,
             session: &Session,
-        ) -> Option<Box<dyn View>> {
+            errors: &[FieldError],
+        ) -> Option<Navigation> {
             egui::Grid::new("Criterion editable")
                 .num_columns(2)
                 .show(ui, |ui| {
-                    ui.label("Name: ");
-                    ui.text_edit_singleline(&mut self.name);
+                    let label = ui.label(tr("Name: "));
+                    ui.text_edit_singleline(&mut self.name)
+                        .labelled_by(label.id);
+                    show_field_errors(ui, errors, "name");
                     ui.end_row();
-                    ui.label("description: ");
-                    ui.text_edit_singleline(&mut self.description);
+                    let label = ui.label(tr("description: "));
+                    ui.text_edit_singleline(&mut self.description)
+                        .labelled_by(label.id);
                 });
             None
         }
@@ -315,13 +1121,15 @@ pub mod schema {
     }
 
     pub trait Showable {
+        /// Fields of the value, with `errors` next to the ones they are about.
         fn show_editable(
             &mut self,
             ui: &mut Ui,
             ctx: &egui::Context,
             base_url: &String,
             session: &Session,
-        ) -> Option<Box<dyn View>>;
+            errors: &[FieldError],
+        ) -> Option<Navigation>;
 
         fn show(
             &self,
@@ -332,6 +1140,214 @@ pub mod schema {
         );
     }
 
+    /// A problem with one field of a form.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct FieldError {
+        /// Name of the struct field, like `"email"`
+        pub field: &'static str,
+        pub message: String,
+    }
+
+    fn field_error(field: &'static str, message: impl Into<String>) -> FieldError {
+        FieldError {
+            field,
+            message: message.into(),
+        }
+    }
+
+    /// What validation needs to know besides the value itself.
+    #[derive(Clone, Copy, Default)]
+    pub struct ValidationContext<'a> {
+        /// Unix time of the check, deadlines have to be after it
+        pub now: i64,
+        /// Names already used by others of the same kind in the ranking,
+        /// e-mails for experts
+        pub taken: &'a [String],
+    }
+
+    impl<'a> ValidationContext<'a> {
+        /// Checking against the clock and `taken`.
+        pub fn now(taken: &'a [String]) -> Self {
+            Self {
+                now: chrono::Utc::now().timestamp(),
+                taken,
+            }
+        }
+
+        /// Whether `name` is taken, ignoring case and surrounding spaces.
+        fn is_taken(&self, name: &str) -> bool {
+            let name = name.trim();
+            self.taken
+                .iter()
+                .any(|taken| taken.trim().eq_ignore_ascii_case(name))
+        }
+    }
+
+    /// Checks made before anything is sent to the server, so forms can point
+    /// at the wrong field instead of posting it.
+    pub trait Validate {
+        /// Every problem found, empty when the value can be sent.
+        fn validate(&self, context: &ValidationContext<'_>) -> Vec<FieldError>;
+    }
+
+    /// The errors about `field` in red, for the grid cell after it.
+    pub fn show_field_errors(ui: &mut Ui, errors: &[FieldError], field: &str) {
+        let messages: Vec<&str> = errors
+            .iter()
+            .filter(|error| error.field == field)
+            .map(|error| error.message.as_str())
+            .collect();
+        if !messages.is_empty() {
+            ui.label(RichText::new(messages.join("\n")).color(Color32::RED));
+        }
+    }
+
+    /// Errors about a name that has to be filled in and unique, `taken` is
+    /// like "Another alternative is called {name}".
+    fn check_name(
+        field: &'static str,
+        name: &str,
+        taken: &str,
+        context: &ValidationContext<'_>,
+    ) -> Vec<FieldError> {
+        if name.trim().is_empty() {
+            vec![field_error(field, tr("Can't be empty"))]
+        } else if context.is_taken(name) {
+            vec![field_error(
+                field,
+                tr_args(taken, &[("name", &name.trim())]),
+            )]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn is_web_url(url: &str) -> bool {
+        url.starts_with("https://") || url.starts_with("http://")
+    }
+
+    /// Something like `name@example.com`, what the server can send invitations to.
+    pub fn is_email(email: &str) -> bool {
+        let Some((local, domain)) = email.split_once('@') else {
+            return false;
+        };
+        !local.is_empty()
+            && !domain.contains('@')
+            && domain.contains('.')
+            && !domain.starts_with('.')
+            && !domain.ends_with('.')
+            && !email.chars().any(char::is_whitespace)
+    }
+
+    impl Validate for Alternative {
+        fn validate(&self, context: &ValidationContext<'_>) -> Vec<FieldError> {
+            let mut errors = check_name(
+                "name",
+                &self.name,
+                "Another alternative is called {name}",
+                context,
+            );
+            let picture = is_web_url(&self.image_url) || self.image_url.starts_with("data:image/");
+            if !self.image_url.is_empty() && !picture {
+                errors.push(field_error(
+                    "image_url",
+                    tr("Use an http(s):// address or upload a picture"),
+                ));
+            }
+            for (i, link) in self.links.iter().enumerate() {
+                if !is_web_url(&link.url) {
+                    errors.push(field_error(
+                        "links",
+                        tr_args(
+                            "Link {number} needs an http(s):// URL",
+                            &[("number", &(i + 1))],
+                        ),
+                    ));
+                }
+            }
+            errors
+        }
+    }
+
+    impl Validate for Expert {
+        fn validate(&self, context: &ValidationContext<'_>) -> Vec<FieldError> {
+            let mut errors = Vec::new();
+            if self.name.trim().is_empty() {
+                errors.push(field_error("name", tr("Can't be empty")));
+            }
+            if !is_email(self.email.trim()) {
+                errors.push(field_error(
+                    "email",
+                    tr_args(
+                        "{email} is not an e-mail address",
+                        &[("email", &self.email)],
+                    ),
+                ));
+            } else if context.is_taken(&self.email) {
+                errors.push(field_error(
+                    "email",
+                    tr_args(
+                        "{email} is already an expert of the ranking",
+                        &[("email", &self.email.trim())],
+                    ),
+                ));
+            }
+            errors
+        }
+    }
+
+    impl Validate for Criterion {
+        fn validate(&self, context: &ValidationContext<'_>) -> Vec<FieldError> {
+            check_name(
+                "name",
+                &self.name,
+                "Another criterion is called {name}",
+                context,
+            )
+        }
+    }
+
+    impl Validate for Scale {
+        fn validate(&self, context: &ValidationContext<'_>) -> Vec<FieldError> {
+            let mut errors = check_name(
+                "description",
+                &self.description,
+                "Another scale entry is called {name}",
+                context,
+            );
+            // Weights are ratios of these, so they have to be positive
+            if !(self.value.is_finite() && self.value > 0.0) {
+                errors.push(field_error("value", tr("Has to be greater than zero")));
+            }
+            errors
+        }
+    }
+
+    impl Validate for Ranking {
+        fn validate(&self, context: &ValidationContext<'_>) -> Vec<FieldError> {
+            let mut errors = check_name(
+                "description",
+                &self.description,
+                "Another ranking is called {name}",
+                context,
+            );
+            let typed = self.expiring_text.as_deref().unwrap_or_default().trim();
+            if !typed.is_empty() && parse_date(typed).is_err() {
+                errors.push(field_error(
+                    "expiring",
+                    tr("Write the deadline as YYYY-MM-DD, or leave it blank for never"),
+                ));
+            // 0 is for rankings that never expire
+            } else if self.expiring > 0 && self.expiring <= context.now {
+                errors.push(field_error(
+                    "expiring",
+                    tr("The deadline has passed already"),
+                ));
+            }
+            errors
+        }
+    }
+
     #[derive(serde::Deserialize, serde::Serialize, Clone)]
     pub struct Scale {
         pub scale_id: i64,
@@ -351,6 +1367,46 @@ pub mod schema {
         }
     }
 
+    /// Problems of a whole scale listed from least to most important: the
+    /// values have to go up from entry to entry and no description may repeat.
+    pub fn scale_warnings(scale: &[Scale]) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for pair in scale.windows(2) {
+            if pair[1].value <= pair[0].value {
+                warnings.push(tr_args(
+                    "{entry} ({value}) should be worth more than {previous} ({previous_value})",
+                    &[
+                        ("entry", &pair[1].description),
+                        ("value", &pair[1].value),
+                        ("previous", &pair[0].description),
+                        ("previous_value", &pair[0].value),
+                    ],
+                ));
+            }
+        }
+        for (i, entry) in scale.iter().enumerate() {
+            let description = entry.description.trim();
+            let first = scale
+                .iter()
+                .position(|other| other.description.trim().eq_ignore_ascii_case(description));
+            if !description.is_empty() && first == Some(i) {
+                let count = scale
+                    .iter()
+                    .filter(|other| other.description.trim().eq_ignore_ascii_case(description))
+                    .count();
+                if count > 1 {
+                    warnings.push(tr_plural(
+                        "{description} is listed {count} time",
+                        "{description} is listed {count} times",
+                        count as u64,
+                        &[("description", &description)],
+                    ));
+                }
+            }
+        }
+        warnings
+    }
+
     impl Showable for Scale {
         fn show_editable(
             &mut self,
@@ -358,15 +1414,20 @@ pub mod schema {
             ctx: &egui::Context,
             base_url: &String,
             session: &Session,
-        ) -> Option<Box<dyn View>> {
+            errors: &[FieldError],
+        ) -> Option<Navigation> {
             egui::Grid::new("Scale editable")
                 .num_columns(2)
                 .show(ui, |ui| {
-                    ui.label("Description: ");
-                    ui.text_edit_singleline(&mut self.description);
+                    let label = ui.label(tr("Description: "));
+                    ui.text_edit_singleline(&mut self.description)
+                        .labelled_by(label.id);
+                    show_field_errors(ui, errors, "description");
                     ui.end_row();
-                    ui.label("Value: ");
-                    ui.add(egui::DragValue::new(&mut self.value).speed(0.1));
+                    let label = ui.label(tr("Value: "));
+                    ui.add(egui::DragValue::new(&mut self.value).speed(0.1))
+                        .labelled_by(label.id);
+                    show_field_errors(ui, errors, "value");
                 });
             None
         }
@@ -383,4 +1444,140 @@ pub mod schema {
             ui.label(self.scale_id.to_string());
         }
     }
+
+    /// A single answer to "which alternative is better based on this criterion".
+    #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+    pub struct ABInput {
+        #[serde(rename = "alternativeA_id")]
+        pub alternative_a_id: i64,
+        #[serde(rename = "alternativeB_id")]
+        pub alternative_b_id: i64,
+        pub winner_id: i64,
+        pub expert_id: u64,
+        pub criteria_id: i64,
+        /// Why the expert picked the winner, may be empty
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        pub comment: String,
+        /// When the server got the answer, as a timestamp. Only the export
+        /// has it, it is never sent.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub answered_at: Option<i64>,
+    }
+
+    /// The scale entry an expert picked for a criterion.
+    #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+    pub struct Weights {
+        pub weights_id: i64,
+        pub ranking_id: i64,
+        pub expert_id: u64,
+        pub criteria_id: i64,
+        pub scale_id: i64,
+        /// Why the expert finds the criterion this important, may be empty
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        pub comment: String,
+        /// When the server got the answer, like [`ABInput::answered_at`]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub answered_at: Option<i64>,
+    }
+
+    /// How far an expert got with the questions of a ranking.
+    #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+    pub struct Participation {
+        pub expert_id: u64,
+        /// Number of answered pairs, over all criteria
+        pub comparisons: usize,
+        /// Number of criteria given an importance
+        pub weights: usize,
+        /// Timestamp of the newest answer, `None` before the first one or
+        /// when the server doesn't say
+        pub last_answered: Option<i64>,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ParticipationStatus {
+        NotStarted,
+        Incomplete,
+        Complete,
+    }
+
+    impl Participation {
+        /// Counts the answers of every expert of the ranking in its `export_data`
+        /// and finds the newest of them.
+        pub fn from_export(export: &str) -> Option<Vec<Participation>> {
+            #[derive(serde::Deserialize)]
+            struct Answers {
+                experts: Vec<Expert>,
+                #[serde(default)]
+                comparisons: Vec<ABInput>,
+                #[serde(default)]
+                weights: Vec<Weights>,
+                #[serde(default)]
+                criteria_weights: Vec<CriterionWeight>,
+            }
+            let answers: Answers = serde_json::from_str(export).ok()?;
+            let participation = answers
+                .experts
+                .iter()
+                .map(|expert| {
+                    let id = expert.expert_id;
+                    let comparisons_at = answers
+                        .comparisons
+                        .iter()
+                        .filter(|c| c.expert_id == id)
+                        .filter_map(|c| c.answered_at);
+                    let weights_at = answers
+                        .weights
+                        .iter()
+                        .filter(|w| w.expert_id == id)
+                        .filter_map(|w| w.answered_at);
+                    Participation {
+                        expert_id: id,
+                        comparisons: answers
+                            .comparisons
+                            .iter()
+                            .filter(|c| c.expert_id == id)
+                            .count(),
+                        weights: answers.weights.iter().filter(|w| w.expert_id == id).count()
+                            + answers
+                                .criteria_weights
+                                .iter()
+                                .filter(|w| w.expert_id == id)
+                                .count(),
+                        last_answered: comparisons_at.chain(weights_at).max(),
+                    }
+                })
+                .collect();
+            Some(participation)
+        }
+
+        /// Status given how many answers a ranking with `alternatives` and
+        /// `criteria` needs: every pair for every criterion, and every criterion's weight.
+        pub fn status(&self, alternatives: usize, criteria: usize) -> ParticipationStatus {
+            self.status_in(
+                &ComparisonDesign::Complete,
+                alternatives,
+                criteria,
+                criteria,
+            )
+        }
+
+        /// Status when the experts are only asked the pairs of `design`, for
+        /// the `leaf_criteria` without sub-criteria out of all `criteria`.
+        pub fn status_in(
+            &self,
+            design: &ComparisonDesign,
+            alternatives: usize,
+            leaf_criteria: usize,
+            criteria: usize,
+        ) -> ParticipationStatus {
+            let pairs = design.pair_count(alternatives);
+            if self.comparisons == 0 && self.weights == 0 {
+                ParticipationStatus::NotStarted
+            } else if self.comparisons >= pairs * leaf_criteria && self.weights >= criteria {
+                ParticipationStatus::Complete
+            } else {
+                ParticipationStatus::Incomplete
+            }
+        }
+    }
 }
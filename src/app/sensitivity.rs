@@ -0,0 +1,194 @@
+pub mod sensitivity {
+    //! How the final ranking responds to changes of the criteria weights.
+
+    use serde_json::Value;
+
+    /// Scores of the alternatives on each criterion, taken from the results of
+    /// the algorithm.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Model {
+        pub alternatives: Vec<String>,
+        /// Only the criteria the alternatives were scored on, those without sub-criteria
+        pub criteria: Vec<String>,
+        /// Global weights of `criteria` from the results, summing to 1
+        pub weights: Vec<f64>,
+        /// `scores[alternative][criterion]`
+        pub scores: Vec<Vec<f64>>,
+    }
+
+    /// A range of weights of one criterion over which the same alternative leads.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Stretch {
+        pub from: f64,
+        pub to: f64,
+        pub leader: usize,
+    }
+
+    impl Model {
+        /// Reads an `export_data` response, `None` until the algorithm has run.
+        pub fn from_export(export: &str) -> Option<Model> {
+            let export: Value = serde_json::from_str(export).ok()?;
+            let results = &export["results"];
+            let scores = results["scores"].as_array()?;
+            let named = |key: &str, id_key: &str| -> Vec<(i64, String)> {
+                export[key]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|item| {
+                        Some((item[id_key].as_i64()?, item["name"].as_str()?.to_string()))
+                    })
+                    .collect()
+            };
+            let alternatives = named("alternatives", "alternative_id");
+            let criteria: Vec<(i64, String)> = named("criteria", "criteria_id")
+                .into_iter()
+                .filter(|(id, _)| {
+                    scores
+                        .iter()
+                        .any(|score| score["criteria_id"].as_i64() == Some(*id))
+                })
+                .collect();
+            if alternatives.is_empty() || criteria.is_empty() {
+                return None;
+            }
+
+            let weights: Vec<f64> = criteria
+                .iter()
+                .map(|(id, _)| {
+                    results["criteria_weights"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .find(|weight| weight["criteria_id"].as_i64() == Some(*id))
+                        .and_then(|weight| weight["weight"].as_f64())
+                        .unwrap_or(0.0)
+                })
+                .collect();
+            let total: f64 = weights.iter().sum();
+            let weights = match total > 0.0 {
+                true => weights.iter().map(|weight| weight / total).collect(),
+                false => vec![1.0 / criteria.len() as f64; criteria.len()],
+            };
+            let scores = alternatives
+                .iter()
+                .map(|(alternative_id, _)| {
+                    criteria
+                        .iter()
+                        .map(|(criteria_id, _)| {
+                            scores
+                                .iter()
+                                .find(|score| {
+                                    score["alternative_id"].as_i64() == Some(*alternative_id)
+                                        && score["criteria_id"].as_i64() == Some(*criteria_id)
+                                })
+                                .and_then(|score| score["score"].as_f64())
+                                .unwrap_or(0.0)
+                        })
+                        .collect()
+                })
+                .collect();
+
+            Some(Model {
+                alternatives: alternatives.into_iter().map(|(_, name)| name).collect(),
+                criteria: criteria.into_iter().map(|(_, name)| name).collect(),
+                weights,
+                scores,
+            })
+        }
+
+        /// Total score of every alternative with the given criteria weights.
+        pub fn totals(&self, weights: &[f64]) -> Vec<f64> {
+            self.scores
+                .iter()
+                .map(|scores| scores.iter().zip(weights).map(|(s, w)| s * w).sum())
+                .collect()
+        }
+
+        /// The alternative with the highest total, the first listed one on ties.
+        pub fn leader(&self, weights: &[f64]) -> usize {
+            leader(&self.totals(weights))
+        }
+
+        /// Who leads as the weight of `criterion` goes from 0 to 1 with the
+        /// other criteria keeping their proportions in `weights`. The leader
+        /// changes where one stretch ends and the next begins.
+        pub fn leaders(&self, weights: &[f64], criterion: usize) -> Vec<Stretch> {
+            // Every total is linear in the weight, so it's enough to know both ends
+            let at_zero = self.totals(&set_weight(weights, criterion, 0.0));
+            let at_one = self.totals(&set_weight(weights, criterion, 1.0));
+            let total_at = |weight: f64| -> Vec<f64> {
+                at_zero
+                    .iter()
+                    .zip(&at_one)
+                    .map(|(zero, one)| zero + (one - zero) * weight)
+                    .collect()
+            };
+
+            let mut bounds = vec![0.0, 1.0];
+            for a in 0..at_zero.len() {
+                for b in a + 1..at_zero.len() {
+                    let slopes = (at_one[a] - at_zero[a]) - (at_one[b] - at_zero[b]);
+                    if slopes.abs() < 1e-12 {
+                        continue;
+                    }
+                    let crossing = (at_zero[b] - at_zero[a]) / slopes;
+                    if crossing > 0.0 && crossing < 1.0 {
+                        bounds.push(crossing);
+                    }
+                }
+            }
+            bounds.sort_by(f64::total_cmp);
+            bounds.dedup_by(|a, b| (*a - *b).abs() < 1e-9);
+
+            let mut stretches: Vec<Stretch> = Vec::new();
+            for pair in bounds.windows(2) {
+                let leader = leader(&total_at((pair[0] + pair[1]) / 2.0));
+                match stretches.last_mut() {
+                    Some(last) if last.leader == leader => last.to = pair[1],
+                    _ => stretches.push(Stretch {
+                        from: pair[0],
+                        to: pair[1],
+                        leader,
+                    }),
+                }
+            }
+            stretches
+        }
+    }
+
+    /// `weights` with the one of `criterion` set to `weight` and the others
+    /// scaled to still sum to 1. They're shared equally when all were 0.
+    pub fn set_weight(weights: &[f64], criterion: usize, weight: f64) -> Vec<f64> {
+        if weights.len() < 2 {
+            return vec![1.0; weights.len()];
+        }
+        let weight = weight.clamp(0.0, 1.0);
+        let others: f64 = weights
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != criterion)
+            .map(|(_, w)| w)
+            .sum();
+        let count = (weights.len() - 1) as f64;
+        weights
+            .iter()
+            .enumerate()
+            .map(|(i, w)| match (i == criterion, others > 0.0) {
+                (true, _) => weight,
+                (false, true) => w / others * (1.0 - weight),
+                (false, false) => (1.0 - weight) / count,
+            })
+            .collect()
+    }
+
+    fn leader(totals: &[f64]) -> usize {
+        let mut best = 0;
+        for (i, total) in totals.iter().enumerate() {
+            if *total > totals[best] {
+                best = i;
+            }
+        }
+        best
+    }
+}
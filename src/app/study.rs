@@ -0,0 +1,601 @@
+pub mod study {
+    //! A whole study described in one TOML file, and the changes that make
+    //! a ranking on the server match it.
+    //!
+    //! ```toml
+    //! [ranking]
+    //! description = "Pizzas"
+    //! expires = "2030-01-31"
+    //!
+    //! [variables]
+    //! ranking_method = "GMM"
+    //! aggregation_method = "AIJ"
+    //! completness_required = false
+    //!
+    //! [[alternatives]]
+    //! name = "Margherita"
+    //! description = "Tomato and *mozzarella*"
+    //!
+    //! [[criteria]]
+    //! name = "Taste"
+    //!
+    //! [[criteria]]
+    //! name = "Sweetness"
+    //! parent = "Taste"
+    //!
+    //! [[scale]]
+    //! description = "Important"
+    //! value = 5.0
+    //!
+    //! [[experts]]
+    //! name = "Ada"
+    //! email = "ada@example.com"
+    //! ```
+    //!
+    //! The ranking is found by its description. Alternatives and criteria are
+    //! matched by name, scale entries by description and experts by e-mail:
+    //! whatever the file doesn't list is deleted from the ranking. An expert's
+    //! name and role stay as the server has them, see [`mismatches`].
+
+    use std::collections::{BTreeMap, BTreeSet};
+    use std::fmt;
+
+    use crate::app::hierarchy::hierarchy;
+    use crate::app::schema::schema::{
+        Alternative, Criterion, Expert, Link, Ranking, Scale, Translations, Variables,
+    };
+
+    #[derive(serde::Deserialize, serde::Serialize, Clone)]
+    pub struct Study {
+        pub ranking: RankingEntry,
+        /// Left as they are on the server when missing
+        pub variables: Option<Variables>,
+        #[serde(default)]
+        pub alternatives: Vec<AlternativeEntry>,
+        #[serde(default)]
+        pub criteria: Vec<CriterionEntry>,
+        #[serde(default)]
+        pub scale: Vec<ScaleEntry>,
+        #[serde(default)]
+        pub experts: Vec<ExpertEntry>,
+    }
+
+    #[derive(serde::Deserialize, serde::Serialize, Clone)]
+    pub struct RankingEntry {
+        pub description: String,
+        /// `YYYY-MM-DD`
+        pub expires: String,
+    }
+
+    #[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+    pub struct AlternativeEntry {
+        pub name: String,
+        #[serde(default)]
+        pub description: String,
+        #[serde(default)]
+        pub image_url: String,
+        #[serde(default)]
+        pub links: Vec<Link>,
+        #[serde(default)]
+        pub translations: Translations,
+    }
+
+    #[derive(serde::Deserialize, serde::Serialize, Clone, Default)]
+    pub struct CriterionEntry {
+        pub name: String,
+        #[serde(default)]
+        pub description: String,
+        /// Name of the criterion this one is a sub-criterion of
+        #[serde(default)]
+        pub parent: Option<String>,
+        #[serde(default)]
+        pub translations: Translations,
+    }
+
+    #[derive(serde::Deserialize, serde::Serialize, Clone)]
+    pub struct ScaleEntry {
+        pub description: String,
+        pub value: f32,
+    }
+
+    #[derive(serde::Deserialize, serde::Serialize, Clone)]
+    pub struct ExpertEntry {
+        pub name: String,
+        pub email: String,
+        #[serde(default)]
+        pub admin: bool,
+    }
+
+    impl Study {
+        /// Reads and checks a study file.
+        pub fn from_toml(text: &str) -> Result<Self, String> {
+            let study: Study = toml::from_str(text).map_err(|error| error.to_string())?;
+            study.check()?;
+            Ok(study)
+        }
+
+        fn check(&self) -> Result<(), String> {
+            parse_date(&self.ranking.expires)?;
+            if let Some(warning) = self.variables.as_ref().and_then(|v| v.warnings().pop()) {
+                return Err(warning);
+            }
+            unique("alternative", self.alternatives.iter().map(|a| &a.name))?;
+            unique("criterion", self.criteria.iter().map(|c| &c.name))?;
+            unique("scale entry", self.scale.iter().map(|s| &s.description))?;
+            unique("expert", self.experts.iter().map(|e| &e.email))?;
+            for criterion in &self.criteria {
+                if let Some(parent) = &criterion.parent {
+                    if !self.criteria.iter().any(|c| &c.name == parent) {
+                        return Err(format!(
+                            "The parent {:?} of criterion {:?} isn't in the file",
+                            parent, criterion.name
+                        ));
+                    }
+                }
+            }
+            let tree = self.criteria_tree();
+            // Criteria in a cycle can't be reached from the main ones
+            if hierarchy::tree_order(&tree).len() != tree.len() {
+                return Err("The criteria form a cycle".to_string());
+            }
+            Ok(())
+        }
+
+        /// The criteria as a tree `hierarchy` can walk, ids are positions + 1.
+        fn criteria_tree(&self) -> Vec<Criterion> {
+            let id_of = |name: &str| {
+                let position = self.criteria.iter().position(|c| c.name == name);
+                position.map(|position| position as i64 + 1)
+            };
+            self.criteria
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| Criterion {
+                    criteria_id: i as i64 + 1,
+                    name: entry.name.clone(),
+                    description: entry.description.clone(),
+                    parent_id: entry.parent.as_deref().and_then(id_of),
+                    translations: entry.translations.clone(),
+                    ..Criterion::default()
+                })
+                .collect()
+        }
+    }
+
+    fn unique<'a>(what: &str, keys: impl Iterator<Item = &'a String>) -> Result<(), String> {
+        let mut seen = BTreeSet::new();
+        for key in keys {
+            if !seen.insert(key) {
+                return Err(format!("The {} {:?} is listed twice", what, key));
+            }
+        }
+        Ok(())
+    }
+
+    /// Midnight UTC of a `YYYY-MM-DD` date, as a timestamp.
+    pub fn parse_date(text: &str) -> Result<i64, String> {
+        let date = chrono::NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .map_err(|_| format!("{} is not a YYYY-MM-DD date", text))?;
+        Ok(date.and_hms_opt(0, 0, 0).unwrap().timestamp())
+    }
+
+    /// What the server has for the study's ranking, `ranking` is `None` when
+    /// there's no ranking with its description yet.
+    #[derive(Default)]
+    pub struct Snapshot {
+        pub ranking: Option<Ranking>,
+        pub variables: Option<Variables>,
+        pub alternatives: Vec<Alternative>,
+        pub criteria: Vec<Criterion>,
+        pub scale: Vec<Scale>,
+        pub experts: Vec<Expert>,
+    }
+
+    /// One step towards the study, in the order they have to be made.
+    #[derive(Clone)]
+    pub enum Change {
+        CreateRanking(Ranking),
+        /// New expiry date of the ranking
+        UpdateRanking(Ranking),
+        CreateVariables(Variables),
+        UpdateVariables(Variables),
+        DeleteCriterion(Criterion),
+        DeleteAlternative(Alternative),
+        DeleteScale(Scale),
+        UnassignExpert(Expert),
+        /// `parent` is a name, the criterion may only be created by an earlier change
+        CreateCriterion {
+            criterion: Criterion,
+            parent: Option<String>,
+        },
+        UpdateCriterion(Criterion),
+        CreateAlternative(Alternative),
+        UpdateAlternative(Alternative),
+        CreateScale(Scale),
+        AssignExpert(Expert),
+    }
+
+    impl fmt::Display for Change {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Change::CreateRanking(r) => write!(f, "create ranking {:?}", r.description),
+                Change::UpdateRanking(r) => write!(f, "update ranking {:?}", r.description),
+                Change::CreateVariables(_) => write!(f, "create variables"),
+                Change::UpdateVariables(_) => write!(f, "update variables"),
+                Change::DeleteCriterion(c) => write!(f, "delete criterion {:?}", c.name),
+                Change::DeleteAlternative(a) => write!(f, "delete alternative {:?}", a.name),
+                Change::DeleteScale(s) => write!(f, "delete scale entry {:?}", s.description),
+                Change::UnassignExpert(e) => write!(f, "unassign expert {}", e.email),
+                Change::CreateCriterion { criterion, parent } => match parent {
+                    Some(parent) => write!(
+                        f,
+                        "create criterion {:?} under {:?}",
+                        criterion.name, parent
+                    ),
+                    None => write!(f, "create criterion {:?}", criterion.name),
+                },
+                Change::UpdateCriterion(c) => write!(f, "update criterion {:?}", c.name),
+                Change::CreateAlternative(a) => write!(f, "create alternative {:?}", a.name),
+                Change::UpdateAlternative(a) => write!(f, "update alternative {:?}", a.name),
+                Change::CreateScale(s) => write!(f, "create scale entry {:?}", s.description),
+                Change::AssignExpert(e) => write!(f, "assign expert {}", e.email),
+            }
+        }
+    }
+
+    /// Differences between `study` and `server` no change can fix: experts are
+    /// only ever assigned by e-mail, the server keeps their name and role.
+    pub fn mismatches(study: &Study, server: &Snapshot) -> Vec<String> {
+        let mut mismatches = Vec::new();
+        for entry in &study.experts {
+            let Some(expert) = server.experts.iter().find(|e| e.email == entry.email) else {
+                continue;
+            };
+            if expert.name != entry.name {
+                mismatches.push(format!(
+                    "Expert {} is called {:?} on the server, not {:?}",
+                    entry.email, expert.name, entry.name
+                ));
+            }
+            if expert.admin != entry.admin {
+                mismatches.push(format!(
+                    "Expert {} has admin = {} on the server, not {}",
+                    entry.email, expert.admin, entry.admin
+                ));
+            }
+        }
+        mismatches
+    }
+
+    /// Changes turning `server` into `study`, empty when they already match.
+    pub fn plan(study: &Study, server: &Snapshot) -> Vec<Change> {
+        let mut changes = Vec::new();
+        let expiring = parse_date(&study.ranking.expires).unwrap_or_default();
+        match &server.ranking {
+            None => {
+                let ranking = Ranking::draft(study.ranking.description.clone(), expiring);
+                changes.push(Change::CreateRanking(ranking));
+            }
+            Some(ranking) if ranking.expiring != expiring => {
+                let mut ranking = ranking.clone();
+                ranking.expiring = expiring;
+                changes.push(Change::UpdateRanking(ranking));
+            }
+            Some(_) => {}
+        }
+
+        if let Some(variables) = &study.variables {
+            match &server.variables {
+                None => changes.push(Change::CreateVariables(variables.clone())),
+                Some(existing) if !same_json(existing, variables) => {
+                    changes.push(Change::UpdateVariables(variables.clone()));
+                }
+                Some(_) => {}
+            }
+        }
+
+        let (delete_criteria, create_criteria, update_criteria) = plan_criteria(study, server);
+        changes.extend(delete_criteria);
+
+        let wanted_alternative = |name: &str| study.alternatives.iter().find(|a| a.name == name);
+        for alternative in &server.alternatives {
+            if wanted_alternative(&alternative.name).is_none() {
+                changes.push(Change::DeleteAlternative(alternative.clone()));
+            }
+        }
+        let is_wanted_scale = |scale: &Scale| {
+            study
+                .scale
+                .iter()
+                .any(|s| s.description == scale.description && s.value == scale.value)
+        };
+        for scale in &server.scale {
+            if !is_wanted_scale(scale) {
+                changes.push(Change::DeleteScale(scale.clone()));
+            }
+        }
+        for expert in &server.experts {
+            if !study.experts.iter().any(|e| e.email == expert.email) {
+                changes.push(Change::UnassignExpert(expert.clone()));
+            }
+        }
+
+        changes.extend(create_criteria);
+        changes.extend(update_criteria);
+        for entry in &study.alternatives {
+            let wanted = Alternative {
+                alternative_id: 0,
+                name: entry.name.clone(),
+                description: entry.description.clone(),
+                image_url: entry.image_url.clone(),
+                links: entry.links.clone(),
+                translations: entry.translations.clone(),
+            };
+            match server.alternatives.iter().find(|a| a.name == entry.name) {
+                None => changes.push(Change::CreateAlternative(wanted)),
+                Some(existing) => {
+                    let wanted = Alternative {
+                        alternative_id: existing.alternative_id,
+                        ..wanted
+                    };
+                    if *existing != wanted {
+                        changes.push(Change::UpdateAlternative(wanted));
+                    }
+                }
+            }
+        }
+        for entry in &study.scale {
+            let exists = server
+                .scale
+                .iter()
+                .any(|s| s.description == entry.description && s.value == entry.value);
+            if !exists {
+                changes.push(Change::CreateScale(Scale {
+                    scale_id: 0,
+                    description: entry.description.clone(),
+                    value: entry.value,
+                    ranking_id: 0,
+                }));
+            }
+        }
+        for entry in &study.experts {
+            if !server.experts.iter().any(|e| e.email == entry.email) {
+                changes.push(Change::AssignExpert(Expert {
+                    expert_id: 0,
+                    name: entry.name.clone(),
+                    email: entry.email.clone(),
+                    admin: entry.admin,
+                }));
+            }
+        }
+        changes
+    }
+
+    /// Deletions (sub-criteria first), creations (parents first) and updates
+    /// of the criteria. Criteria can't move in the tree, the ones that do are
+    /// deleted and created again together with everything below them.
+    fn plan_criteria(study: &Study, server: &Snapshot) -> (Vec<Change>, Vec<Change>, Vec<Change>) {
+        let name_of = |id: i64| {
+            let criterion = server.criteria.iter().find(|c| c.criteria_id == id);
+            criterion.map(|c| c.name.clone())
+        };
+        let server_parent = |criterion: &Criterion| criterion.parent_id.and_then(name_of);
+        let entry_of = |name: &str| study.criteria.iter().find(|c| c.name == name);
+
+        let mut recreated: BTreeSet<String> = BTreeSet::new();
+        loop {
+            let before = recreated.len();
+            for criterion in &server.criteria {
+                let Some(entry) = entry_of(&criterion.name) else {
+                    continue;
+                };
+                let parent = server_parent(criterion);
+                let moved = parent != entry.parent;
+                let parent_recreated = parent.map_or(false, |p| recreated.contains(&p));
+                if moved || parent_recreated {
+                    recreated.insert(criterion.name.clone());
+                }
+            }
+            if recreated.len() == before {
+                break;
+            }
+        }
+
+        let mut deletions = Vec::new();
+        let mut by_depth: Vec<(usize, usize)> = hierarchy::tree_order(&server.criteria);
+        by_depth.sort_by_key(|(_, depth)| std::cmp::Reverse(*depth));
+        for (index, _) in by_depth {
+            let criterion = &server.criteria[index];
+            if entry_of(&criterion.name).is_none() || recreated.contains(&criterion.name) {
+                deletions.push(Change::DeleteCriterion(criterion.clone()));
+            }
+        }
+
+        let mut creations = Vec::new();
+        let mut updates = Vec::new();
+        let tree = study.criteria_tree();
+        let existing: BTreeMap<&str, &Criterion> = server
+            .criteria
+            .iter()
+            .map(|c| (c.name.as_str(), c))
+            .collect();
+        for (index, _) in hierarchy::tree_order(&tree) {
+            let entry = &study.criteria[index];
+            let wanted = &tree[index];
+            match existing.get(entry.name.as_str()) {
+                Some(existing) if !recreated.contains(&entry.name) => {
+                    let changed = existing.description != wanted.description
+                        || existing.translations != wanted.translations;
+                    if changed {
+                        updates.push(Change::UpdateCriterion(Criterion {
+                            criteria_id: existing.criteria_id,
+                            ranking_id: existing.ranking_id,
+                            parent_id: existing.parent_id,
+                            ..wanted.clone()
+                        }));
+                    }
+                }
+                _ => creations.push(Change::CreateCriterion {
+                    criterion: Criterion {
+                        criteria_id: 0,
+                        parent_id: None,
+                        ..wanted.clone()
+                    },
+                    parent: entry.parent.clone(),
+                }),
+            }
+        }
+        (deletions, creations, updates)
+    }
+
+    fn same_json<T: serde::Serialize>(a: &T, b: &T) -> bool {
+        serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use blocking::{apply, snapshot};
+
+    #[cfg(not(target_arch = "wasm32"))]
+    mod blocking {
+        use std::collections::BTreeMap;
+
+        use super::{Change, Snapshot, Study};
+        use crate::app::api::api::Client;
+        use crate::app::schema::schema::{Alternative, Criterion, Expert, Ranking, Scale};
+
+        /// What the server has for `study`, matching the ranking by its description.
+        pub fn snapshot(client: &Client, study: &Study) -> Result<Snapshot, String> {
+            let rankings: Vec<Ranking> = client.get("/all_rankings")?;
+            let mut matching = rankings
+                .into_iter()
+                .filter(|r| r.description == study.ranking.description);
+            let Some(ranking) = matching.next() else {
+                return Ok(Snapshot::default());
+            };
+            if matching.next().is_some() {
+                return Err(format!(
+                    "More than one ranking is called {:?}",
+                    study.ranking.description
+                ));
+            }
+            let id = ranking.ranking_id;
+            Ok(Snapshot {
+                // Missing until created
+                variables: client.get_optional(&format!("/variables/{}", id))?,
+                alternatives: client.get(&format!("/all_alternatives/{}", id))?,
+                criteria: client.get(&format!("/criteria/{}", id))?,
+                scale: client.get(&format!("/get_scale/{}", id))?,
+                experts: client.get(&format!("/experts/{}", id))?,
+                ranking: Some(ranking),
+            })
+        }
+
+        /// Makes `changes`, calling `on_change` with each before it is made.
+        /// `server` is the snapshot they were planned from.
+        pub fn apply(
+            client: &Client,
+            server: &Snapshot,
+            changes: &[Change],
+            mut on_change: impl FnMut(&Change),
+        ) -> Result<(), String> {
+            // Checked before anything is made, so the study isn't applied halfway
+            #[cfg(not(feature = "content-updates"))]
+            if let Some(change) = changes.iter().find(|change| {
+                matches!(
+                    change,
+                    Change::UpdateCriterion(_) | Change::UpdateAlternative(_)
+                )
+            }) {
+                return Err(format!(
+                    "Can't {}: this build can't change existing alternatives and criteria, \
+                     rename them in the study to create new ones",
+                    change
+                ));
+            }
+            let mut ranking_id = server.ranking.as_ref().map(|r| r.ranking_id);
+            let mut criteria_ids: BTreeMap<String, i64> = server
+                .criteria
+                .iter()
+                .map(|c| (c.name.clone(), c.criteria_id))
+                .collect();
+            for change in changes {
+                on_change(change);
+                if let Change::CreateRanking(ranking) = change {
+                    let reply = client.post("/create_ranking", ranking)?;
+                    ranking_id = Some(parse::<Ranking>(&reply)?.ranking_id);
+                    continue;
+                }
+                let id = ranking_id.ok_or("The ranking has to be created first")?;
+                match change {
+                    Change::CreateRanking(_) => unreachable!(),
+                    Change::UpdateRanking(ranking) => {
+                        client.put(&format!("/ranking/{}", id), ranking)?;
+                    }
+                    // Creating variables again replaces the existing ones
+                    Change::CreateVariables(variables) | Change::UpdateVariables(variables) => {
+                        client.post(&format!("/create_variables/{}", id), variables)?;
+                    }
+                    Change::DeleteCriterion(criterion) => {
+                        client.delete(&format!("/criteria/{}/{}", id, criterion.criteria_id))?;
+                        criteria_ids.remove(&criterion.name);
+                    }
+                    Change::DeleteAlternative(alternative) => {
+                        let path = format!("/alternative/{}/{}", id, alternative.alternative_id);
+                        client.delete(&path)?;
+                    }
+                    Change::DeleteScale(scale) => {
+                        client.delete(&format!("/scale/{}/{}", id, scale.scale_id))?;
+                    }
+                    Change::UnassignExpert(expert) => {
+                        client.delete(&format!("/experts/{}/{}", id, expert.expert_id))?;
+                    }
+                    Change::CreateCriterion { criterion, parent } => {
+                        let parent_id = match parent {
+                            Some(parent) => Some(
+                                *criteria_ids
+                                    .get(parent)
+                                    .ok_or(format!("The parent {:?} wasn't created", parent))?,
+                            ),
+                            None => None,
+                        };
+                        let criterion = Criterion {
+                            ranking_id: id,
+                            parent_id,
+                            ..criterion.clone()
+                        };
+                        let reply = client.post(&format!("/create_criteria/{}", id), &criterion)?;
+                        let created: Criterion = parse(&reply)?;
+                        criteria_ids.insert(created.name, created.criteria_id);
+                    }
+                    Change::UpdateCriterion(criterion) => {
+                        let path = format!("/criteria/{}/{}", id, criterion.criteria_id);
+                        client.put(&path, criterion)?;
+                    }
+                    Change::CreateAlternative(alternative) => {
+                        client.post(&format!("/create_alternative/{}", id), alternative)?;
+                    }
+                    Change::UpdateAlternative(alternative) => {
+                        let path = format!("/alternative/{}/{}", id, alternative.alternative_id);
+                        client.put::<Alternative>(&path, alternative)?;
+                    }
+                    Change::CreateScale(scale) => {
+                        let scale = Scale {
+                            ranking_id: id,
+                            ..scale.clone()
+                        };
+                        client.post(&format!("/create_scale/{}", id), &scale)?;
+                    }
+                    Change::AssignExpert(expert) => {
+                        client.post::<Expert>(&format!("/create_expert/{}", id), expert)?;
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        fn parse<T: serde::de::DeserializeOwned>(reply: &str) -> Result<T, String> {
+            serde_json::from_str(reply)
+                .map_err(|error| format!("Unexpected reply {:?}: {}", reply, error))
+        }
+    }
+}
@@ -0,0 +1,229 @@
+pub mod translations {
+    use egui::Ui;
+
+    use crate::app::{
+        api::api,
+        download::download::Download,
+        i18n::i18n::{tr, tr_args, Language},
+        login::login::Session,
+        schema::schema::{Alternative, Criterion, Translations},
+    };
+
+    /// Side by side editing of the names and descriptions of the alternatives
+    /// and criteria in another language.
+    pub struct TranslationEditor {
+        ranking_id: i64,
+        language: Language,
+        /// Copies being edited, taken from the downloaded lists
+        alternatives: Vec<Alternative>,
+        criteria: Vec<Criterion>,
+        /// One request per changed alternative or criterion
+        saves: Vec<Download>,
+        error: String,
+    }
+
+    impl TranslationEditor {
+        pub fn new(ranking_id: i64) -> Self {
+            Self {
+                ranking_id,
+                // Content is usually written in English first
+                language: Language::Polish,
+                alternatives: Vec::new(),
+                criteria: Vec::new(),
+                saves: Vec::new(),
+                error: String::new(),
+            }
+        }
+
+        /// Returns true once all changes are saved, the lists should then be
+        /// downloaded again.
+        pub fn show(
+            &mut self,
+            ui: &mut Ui,
+            ctx: &egui::Context,
+            base_url: &String,
+            session: &Session,
+            alternatives: Option<&Vec<Alternative>>,
+            criteria: Option<&Vec<Criterion>>,
+        ) -> bool {
+            let (Some(alternatives), Some(criteria)) = (alternatives, criteria) else {
+                ui.spinner();
+                return false;
+            };
+            // Added or deleted ones show up, edits of the others are lost then
+            if !same_ids(&self.alternatives, alternatives, |a| a.alternative_id) {
+                self.alternatives = alternatives.clone();
+            }
+            if !same_ids(&self.criteria, criteria, |c| c.criteria_id) {
+                self.criteria = criteria.clone();
+            }
+
+            egui::ComboBox::from_label(tr("Translate to"))
+                .selected_text(self.language.name())
+                .show_ui(ui, |ui| {
+                    for option in Language::ALL {
+                        ui.selectable_value(&mut self.language, option, option.name());
+                    }
+                });
+            let code = self.language.code();
+
+            egui::Grid::new("Translations")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("");
+                    ui.strong(tr("Default"));
+                    ui.strong(self.language.name());
+                    ui.end_row();
+                    for alternative in &mut self.alternatives {
+                        show_row(
+                            ui,
+                            ("alternative", alternative.alternative_id),
+                            &mut alternative.translations,
+                            code,
+                            &alternative.name,
+                            &alternative.description,
+                        );
+                    }
+                    for criterion in &mut self.criteria {
+                        show_row(
+                            ui,
+                            ("criterion", criterion.criteria_id),
+                            &mut criterion.translations,
+                            code,
+                            &criterion.name,
+                            &criterion.description,
+                        );
+                    }
+                });
+
+            let changed_alternatives: Vec<&Alternative> = self
+                .alternatives
+                .iter()
+                .filter(|edited| {
+                    alternatives.iter().any(|original| {
+                        original.alternative_id == edited.alternative_id
+                            && original.translations != edited.translations
+                    })
+                })
+                .collect();
+            let changed_criteria: Vec<&Criterion> = self
+                .criteria
+                .iter()
+                .filter(|edited| {
+                    criteria.iter().any(|original| {
+                        original.criteria_id == edited.criteria_id
+                            && original.translations != edited.translations
+                    })
+                })
+                .collect();
+            let changes = changed_alternatives.len() + changed_criteria.len();
+
+            let saving = !self.saves.is_empty();
+            let save = ui.add_enabled(
+                changes > 0 && !saving,
+                egui::Button::new(tr("Save translations")),
+            );
+            if save.clicked() {
+                self.error.clear();
+                let mut requests = Vec::new();
+                for alternative in changed_alternatives {
+                    let url = format!(
+                        "{}/alternative/{}/{}",
+                        base_url, self.ranking_id, alternative.alternative_id
+                    );
+                    requests.push(api::put_json(url, alternative, &session.access_token));
+                }
+                for criterion in changed_criteria {
+                    let url = format!(
+                        "{}/criteria/{}/{}",
+                        base_url, self.ranking_id, criterion.criteria_id
+                    );
+                    requests.push(api::put_json(url, criterion, &session.access_token));
+                }
+                for request in requests {
+                    match request {
+                        Ok(request) => {
+                            let mut download = Download::default();
+                            download.download(ctx, request);
+                            self.saves.push(download);
+                        }
+                        Err(error) => self.error = error,
+                    }
+                }
+            }
+            if !self.error.is_empty() {
+                ui.label(&self.error);
+            }
+            if !saving {
+                return false;
+            }
+
+            let mut finished = true;
+            for download in &self.saves {
+                match download
+                    .promise
+                    .as_ref()
+                    .and_then(|promise| promise.ready())
+                {
+                    None => finished = false,
+                    Some(Ok(response)) if !response.ok => {
+                        self.error = tr_args(
+                            "Failed, server responded with: {status} {text}",
+                            &[
+                                ("status", &response.status),
+                                ("text", &response.status_text),
+                            ],
+                        );
+                    }
+                    Some(Err(error)) => self.error = error.clone(),
+                    Some(Ok(_)) => {}
+                }
+            }
+            if !finished {
+                ui.spinner();
+                return false;
+            }
+            self.saves.clear();
+            true
+        }
+    }
+
+    /// The default name and description next to fields for their translation.
+    fn show_row(
+        ui: &mut Ui,
+        // Keeps the fields focused while other sections of the page load
+        id_source: (&str, i64),
+        translations: &mut Translations,
+        code: &str,
+        name: &str,
+        description: &str,
+    ) {
+        let mut translation = translations.get(code).cloned().unwrap_or_default();
+        let label = ui.label(tr("Name"));
+        let default = ui.label(name);
+        let field = egui::TextEdit::singleline(&mut translation.name)
+            .id_source((id_source, code, "name"))
+            .hint_text(name);
+        ui.add(field).labelled_by(label.id).labelled_by(default.id);
+        ui.end_row();
+        let label = ui.label(tr("Description"));
+        let default = ui.label(description);
+        let field = egui::TextEdit::multiline(&mut translation.description)
+            .id_source((id_source, code, "description"))
+            .hint_text(description);
+        ui.add(field).labelled_by(label.id).labelled_by(default.id);
+        ui.end_row();
+
+        // Blank translations are left out, the default is shown instead
+        if translation.name.is_empty() && translation.description.is_empty() {
+            translations.remove(code);
+        } else {
+            translations.insert(code.to_string(), translation);
+        }
+    }
+
+    fn same_ids<T>(edited: &[T], downloaded: &[T], id: impl Fn(&T) -> i64) -> bool {
+        edited.iter().map(&id).eq(downloaded.iter().map(&id))
+    }
+}
@@ -1,10 +1,67 @@
 use egui::Ui;
 use ehttp::Request;
-use json_minimal::Json;
 
-use crate::app::data::Data;
+use super::login::login::Session;
 
-use super::login::login::{AccessToken, Session};
+/// What a view wants to happen after it was shown.
+pub enum Navigation {
+    /// Open a new view, keeping the current one in the history
+    Push(Box<dyn View>),
+    /// Swap the current view for another one, e.g. a finished form for what it created
+    Replace(Box<dyn View>),
+    /// Return to the previous view, which reloads its data
+    Back,
+}
+
+/// Location of a view that can be linked to, e.g. `#/ranking/12/edit`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Route {
+    RankingList,
+    Rank(i64),
+    EditRanking(i64),
+    Results(i64),
+    Participation(i64),
+    Experts,
+}
+
+impl Route {
+    /// Parses a URL fragment, with or without the leading `#`.
+    pub fn parse(fragment: &str) -> Option<Self> {
+        let path = fragment.trim_start_matches('#').trim_matches('/');
+        let segments: Vec<&str> = path.split('/').collect();
+        match segments.as_slice() {
+            [""] => Some(Route::RankingList),
+            ["rank", id] => id.parse().ok().map(Route::Rank),
+            ["ranking", id, "edit"] => id.parse().ok().map(Route::EditRanking),
+            ["ranking", id, "results"] => id.parse().ok().map(Route::Results),
+            ["ranking", id, "participation"] => id.parse().ok().map(Route::Participation),
+            ["experts"] => Some(Route::Experts),
+            _ => None,
+        }
+    }
+
+    pub fn fragment(&self) -> String {
+        match self {
+            Route::RankingList => "#/".to_string(),
+            Route::Rank(id) => format!("#/rank/{}", id),
+            Route::EditRanking(id) => format!("#/ranking/{}/edit", id),
+            Route::Results(id) => format!("#/ranking/{}/results", id),
+            Route::Participation(id) => format!("#/ranking/{}/participation", id),
+            Route::Experts => "#/experts".to_string(),
+        }
+    }
+
+    /// Address of the route in the web app, for sharing. The native app has
+    /// no address of its own, so there the web app is expected at `base_url`.
+    pub fn link(&self, base_url: &str) -> String {
+        #[cfg(target_arch = "wasm32")]
+        if let Some(Ok(href)) = eframe::web_sys::window().map(|w| w.location().href()) {
+            let page = href.split('#').next().unwrap_or_default();
+            return format!("{}{}", page, self.fragment());
+        }
+        format!("{}/{}", base_url.trim_end_matches('/'), self.fragment())
+    }
+}
 
 pub trait View {
     fn show(
@@ -13,7 +70,18 @@ pub trait View {
         ctx: &egui::Context,
         base_url: &String,
         session: &Session,
-    ) -> Option<Box<dyn View>>;
+    ) -> Option<Navigation>;
     fn get_request(&self, base_url: &String, session: &Session) -> Option<Request>;
     fn populate_from_json(&mut self, json: &String);
+
+    /// Short name shown in the breadcrumbs.
+    fn title(&self) -> String;
+
+    /// Where this view can be linked to, if anywhere.
+    fn route(&self) -> Option<Route> {
+        None
+    }
+
+    /// Called when the view becomes current again through Back or Forward.
+    fn refresh(&mut self, _ctx: &egui::Context, _base_url: &String, _session: &Session) {}
 }
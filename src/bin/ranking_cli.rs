@@ -0,0 +1,340 @@
+//! Admin tasks from the command line, for scripting the setup of a study.
+//!
+//! `cargo run --bin ranking_cli -- login example@example.com`, then see
+//! `ranking_cli help` for the other commands. The login is kept in the file
+//! named by `RANKING_SESSION`, `~/.ranking_session.json` by default.
+
+#![warn(clippy::all, rust_2018_idioms)]
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(error) = cli::run(args) {
+        eprintln!("Error: {}", error);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn main() {}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod cli {
+    use std::path::PathBuf;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use ranking_client::api::Client;
+    use ranking_client::schema::{Alternative, Criterion, Expert, Ranking, Scale, Variables};
+    use ranking_client::study::{self, parse_date, Study};
+    use ranking_client::AccessToken;
+
+    const USAGE: &str = "\
+Usage: ranking_cli [--url URL] COMMAND [ARGS]
+
+Commands:
+  login EMAIL                                  log in and remember the session
+  rankings                                     list rankings: id, state, expiry date or
+                                               never, description
+  create-ranking DESCRIPTION [--expires DATE]  DATE as YYYY-MM-DD, 30 days from now by default
+  delete-ranking RANKING
+  add-alternative RANKING NAME [DESCRIPTION]
+  add-criterion RANKING NAME [DESCRIPTION] [--parent CRITERION]
+  add-scale RANKING DESCRIPTION VALUE
+  add-expert RANKING NAME EMAIL [--admin]
+  set-variables RANKING FILE                   variables as JSON, like the export has them
+  trigger RANKING [--timeout SECONDS]          run the algorithm and wait for it to finish,
+                                               600 seconds at most by default
+  export RANKING FILE                          write everything about the ranking as JSON
+  apply FILE [--dry-run]                       make the server match a study file, printing
+                                               the changes; --dry-run only prints them
+
+Commands creating something print its id. --url defaults to RANKING_URL, then
+to the server of the last login, then to http://127.0.0.1:8000.";
+
+    /// How many seconds `trigger` waits for the results unless told otherwise.
+    const TRIGGER_TIMEOUT: u64 = 600;
+
+    /// What `login` leaves for the following commands.
+    #[derive(serde::Deserialize, serde::Serialize)]
+    struct SavedSession {
+        base_url: String,
+        access_token: AccessToken,
+    }
+
+    pub fn run(args: Vec<String>) -> Result<(), String> {
+        let mut args = args.into_iter();
+        let mut url = std::env::var("RANKING_URL").ok();
+        let mut positional = Vec::new();
+        let mut options = Vec::new();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--url" => url = Some(args.next().ok_or("--url needs a value")?),
+                "--expires" | "--parent" | "--timeout" => {
+                    let value = args.next().ok_or(format!("{} needs a value", arg))?;
+                    options.push((arg, Some(value)));
+                }
+                "--admin" | "--dry-run" => options.push((arg, None)),
+                "-h" | "--help" => positional.insert(0, "help".to_string()),
+                _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+                _ => positional.push(arg),
+            }
+        }
+        let option = |name: &str| {
+            options
+                .iter()
+                .find(|(option, _)| option == name)
+                .map(|(_, value)| value.clone().unwrap_or_default())
+        };
+
+        let Some((command, rest)) = positional.split_first() else {
+            println!("{}", USAGE);
+            return Ok(());
+        };
+        let rest: Vec<&str> = rest.iter().map(String::as_str).collect();
+        match (command.as_str(), rest.as_slice()) {
+            ("help", _) => {
+                println!("{}", USAGE);
+                Ok(())
+            }
+            ("login", [email]) => {
+                let base_url = url
+                    .or_else(|| load_session().ok().map(|saved| saved.base_url))
+                    .unwrap_or_else(|| "http://127.0.0.1:8000".to_string());
+                let mut client = Client::new(base_url);
+                let expert = client.login(email)?;
+                let saved = SavedSession {
+                    base_url: client.base_url().to_string(),
+                    access_token: client.token().unwrap().clone(),
+                };
+                let json = serde_json::to_string(&saved).map_err(|error| error.to_string())?;
+                save_session(&json).map_err(|error| error.to_string())?;
+                let role = if expert.admin { "admin" } else { "expert" };
+                println!(
+                    "Logged in to {} as {} ({})",
+                    saved.base_url, expert.name, role
+                );
+                Ok(())
+            }
+            ("rankings", []) => {
+                let rankings: Vec<Ranking> = client(url)?.get("/all_rankings")?;
+                for ranking in rankings {
+                    println!(
+                        "{}\t{}\t{}\t{}",
+                        ranking.ranking_id,
+                        ranking.state.name(),
+                        format_date(ranking.expiring),
+                        ranking.description
+                    );
+                }
+                Ok(())
+            }
+            ("create-ranking", [description]) => {
+                let expiring = match option("--expires") {
+                    Some(date) => parse_date(&date)?,
+                    None => chrono::Utc::now().timestamp() + 30 * 24 * 60 * 60,
+                };
+                let ranking = Ranking::draft(description.to_string(), expiring);
+                let created: Ranking = create(&client(url)?, "/create_ranking", &ranking)?;
+                println!("{}", created.ranking_id);
+                Ok(())
+            }
+            ("delete-ranking", [ranking]) => {
+                let ranking_id = parse_id(ranking)?;
+                client(url)?.delete(&format!("/ranking/{}", ranking_id))
+            }
+            ("add-alternative", [ranking, name, description @ ..]) if description.len() <= 1 => {
+                let ranking_id = parse_id(ranking)?;
+                let alternative = Alternative {
+                    alternative_id: 0,
+                    name: name.to_string(),
+                    description: description.first().unwrap_or(&"").to_string(),
+                    ..Alternative::default()
+                };
+                let path = format!("/create_alternative/{}", ranking_id);
+                let created: Alternative = create(&client(url)?, &path, &alternative)?;
+                println!("{}", created.alternative_id);
+                Ok(())
+            }
+            ("add-criterion", [ranking, name, description @ ..]) if description.len() <= 1 => {
+                let ranking_id = parse_id(ranking)?;
+                let criterion = Criterion {
+                    ranking_id,
+                    name: name.to_string(),
+                    description: description.first().unwrap_or(&"").to_string(),
+                    parent_id: option("--parent").as_deref().map(parse_id).transpose()?,
+                    ..Criterion::default()
+                };
+                let path = format!("/create_criteria/{}", ranking_id);
+                let created: Criterion = create(&client(url)?, &path, &criterion)?;
+                println!("{}", created.criteria_id);
+                Ok(())
+            }
+            ("add-scale", [ranking, description, value]) => {
+                let ranking_id = parse_id(ranking)?;
+                let scale = Scale {
+                    scale_id: 0,
+                    description: description.to_string(),
+                    value: value
+                        .parse()
+                        .map_err(|_| format!("{} is not a number", value))?,
+                    ranking_id,
+                };
+                let path = format!("/create_scale/{}", ranking_id);
+                let created: Scale = create(&client(url)?, &path, &scale)?;
+                println!("{}", created.scale_id);
+                Ok(())
+            }
+            ("add-expert", [ranking, name, email]) => {
+                let ranking_id = parse_id(ranking)?;
+                let expert = Expert {
+                    expert_id: 0,
+                    name: name.to_string(),
+                    email: email.to_string(),
+                    admin: option("--admin").is_some(),
+                };
+                let path = format!("/create_expert/{}", ranking_id);
+                let created: Expert = create(&client(url)?, &path, &expert)?;
+                println!("{}", created.expert_id);
+                Ok(())
+            }
+            ("set-variables", [ranking, file]) => {
+                let ranking_id = parse_id(ranking)?;
+                let json = std::fs::read_to_string(file)
+                    .map_err(|error| format!("Cannot read {}: {}", file, error))?;
+                let variables: Variables = serde_json::from_str(&json)
+                    .map_err(|error| format!("{} holds no variables: {}", file, error))?;
+                if let Some(warning) = variables.warnings().first() {
+                    return Err(warning.clone());
+                }
+                // Creating them again replaces the ones the ranking has
+                let path = format!("/create_variables/{}", ranking_id);
+                client(url)?.post(&path, &variables).map(|_| ())
+            }
+            ("trigger", [ranking]) => {
+                let ranking_id = parse_id(ranking)?;
+                let timeout = match option("--timeout") {
+                    Some(seconds) => seconds
+                        .parse()
+                        .map_err(|_| format!("{} is not a number of seconds", seconds))?,
+                    None => TRIGGER_TIMEOUT,
+                };
+                let client = client(url)?;
+                // The server answers once the results are computed, which may
+                // take longer than anyone wants to wait
+                let (sender, receiver) = mpsc::channel();
+                std::thread::spawn(move || {
+                    let path = format!("/trigger_algorithm/{}", ranking_id);
+                    _ = sender.send(client.post(&path, &()));
+                });
+                match receiver.recv_timeout(Duration::from_secs(timeout)) {
+                    Ok(result) => result?,
+                    Err(_) => {
+                        return Err(format!(
+                            "No results after {} seconds, the algorithm may still be running",
+                            timeout
+                        ))
+                    }
+                };
+                println!("Finished");
+                Ok(())
+            }
+            ("export", [ranking, file]) => {
+                let ranking_id = parse_id(ranking)?;
+                let path = format!("/export_data/{}", ranking_id);
+                let export: serde_json::Value = client(url)?.get(&path)?;
+                let json =
+                    serde_json::to_string_pretty(&export).map_err(|error| error.to_string())?;
+                std::fs::write(file, json)
+                    .map_err(|error| format!("Cannot write {}: {}", file, error))
+            }
+            ("apply", [file]) => {
+                let text = std::fs::read_to_string(file)
+                    .map_err(|error| format!("Cannot read {}: {}", file, error))?;
+                let study =
+                    Study::from_toml(&text).map_err(|error| format!("{}: {}", file, error))?;
+                let client = client(url)?;
+                let server = study::snapshot(&client, &study)?;
+                for mismatch in study::mismatches(&study, &server) {
+                    eprintln!("Warning: {}, the study can't change that", mismatch);
+                }
+                let changes = study::plan(&study, &server);
+                if changes.is_empty() {
+                    println!("Up to date");
+                } else if option("--dry-run").is_some() {
+                    for change in &changes {
+                        println!("{}", change);
+                    }
+                } else {
+                    study::apply(&client, &server, &changes, |change| println!("{}", change))?;
+                }
+                Ok(())
+            }
+            _ => Err(format!("Unknown command or wrong arguments\n\n{}", USAGE)),
+        }
+    }
+
+    fn session_path() -> PathBuf {
+        if let Some(path) = std::env::var_os("RANKING_SESSION") {
+            return PathBuf::from(path);
+        }
+        let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+        home.map(PathBuf::from)
+            .unwrap_or_default()
+            .join(".ranking_session.json")
+    }
+
+    /// Writes the session file, readable only by the user as it holds the token.
+    fn save_session(json: &str) -> std::io::Result<()> {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(session_path())?;
+        // Left from before, maybe readable by others
+        #[cfg(unix)]
+        file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+        std::io::Write::write_all(&mut file, json.as_bytes())
+    }
+
+    fn load_session() -> Result<SavedSession, String> {
+        let path = session_path();
+        let json = std::fs::read_to_string(&path)
+            .map_err(|_| "Not logged in, run `ranking_cli login EMAIL` first".to_string())?;
+        serde_json::from_str(&json)
+            .map_err(|error| format!("Broken session file {}: {}", path.display(), error))
+    }
+
+    /// Client with the saved login, talking to `url` if given.
+    fn client(url: Option<String>) -> Result<Client, String> {
+        let saved = load_session()?;
+        Ok(Client::with_token(
+            url.unwrap_or(saved.base_url),
+            saved.access_token,
+        ))
+    }
+
+    /// Posts `value` and reads back what the server created.
+    fn create<T>(client: &Client, path: &str, value: &T) -> Result<T, String>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+    {
+        let reply = client.post(path, value)?;
+        serde_json::from_str(&reply)
+            .map_err(|error| format!("Unexpected reply {:?}: {}", reply, error))
+    }
+
+    fn parse_id(text: &str) -> Result<i64, String> {
+        text.parse().map_err(|_| format!("{} is not an id", text))
+    }
+
+    fn format_date(timestamp: i64) -> String {
+        // 0 is for rankings that never expire
+        if timestamp <= 0 {
+            return "never".to_string();
+        }
+        chrono::NaiveDateTime::from_timestamp_opt(timestamp, 0)
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_default()
+    }
+}
@@ -1,4 +1,17 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod app;
+pub use app::ahp::ahp;
+pub use app::api::api;
+pub use app::hierarchy::hierarchy;
+pub use app::i18n::i18n;
+pub use app::login::login::AccessToken;
+pub use app::markdown::markdown;
+pub use app::schedule::schedule;
+pub use app::schema::schema;
+pub use app::sensitivity::sensitivity;
+pub use app::study::study;
 pub use app::TemplateApp;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod mock_server;
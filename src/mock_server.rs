@@ -0,0 +1,924 @@
+//! In-memory stand-in for the ranking backend.
+//!
+//! Serves the endpoints the client uses on a local port, so the app can be
+//! developed offline and the integration tests don't need the real server:
+//!
+//! ```no_run
+//! use ranking_client::mock_server::{MockServer, State};
+//!
+//! let server = MockServer::start("127.0.0.1:8000", State::example()).unwrap();
+//! println!("Serving on {}", server.base_url());
+//! server.join();
+//! ```
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use crate::hierarchy;
+use crate::schema::{
+    ABInput, Alternative, CriteriaWeighting, Criterion, CriterionWeight, Expert, Ranking, Scale,
+    Translations, Variables, Weights,
+};
+
+/// Everything the mock backend knows about.
+#[derive(Default)]
+pub struct State {
+    pub experts: Vec<Expert>,
+    pub rankings: Vec<Ranking>,
+    /// Ids of the experts assigned to each ranking.
+    pub members: BTreeMap<i64, Vec<u64>>,
+    pub variables: BTreeMap<i64, Variables>,
+    pub alternatives: BTreeMap<i64, Vec<Alternative>>,
+    pub criteria: Vec<Criterion>,
+    pub scales: Vec<Scale>,
+    pub comparisons: BTreeMap<i64, Vec<ABInput>>,
+    pub weights: Vec<Weights>,
+    /// Criteria weights of the experts who compared the criteria pairwise.
+    pub criteria_weights: Vec<CriterionWeight>,
+    /// Output of the last "trigger algorithm" per ranking.
+    pub results: BTreeMap<i64, Value>,
+    /// Keeps every answer to a pair instead of replacing the earlier one, like
+    /// a backend that doesn't follow the contract in the README.
+    pub keep_repeated_answers: bool,
+    /// Endpoints that fail with `500 Internal Server Error`, as the method and
+    /// the first segment of the path, like `"POST create_scale"`.
+    pub failing: Vec<String>,
+    last_id: i64,
+}
+
+impl State {
+    /// An admin (`example@example.com`), an expert (`expert@example.com`) and
+    /// one fully configured ranking they are both assigned to.
+    pub fn example() -> Self {
+        let mut state = Self::default();
+        let admin = state.add_expert("Admin", "example@example.com", true);
+        let expert = state.add_expert("Bill Nye", "expert@example.com", false);
+
+        let month = 30 * 24 * 60 * 60;
+        let ranking = state.add_ranking(
+            "Superheroes ranking",
+            chrono::Utc::now().timestamp() + month,
+        );
+        state.assign(ranking, admin);
+        state.assign(ranking, expert);
+
+        state.add_alternative(ranking, "Batman", "Do people still read comic books?");
+        state.add_alternative(ranking, "Superman", "Faster than a speeding bullet");
+        state.add_alternative(ranking, "Wonder Woman", "Princess of the Amazons");
+        state.add_criterion(ranking, "Strength", "How strong is the character");
+        state.add_criterion(ranking, "Intelligence", "How clever is the character");
+        state.add_scale(ranking, "Not important", 1.0);
+        state.add_scale(ranking, "Important", 3.0);
+        state.add_scale(ranking, "Very important", 5.0);
+        state
+    }
+
+    pub fn next_id(&mut self) -> i64 {
+        self.last_id += 1;
+        self.last_id
+    }
+
+    pub fn add_expert(&mut self, name: &str, email: &str, admin: bool) -> u64 {
+        let expert_id = self.next_id() as u64;
+        self.experts.push(Expert {
+            expert_id,
+            name: name.to_string(),
+            email: email.to_string(),
+            admin,
+        });
+        expert_id
+    }
+
+    pub fn add_ranking(&mut self, description: &str, expiring: i64) -> i64 {
+        let ranking_id = self.next_id();
+        self.rankings
+            .push(Ranking::new(ranking_id, description.to_string(), expiring));
+        ranking_id
+    }
+
+    pub fn assign(&mut self, ranking_id: i64, expert_id: u64) {
+        let members = self.members.entry(ranking_id).or_default();
+        if !members.contains(&expert_id) {
+            members.push(expert_id);
+        }
+    }
+
+    pub fn add_alternative(&mut self, ranking_id: i64, name: &str, description: &str) -> i64 {
+        let alternative_id = self.next_id();
+        self.alternatives
+            .entry(ranking_id)
+            .or_default()
+            .push(Alternative {
+                alternative_id,
+                name: name.to_string(),
+                description: description.to_string(),
+                image_url: String::new(),
+                links: Vec::new(),
+                translations: Translations::new(),
+            });
+        alternative_id
+    }
+
+    pub fn add_criterion(&mut self, ranking_id: i64, name: &str, description: &str) -> i64 {
+        self.add_sub_criterion(ranking_id, None, name, description)
+    }
+
+    pub fn add_sub_criterion(
+        &mut self,
+        ranking_id: i64,
+        parent_id: Option<i64>,
+        name: &str,
+        description: &str,
+    ) -> i64 {
+        let criteria_id = self.next_id();
+        self.criteria.push(Criterion {
+            criteria_id,
+            ranking_id,
+            name: name.to_string(),
+            description: description.to_string(),
+            parent_id,
+            translations: Translations::new(),
+        });
+        criteria_id
+    }
+
+    pub fn add_scale(&mut self, ranking_id: i64, description: &str, value: f32) -> i64 {
+        let scale_id = self.next_id();
+        self.scales.push(Scale {
+            scale_id,
+            description: description.to_string(),
+            value,
+            ranking_id,
+        });
+        scale_id
+    }
+
+    fn is_member(&self, ranking_id: i64, expert_id: u64) -> bool {
+        self.members
+            .get(&ranking_id)
+            .map_or(false, |members| members.contains(&expert_id))
+    }
+
+    fn remove_ranking(&mut self, ranking_id: i64) {
+        self.rankings.retain(|r| r.ranking_id != ranking_id);
+        self.members.remove(&ranking_id);
+        self.variables.remove(&ranking_id);
+        self.alternatives.remove(&ranking_id);
+        self.criteria.retain(|c| c.ranking_id != ranking_id);
+        self.scales.retain(|s| s.ranking_id != ranking_id);
+        self.comparisons.remove(&ranking_id);
+        self.weights.retain(|w| w.ranking_id != ranking_id);
+        self.criteria_weights.retain(|w| w.ranking_id != ranking_id);
+        self.results.remove(&ranking_id);
+    }
+}
+
+/// A running mock backend.
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Binds `addr` and serves requests on a background thread.
+    ///
+    /// Use port 0 to let the OS pick a free port, see [`Self::base_url`].
+    pub fn start(addr: &str, state: State) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(state));
+        let thread = {
+            let state = state.clone();
+            std::thread::Builder::new()
+                .name("mock_server".to_string())
+                .spawn(move || {
+                    for stream in listener.incoming().flatten() {
+                        let state = state.clone();
+                        std::thread::spawn(move || {
+                            if let Err(error) = handle_connection(stream, &state) {
+                                log::warn!("Mock server connection failed: {}", error);
+                            }
+                        });
+                    }
+                })?
+        };
+        Ok(Self {
+            addr,
+            state,
+            thread,
+        })
+    }
+
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Direct access to the backing data, for seeding and inspecting it in tests.
+    pub fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+
+    /// Blocks until the server thread exits, which it never does on its own.
+    pub fn join(self) {
+        _ = self.thread.join();
+    }
+}
+
+struct MockRequest {
+    method: String,
+    path: String,
+    headers: BTreeMap<String, String>,
+    body: Vec<u8>,
+}
+
+/// Either the JSON to answer with, or a status code and a `detail` message.
+type Reply = Result<Value, (u16, String)>;
+
+fn handle_connection(stream: TcpStream, state: &Mutex<State>) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line)? == 0 {
+        return Ok(());
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default();
+    let path = target.split('?').next().unwrap_or_default().to_string();
+
+    let mut headers = BTreeMap::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            headers.insert(key.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let length = headers
+        .get("content-length")
+        .and_then(|length| length.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    let request = MockRequest {
+        method,
+        path,
+        headers,
+        body,
+    };
+    let (status, body) = match request.method.as_str() {
+        // CORS preflight from the web build
+        "OPTIONS" => (200, String::new()),
+        _ => match route(&request, &mut state.lock().unwrap()) {
+            Ok(value) => (200, value.to_string()),
+            Err((status, detail)) => (status, json!({ "detail": detail }).to_string()),
+        },
+    };
+    log::debug!("{} {} -> {}", request.method, request.path, status);
+    write_response(stream, status, &body)
+}
+
+fn write_response(mut stream: TcpStream, status: u16, body: &str) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        409 => "Conflict",
+        422 => "Unprocessable Entity",
+        500 => "Internal Server Error",
+        _ => "Unknown",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Headers: *\r\n\
+         Access-Control-Allow-Methods: *\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn route(request: &MockRequest, state: &mut State) -> Reply {
+    let segments: Vec<&str> = request.path.trim_matches('/').split('/').collect();
+    let method = request.method.as_str();
+    if let ("POST", ["token"]) = (method, segments.as_slice()) {
+        return login(request, state);
+    }
+
+    let user = authenticate(request, state)?;
+    let endpoint = format!("{} {}", method, segments[0]);
+    if state.failing.contains(&endpoint) {
+        return Err((500, "Failing on purpose".to_string()));
+    }
+    match (method, segments.as_slice()) {
+        ("GET", ["me"]) => Ok(json!(user)),
+
+        ("GET", ["all_rankings"]) => {
+            require_admin(&user)?;
+            Ok(json!(state.rankings))
+        }
+        ("GET", ["rankings", expert_id]) => {
+            let expert_id = parse_id(expert_id)?;
+            if !user.admin && user.expert_id != expert_id {
+                return Err((403, "Cannot list rankings of another expert".to_string()));
+            }
+            let rankings: Vec<&Ranking> = state
+                .rankings
+                .iter()
+                .filter(|r| state.is_member(r.ranking_id, expert_id))
+                .filter(|r| user.admin || r.state.visible_to_experts())
+                .collect();
+            Ok(json!(rankings))
+        }
+        ("POST", ["create_ranking"]) => {
+            require_admin(&user)?;
+            let mut ranking: Ranking = parse_body(request)?;
+            ranking.ranking_id = state.next_id();
+            state.rankings.push(ranking.clone());
+            Ok(json!(ranking))
+        }
+        ("PUT", ["ranking", ranking_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            let update: Ranking = parse_body(request)?;
+            let ranking = state
+                .rankings
+                .iter_mut()
+                .find(|r| r.ranking_id == ranking_id)
+                .unwrap();
+            if update.state != ranking.state && !ranking.state.can_become(update.state) {
+                return Err((
+                    409,
+                    format!(
+                        "Cannot move a ranking from {} to {}",
+                        ranking.state.name(),
+                        update.state.name()
+                    ),
+                ));
+            }
+            ranking.description = update.description;
+            ranking.expiring = update.expiring;
+            ranking.state = update.state;
+            Ok(json!(ranking))
+        }
+        ("DELETE", ["ranking", ranking_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            state.remove_ranking(ranking_id);
+            Ok(Value::Null)
+        }
+
+        ("GET", ["variables", ranking_id]) => {
+            let ranking_id = require_member(state, &user, ranking_id)?;
+            match state.variables.get(&ranking_id) {
+                Some(variables) => Ok(json!(variables)),
+                None => Err((404, "Variables not found".to_string())),
+            }
+        }
+        ("POST", ["create_variables", ranking_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            let variables: Variables = parse_body(request)?;
+            require_known_methods(&variables)?;
+            let reply = json!(variables);
+            state.variables.insert(ranking_id, variables);
+            Ok(reply)
+        }
+        ("GET", ["all_alternatives", ranking_id]) => {
+            let ranking_id = require_member(state, &user, ranking_id)?;
+            Ok(json!(state
+                .alternatives
+                .get(&ranking_id)
+                .cloned()
+                .unwrap_or_default()))
+        }
+        ("POST", ["create_alternative", ranking_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            let mut alternative: Alternative = parse_body(request)?;
+            alternative.alternative_id = state.next_id();
+            let reply = json!(alternative);
+            state
+                .alternatives
+                .entry(ranking_id)
+                .or_default()
+                .push(alternative);
+            Ok(reply)
+        }
+        #[cfg(feature = "content-updates")]
+        ("PUT", ["alternative", ranking_id, alternative_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            let alternative_id: i64 = parse_id(alternative_id)?;
+            let update: Alternative = parse_body(request)?;
+            let alternatives = state.alternatives.entry(ranking_id).or_default();
+            match alternatives
+                .iter_mut()
+                .find(|a| a.alternative_id == alternative_id)
+            {
+                Some(alternative) => {
+                    *alternative = Alternative {
+                        alternative_id,
+                        ..update
+                    };
+                    Ok(json!(alternative))
+                }
+                None => Err((404, format!("Alternative {} not found", alternative_id))),
+            }
+        }
+        ("DELETE", ["alternative", ranking_id, alternative_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            let alternative_id: i64 = parse_id(alternative_id)?;
+            let alternatives = state.alternatives.entry(ranking_id).or_default();
+            remove_where(alternatives, |a| a.alternative_id == alternative_id)
+        }
+
+        ("GET", ["experts", ranking_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            let experts: Vec<&Expert> = state
+                .experts
+                .iter()
+                .filter(|e| state.is_member(ranking_id, e.expert_id))
+                .collect();
+            Ok(json!(experts))
+        }
+        ("POST", ["create_expert", ranking_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            let expert: Expert = parse_body(request)?;
+            let expert_id = match state.experts.iter().find(|e| e.email == expert.email) {
+                Some(existing) => existing.expert_id,
+                None => state.add_expert(&expert.name, &expert.email, expert.admin),
+            };
+            state.assign(ranking_id, expert_id);
+            Ok(json!(state
+                .experts
+                .iter()
+                .find(|e| e.expert_id == expert_id)))
+        }
+        ("DELETE", ["experts", ranking_id, expert_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            let expert_id: u64 = parse_id(expert_id)?;
+            let members = state.members.entry(ranking_id).or_default();
+            remove_where(members, |id| *id == expert_id)
+        }
+
+        ("GET", ["criteria", ranking_id]) => {
+            let ranking_id = require_member(state, &user, ranking_id)?;
+            let criteria: Vec<&Criterion> = state
+                .criteria
+                .iter()
+                .filter(|c| c.ranking_id == ranking_id)
+                .collect();
+            Ok(json!(criteria))
+        }
+        ("POST", ["create_criteria", ranking_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            let mut criterion: Criterion = parse_body(request)?;
+            if let Some(parent_id) = criterion.parent_id {
+                let known = state
+                    .criteria
+                    .iter()
+                    .any(|c| c.ranking_id == ranking_id && c.criteria_id == parent_id);
+                if !known {
+                    return Err((422, format!("Unknown parent criterion {}", parent_id)));
+                }
+            }
+            criterion.criteria_id = state.next_id();
+            criterion.ranking_id = ranking_id;
+            state.criteria.push(criterion.clone());
+            Ok(json!(criterion))
+        }
+        #[cfg(feature = "content-updates")]
+        ("PUT", ["criteria", ranking_id, criteria_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            let criteria_id: i64 = parse_id(criteria_id)?;
+            let update: Criterion = parse_body(request)?;
+            match state
+                .criteria
+                .iter_mut()
+                .find(|c| c.ranking_id == ranking_id && c.criteria_id == criteria_id)
+            {
+                Some(criterion) => {
+                    // The place in the tree stays, only the texts change
+                    criterion.name = update.name;
+                    criterion.description = update.description;
+                    criterion.translations = update.translations;
+                    Ok(json!(criterion))
+                }
+                None => Err((404, format!("Criterion {} not found", criteria_id))),
+            }
+        }
+        ("DELETE", ["criteria", ranking_id, criteria_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            let criteria_id: i64 = parse_id(criteria_id)?;
+            if state
+                .criteria
+                .iter()
+                .any(|c| c.parent_id == Some(criteria_id))
+            {
+                return Err((409, "Delete the sub-criteria first".to_string()));
+            }
+            remove_where(&mut state.criteria, |c| {
+                c.ranking_id == ranking_id && c.criteria_id == criteria_id
+            })
+        }
+
+        ("GET", ["get_scale", ranking_id]) => {
+            let ranking_id = require_member(state, &user, ranking_id)?;
+            let scales: Vec<&Scale> = state
+                .scales
+                .iter()
+                .filter(|s| s.ranking_id == ranking_id)
+                .collect();
+            Ok(json!(scales))
+        }
+        ("POST", ["create_scale", ranking_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            let mut scale: Scale = parse_body(request)?;
+            scale.scale_id = state.next_id();
+            scale.ranking_id = ranking_id;
+            state.scales.push(scale.clone());
+            Ok(json!(scale))
+        }
+        ("DELETE", ["scale", ranking_id, scale_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            let scale_id: i64 = parse_id(scale_id)?;
+            remove_where(&mut state.scales, |s| {
+                s.ranking_id == ranking_id && s.scale_id == scale_id
+            })
+        }
+
+        ("POST", ["rankAB", ranking_id]) => {
+            let ranking_id = require_member(state, &user, ranking_id)?;
+            require_open(state, ranking_id)?;
+            let mut input: ABInput = parse_body(request)?;
+            input.expert_id = user.expert_id;
+            input.answered_at = Some(chrono::Utc::now().timestamp());
+            let pair = |i: &ABInput| {
+                let (a, b) = (i.alternative_a_id, i.alternative_b_id);
+                (i.expert_id, i.criteria_id, a.min(b), a.max(b))
+            };
+            // Answering the same pair again replaces the previous answer
+            let comparisons = state.comparisons.entry(ranking_id).or_default();
+            if !state.keep_repeated_answers {
+                comparisons.retain(|c| pair(c) != pair(&input));
+            }
+            comparisons.push(input.clone());
+            Ok(json!(input))
+        }
+        ("POST", ["weight", ranking_id]) => {
+            let ranking_id = require_member(state, &user, ranking_id)?;
+            require_open(state, ranking_id)?;
+            let mut weights: Weights = parse_body(request)?;
+            weights.weights_id = state.next_id();
+            weights.ranking_id = ranking_id;
+            weights.expert_id = user.expert_id;
+            weights.answered_at = Some(chrono::Utc::now().timestamp());
+            state.weights.retain(|w| {
+                !(w.ranking_id == ranking_id
+                    && w.expert_id == weights.expert_id
+                    && w.criteria_id == weights.criteria_id)
+            });
+            state.weights.push(weights.clone());
+            Ok(json!(weights))
+        }
+        #[cfg(feature = "pairwise-criteria")]
+        ("POST", ["criteria_weights", ranking_id]) => {
+            let ranking_id = require_member(state, &user, ranking_id)?;
+            require_open(state, ranking_id)?;
+            let mut weights: Vec<CriterionWeight> = parse_body(request)?;
+            for weight in weights.iter_mut() {
+                let known = state
+                    .criteria
+                    .iter()
+                    .any(|c| c.ranking_id == ranking_id && c.criteria_id == weight.criteria_id);
+                if !known {
+                    return Err((422, format!("Unknown criterion {}", weight.criteria_id)));
+                }
+                weight.ranking_id = ranking_id;
+                weight.expert_id = user.expert_id;
+            }
+            // Weighing again replaces all of the expert's previous weights
+            state
+                .criteria_weights
+                .retain(|w| !(w.ranking_id == ranking_id && w.expert_id == user.expert_id));
+            state.criteria_weights.extend(weights.iter().cloned());
+            Ok(json!(weights))
+        }
+
+        ("POST", ["trigger_algorithm", ranking_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            let results = compute_results(state, ranking_id);
+            state.results.insert(ranking_id, results.clone());
+            Ok(results)
+        }
+        ("GET", ["export_data", ranking_id]) => {
+            require_admin(&user)?;
+            let ranking_id = require_ranking(state, ranking_id)?;
+            Ok(export(state, ranking_id))
+        }
+
+        _ => Err((404, "Not Found".to_string())),
+    }
+}
+
+fn login(request: &MockRequest, state: &State) -> Reply {
+    let body = String::from_utf8_lossy(&request.body);
+    let email = body
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == "username")
+        .map(|(_, value)| form_decode(value))
+        .unwrap_or_default();
+    match state.experts.iter().find(|e| e.email == email) {
+        Some(expert) => Ok(json!({
+            "acces_token": format!("mock-token-{}", expert.expert_id),
+            "token_type": "bearer",
+        })),
+        None => Err((401, "Incorrect username or password".to_string())),
+    }
+}
+
+/// Undoes `application/x-www-form-urlencoded` escaping.
+fn form_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+            (b'+', _) => decoded.push(b' '),
+            (byte, _) => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn authenticate(request: &MockRequest, state: &State) -> Result<Expert, (u16, String)> {
+    request
+        .headers
+        .get("authorization")
+        .and_then(|header| header.split_whitespace().last())
+        .and_then(|token| token.strip_prefix("mock-token-"))
+        .and_then(|id| id.parse::<u64>().ok())
+        .and_then(|id| state.experts.iter().find(|e| e.expert_id == id))
+        .cloned()
+        .ok_or_else(|| (401, "Not authenticated".to_string()))
+}
+
+fn require_admin(user: &Expert) -> Result<(), (u16, String)> {
+    match user.admin {
+        true => Ok(()),
+        false => Err((403, "Admin privileges required".to_string())),
+    }
+}
+
+fn require_ranking(state: &State, ranking_id: &str) -> Result<i64, (u16, String)> {
+    let ranking_id = parse_id(ranking_id)?;
+    match state.rankings.iter().any(|r| r.ranking_id == ranking_id) {
+        true => Ok(ranking_id),
+        false => Err((404, format!("Ranking {} not found", ranking_id))),
+    }
+}
+
+fn require_member(state: &State, user: &Expert, ranking_id: &str) -> Result<i64, (u16, String)> {
+    let ranking_id = require_ranking(state, ranking_id)?;
+    match user.admin || state.is_member(ranking_id, user.expert_id) {
+        true => Ok(ranking_id),
+        false => Err((403, "Not assigned to this ranking".to_string())),
+    }
+}
+
+fn require_open(state: &State, ranking_id: i64) -> Result<(), (u16, String)> {
+    let ranking = state.rankings.iter().find(|r| r.ranking_id == ranking_id);
+    if let Some(reason) = ranking.and_then(|r| r.state.closed_reason()) {
+        return Err((409, reason.to_string()));
+    }
+    let remaining = ranking.and_then(|r| r.remaining_at(chrono::Utc::now()));
+    match remaining {
+        Some(remaining) if remaining.num_seconds() <= 0 => {
+            Err((409, "The ranking has expired".to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn require_known_methods(variables: &Variables) -> Result<(), (u16, String)> {
+    match variables.warnings().first() {
+        Some(warning) => Err((422, warning.clone())),
+        None => Ok(()),
+    }
+}
+
+fn parse_id<T: std::str::FromStr>(id: &str) -> Result<T, (u16, String)> {
+    id.parse()
+        .map_err(|_| (422, format!("'{}' is not a valid id", id)))
+}
+
+fn parse_body<T: DeserializeOwned>(request: &MockRequest) -> Result<T, (u16, String)> {
+    serde_json::from_slice(&request.body).map_err(|error| (422, error.to_string()))
+}
+
+fn remove_where<T>(items: &mut Vec<T>, predicate: impl Fn(&T) -> bool) -> Reply {
+    let len = items.len();
+    items.retain(|item| !predicate(item));
+    match items.len() < len {
+        true => Ok(Value::Null),
+        false => Err((404, "Not Found".to_string())),
+    }
+}
+
+/// A deliberately simple stand-in for the real AHP computation: criteria are
+/// weighted by the mean scale value experts picked, or the mean of their
+/// pairwise weights, relative to their siblings and multiplied down the tree.
+/// Alternatives are scored by their share of won comparisons.
+fn compute_results(state: &State, ranking_id: i64) -> Value {
+    let alternatives = state
+        .alternatives
+        .get(&ranking_id)
+        .cloned()
+        .unwrap_or_default();
+    let comparisons = state
+        .comparisons
+        .get(&ranking_id)
+        .cloned()
+        .unwrap_or_default();
+
+    let pairwise = state.variables.get(&ranking_id).map_or(false, |v| {
+        v.criteria_weighting == CriteriaWeighting::Pairwise
+    });
+    let criteria: Vec<Criterion> = state
+        .criteria
+        .iter()
+        .filter(|c| c.ranking_id == ranking_id)
+        .cloned()
+        .collect();
+    let mut local_weights: Vec<(i64, f64)> = criteria
+        .iter()
+        .map(|criterion| {
+            let of_criterion = |ranking: i64, criteria: i64| {
+                ranking == ranking_id && criteria == criterion.criteria_id
+            };
+            let values: Vec<f64> = match pairwise {
+                true => state
+                    .criteria_weights
+                    .iter()
+                    .filter(|w| of_criterion(w.ranking_id, w.criteria_id))
+                    .map(|w| w.weight)
+                    .collect(),
+                false => state
+                    .weights
+                    .iter()
+                    .filter(|w| of_criterion(w.ranking_id, w.criteria_id))
+                    .filter_map(|w| state.scales.iter().find(|s| s.scale_id == w.scale_id))
+                    .map(|s| s.value as f64)
+                    .collect(),
+            };
+            let mean = match values.is_empty() {
+                true => 1.0,
+                false => values.iter().sum::<f64>() / values.len() as f64,
+            };
+            (criterion.criteria_id, mean)
+        })
+        .collect();
+    // Sub-criteria are weighed against their siblings, then scaled by their parents
+    for group in hierarchy::sibling_groups(&criteria) {
+        let mut siblings: Vec<(i64, f64)> = group.iter().map(|&i| local_weights[i]).collect();
+        normalise(&mut siblings);
+        for (&i, sibling) in group.iter().zip(siblings) {
+            local_weights[i] = sibling;
+        }
+    }
+    let local: Vec<f64> = local_weights.iter().map(|(_, weight)| *weight).collect();
+    let global = hierarchy::global_weights(&criteria, &local);
+    let criteria_weights: Vec<(i64, f64)> = criteria
+        .iter()
+        .zip(&global)
+        .map(|(criterion, weight)| (criterion.criteria_id, *weight))
+        .collect();
+
+    // Alternatives are only compared on the criteria without sub-criteria
+    let leaves: Vec<i64> = hierarchy::leaves(&criteria)
+        .iter()
+        .map(|c| c.criteria_id)
+        .collect();
+    let mut scores = Vec::new();
+    let mut totals: BTreeMap<i64, f64> = BTreeMap::new();
+    let leaf_weights = criteria_weights
+        .iter()
+        .filter(|(criteria_id, _)| leaves.contains(criteria_id));
+    for (criteria_id, weight) in leaf_weights {
+        // Everyone starts with one win so nobody ends up with a zero score
+        let mut wins: Vec<(i64, f64)> = alternatives
+            .iter()
+            .map(|a| {
+                let won = comparisons
+                    .iter()
+                    .filter(|c| c.criteria_id == *criteria_id && c.winner_id == a.alternative_id)
+                    .count();
+                (a.alternative_id, 1.0 + won as f64)
+            })
+            .collect();
+        normalise(&mut wins);
+        for (alternative_id, score) in wins {
+            scores.push(json!({
+                "alternative_id": alternative_id,
+                "criteria_id": criteria_id,
+                "score": score,
+            }));
+            *totals.entry(alternative_id).or_default() += weight * score;
+        }
+    }
+
+    let mut ranking: Vec<(i64, f64)> = totals.into_iter().collect();
+    ranking.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    json!({
+        "criteria_weights": criteria_weights
+            .iter()
+            .zip(&local)
+            .map(|((criteria_id, weight), local_weight)| json!({
+                "criteria_id": criteria_id,
+                "weight": weight,
+                "local_weight": local_weight,
+            }))
+            .collect::<Vec<_>>(),
+        "scores": scores,
+        "ranking": ranking
+            .iter()
+            .map(|(alternative_id, score)| json!({ "alternative_id": alternative_id, "score": score }))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn normalise(values: &mut [(i64, f64)]) {
+    let sum: f64 = values.iter().map(|(_, value)| value).sum();
+    if sum > 0.0 {
+        for (_, value) in values.iter_mut() {
+            *value /= sum;
+        }
+    }
+}
+
+fn export(state: &State, ranking_id: i64) -> Value {
+    let experts: Vec<&Expert> = state
+        .experts
+        .iter()
+        .filter(|e| state.is_member(ranking_id, e.expert_id))
+        .collect();
+    json!({
+        "ranking": state.rankings.iter().find(|r| r.ranking_id == ranking_id),
+        "variables": state.variables.get(&ranking_id),
+        "alternatives": state.alternatives.get(&ranking_id).cloned().unwrap_or_default(),
+        "criteria": state.criteria.iter().filter(|c| c.ranking_id == ranking_id).collect::<Vec<_>>(),
+        "scale": state.scales.iter().filter(|s| s.ranking_id == ranking_id).collect::<Vec<_>>(),
+        "experts": experts,
+        "comparisons": state.comparisons.get(&ranking_id).cloned().unwrap_or_default(),
+        "weights": state.weights.iter().filter(|w| w.ranking_id == ranking_id).collect::<Vec<_>>(),
+        "criteria_weights": state
+            .criteria_weights
+            .iter()
+            .filter(|w| w.ranking_id == ranking_id)
+            .collect::<Vec<_>>(),
+        "results": state.results.get(&ranking_id),
+    })
+}
@@ -0,0 +1,60 @@
+//! Weights from pairwise comparisons.
+
+use ranking_client::ahp::{
+    consistency_ratio, geometric_mean_weights, matrix, Judgement, SCALE_PRESETS,
+};
+
+fn judgement(preferred: usize, other: usize, intensity: f64) -> Judgement {
+    Judgement {
+        preferred,
+        other,
+        intensity,
+    }
+}
+
+#[test]
+fn consistent_judgements_give_their_ratios_as_weights() {
+    // 0 is twice as important as 1, which is twice as important as 2
+    let matrix = matrix(
+        3,
+        &[
+            judgement(0, 1, 2.0),
+            judgement(1, 2, 2.0),
+            judgement(0, 2, 4.0),
+        ],
+    );
+    assert_eq!(matrix[1][0], 0.5);
+    let weights = geometric_mean_weights(&matrix);
+    for (weight, expected) in weights.iter().zip([4.0 / 7.0, 2.0 / 7.0, 1.0 / 7.0]) {
+        assert!((weight - expected).abs() < 1e-9, "{:?}", weights);
+    }
+    assert!(consistency_ratio(&matrix, &weights) < 1e-9);
+}
+
+#[test]
+fn contradicting_judgements_are_inconsistent() {
+    // 0 beats 1 beats 2 beats 0
+    let matrix = matrix(
+        3,
+        &[
+            judgement(0, 1, 9.0),
+            judgement(1, 2, 9.0),
+            judgement(2, 0, 9.0),
+        ],
+    );
+    let weights = geometric_mean_weights(&matrix);
+    assert!((weights.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    assert!(consistency_ratio(&matrix, &weights) > 1.0);
+}
+
+#[test]
+fn presets_start_at_equal_and_go_up() {
+    for preset in &SCALE_PRESETS {
+        assert_eq!(preset.entries[0].1, 1.0, "{}", preset.name);
+        assert!(
+            preset.entries.windows(2).all(|pair| pair[0].1 < pair[1].1),
+            "{}",
+            preset.name
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! Runs the `ranking_cli` binary against the mock backend.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use ranking_client::mock_server::{MockServer, State};
+use ranking_client::schema::RankingMethod;
+
+struct Cli {
+    server: MockServer,
+    session: PathBuf,
+}
+
+impl Cli {
+    fn start(name: &str) -> Self {
+        let server = MockServer::start("127.0.0.1:0", State::example()).unwrap();
+        let session =
+            std::env::temp_dir().join(format!("ranking_cli_{}_{}.json", name, std::process::id()));
+        Self { server, session }
+    }
+
+    /// Runs the command and returns what it printed, panicking when it fails.
+    fn run(&self, args: &[&str]) -> String {
+        let output = self.command(args).output().unwrap();
+        assert!(
+            output.status.success(),
+            "ranking_cli {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    }
+
+    fn command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_ranking_cli"));
+        command
+            .args(["--url", &self.server.base_url()])
+            .args(args)
+            .env("RANKING_SESSION", &self.session)
+            .env_remove("RANKING_URL");
+        command
+    }
+}
+
+impl Drop for Cli {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.session);
+    }
+}
+
+#[test]
+fn a_study_can_be_set_up_and_exported_from_a_script() {
+    let cli = Cli::start("setup");
+    cli.run(&["login", "example@example.com"]);
+
+    let ranking = cli.run(&["create-ranking", "Pizzas", "--expires", "2030-01-31"]);
+    let ranking_id: i64 = ranking.parse().unwrap();
+    for name in ["Margherita", "Capricciosa", "Hawaii"] {
+        cli.run(&["add-alternative", &ranking, name]);
+    }
+    let taste = cli.run(&["add-criterion", &ranking, "Taste"]);
+    cli.run(&["add-criterion", &ranking, "Sweetness", "--parent", &taste]);
+    cli.run(&["add-criterion", &ranking, "Price", "How much it costs"]);
+    cli.run(&["add-scale", &ranking, "Not important", "1"]);
+    cli.run(&["add-scale", &ranking, "Important", "5"]);
+    cli.run(&["add-expert", &ranking, "Ada", "ada@example.com"]);
+
+    let variables = cli.session.with_extension("variables.json");
+    let json =
+        r#"{"ranking_method": "GMM", "aggregation_method": "AIJ", "completness_required": false}"#;
+    std::fs::write(&variables, json).unwrap();
+    cli.run(&["set-variables", &ranking, variables.to_str().unwrap()]);
+    std::fs::remove_file(&variables).unwrap();
+
+    assert!(cli
+        .run(&["rankings"])
+        .lines()
+        .any(|line| line == format!("{}\tDraft\t2030-01-31\tPizzas", ranking_id)));
+    assert_eq!(
+        cli.run(&["trigger", &ranking, "--timeout", "60"]),
+        "Finished"
+    );
+
+    let export = cli.session.with_extension("export.json");
+    cli.run(&["export", &ranking, export.to_str().unwrap()]);
+    let exported: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&export).unwrap()).unwrap();
+    std::fs::remove_file(&export).unwrap();
+    assert_eq!(exported["alternatives"].as_array().unwrap().len(), 3);
+    assert_eq!(exported["criteria"].as_array().unwrap().len(), 3);
+    assert_eq!(exported["experts"][0]["email"], "ada@example.com");
+
+    let state = cli.server.state();
+    let sweetness = state.criteria.iter().find(|c| c.name == "Sweetness");
+    assert_eq!(sweetness.unwrap().parent_id, Some(taste.parse().unwrap()));
+    assert_eq!(
+        state.variables[&ranking_id].ranking_method,
+        RankingMethod::Gmm
+    );
+    assert!(state.results.contains_key(&ranking_id));
+}
+
+#[test]
+fn rankings_can_be_deleted() {
+    let cli = Cli::start("delete");
+    cli.run(&["login", "example@example.com"]);
+    let ranking = cli.run(&["create-ranking", "Short lived"]);
+    cli.run(&["delete-ranking", &ranking]);
+    assert!(!cli.run(&["rankings"]).contains("Short lived"));
+}
+
+#[test]
+fn rankings_without_a_deadline_never_expire() {
+    let cli = Cli::start("never");
+    cli.run(&["login", "example@example.com"]);
+    let ranking_id = cli.server.state().add_ranking("Open-ended", 0);
+    let listed = cli.run(&["rankings"]);
+    let line = format!("{}\tOpen\tnever\tOpen-ended", ranking_id);
+    assert!(listed.lines().any(|l| l == line), "{}", listed);
+}
+
+#[test]
+#[cfg(unix)]
+fn the_session_file_is_private() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let cli = Cli::start("private");
+    std::fs::write(&cli.session, "").unwrap();
+    cli.run(&["login", "example@example.com"]);
+    let mode = std::fs::metadata(&cli.session)
+        .unwrap()
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[test]
+fn commands_fail_with_a_message() {
+    let cli = Cli::start("errors");
+    let output = cli.command(&["rankings"]).output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Not logged in"));
+
+    cli.run(&["login", "expert@example.com"]);
+    let output = cli.command(&["delete-ranking", "1"]).output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("403"));
+}
+
+#[test]
+fn study_files_are_applied_once() {
+    let cli = Cli::start("apply");
+    cli.run(&["login", "example@example.com"]);
+    let file = cli.session.with_extension("study.toml");
+    let study = r#"
+[ranking]
+description = "Pizzas"
+expires = "2030-01-31"
+
+[variables]
+ranking_method = "GMM"
+aggregation_method = "AIJ"
+completness_required = false
+
+[[alternatives]]
+name = "Margherita"
+
+[[criteria]]
+name = "Taste"
+
+[[criteria]]
+name = "Sweetness"
+parent = "Taste"
+
+[[experts]]
+name = "Ada"
+email = "ada@example.com"
+"#;
+    std::fs::write(&file, study).unwrap();
+    let path = file.to_str().unwrap();
+
+    let planned = cli.run(&["apply", path, "--dry-run"]);
+    assert!(planned.contains("create criterion \"Sweetness\" under \"Taste\""));
+    assert!(!cli.run(&["rankings"]).contains("Pizzas"));
+    assert_eq!(cli.run(&["apply", path]), planned);
+    assert_eq!(cli.run(&["apply", path]), "Up to date");
+
+    std::fs::write(&file, study.replace("Margherita", "Hawaii")).unwrap();
+    assert_eq!(
+        cli.run(&["apply", path]),
+        "delete alternative \"Margherita\"\ncreate alternative \"Hawaii\""
+    );
+    #[cfg(not(feature = "content-updates"))]
+    {
+        // The backend can't change Hawaii, so Funghi isn't made either
+        let edited = study.replace(
+            "Margherita",
+            "Hawaii\"\ndescription = \"With pineapple\"\n\n[[alternatives]]\nname = \"Funghi",
+        );
+        std::fs::write(&file, edited).unwrap();
+        let output = cli.command(&["apply", path]).output().unwrap();
+        assert!(!output.status.success());
+        let error = String::from_utf8_lossy(&output.stderr);
+        assert!(error.contains("update alternative \"Hawaii\""), "{}", error);
+        let state = cli.server.state();
+        assert!(!state
+            .alternatives
+            .values()
+            .flatten()
+            .any(|a| a.name == "Funghi"));
+    }
+    std::fs::remove_file(&file).unwrap();
+
+    let state = cli.server.state();
+    let sweetness = state.criteria.iter().find(|c| c.name == "Sweetness");
+    let taste = state.criteria.iter().find(|c| c.name == "Taste");
+    assert_eq!(
+        sweetness.unwrap().parent_id,
+        Some(taste.unwrap().criteria_id)
+    );
+}
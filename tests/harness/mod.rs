@@ -0,0 +1,244 @@
+//! Runs the whole app headlessly against the mock backend.
+//!
+//! Widgets are found by their label in the accessibility tree egui builds every
+//! frame, and clicked by sending the matching accessibility action, so tests
+//! don't depend on layout or screen positions.
+
+use std::time::{Duration, Instant};
+
+use egui::accesskit::{self, Action, ActionRequest, Role};
+use ranking_client::mock_server::{MockServer, State};
+use ranking_client::TemplateApp;
+
+pub struct Harness {
+    pub server: MockServer,
+    pub app: TemplateApp,
+    ctx: egui::Context,
+    events: Vec<egui::Event>,
+    nodes: Vec<(accesskit::NodeId, accesskit::Node)>,
+}
+
+impl Harness {
+    /// The app on its login screen, talking to a mock backend seeded with
+    /// [`State::example`].
+    pub fn new() -> Self {
+        Self::with_state(State::example())
+    }
+
+    pub fn with_state(state: State) -> Self {
+        let server = MockServer::start("127.0.0.1:0", state).unwrap();
+        let app = TemplateApp::with_base_url(server.base_url());
+        let ctx = egui::Context::default();
+        ctx.enable_accesskit();
+        let mut harness = Self {
+            server,
+            app,
+            ctx,
+            events: Vec::new(),
+            nodes: Vec::new(),
+        };
+        harness.step();
+        harness
+    }
+
+    /// Runs a single frame, delivering the queued input events.
+    pub fn step(&mut self) {
+        let input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(
+                egui::Pos2::ZERO,
+                egui::vec2(1024.0, 768.0),
+            )),
+            events: std::mem::take(&mut self.events),
+            ..Default::default()
+        };
+        let app = &mut self.app;
+        let output = self.ctx.run(input, |ctx| app.show(ctx));
+        if let Some(update) = output.platform_output.accesskit_update {
+            self.nodes = update.nodes;
+        }
+    }
+
+    /// Steps frames until `predicate` holds, giving pending requests time to finish.
+    pub fn run_until(&mut self, what: &str, predicate: impl Fn(&Self) -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !predicate(self) {
+            assert!(
+                Instant::now() < deadline,
+                "Timed out waiting for {}, the screen shows: {:#?}",
+                what,
+                self.texts()
+            );
+            std::thread::sleep(Duration::from_millis(5));
+            self.step();
+        }
+    }
+
+    /// Steps frames until a widget labelled exactly `name` is shown.
+    pub fn wait_for(&mut self, name: &str) {
+        self.run_until(name, |harness| harness.has(name));
+    }
+
+    pub fn has(&self, name: &str) -> bool {
+        self.find(|node| node.name() == Some(name)).is_some()
+    }
+
+    /// Every label currently on screen, in tree order.
+    pub fn texts(&self) -> Vec<String> {
+        self.nodes
+            .iter()
+            .filter_map(|(_, node)| node.name().or(node.value()))
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Clicks the widget labelled `name`, waiting for it to appear first.
+    pub fn click(&mut self, name: &str) {
+        self.wait_for(name);
+        let target = self
+            .find(|node| node.name() == Some(name) && node.role() != Role::StaticText)
+            .or_else(|| self.find(|node| node.name() == Some(name)))
+            .unwrap();
+        self.request(Action::Default, target);
+        self.step();
+    }
+
+    /// Clicks the widget screen readers describe as `description`, for buttons
+    /// that share their label, like the "Delete" of every row.
+    pub fn click_described(&mut self, description: &str) {
+        let what = format!("widget described as {:?}", description);
+        self.run_until(&what, |harness| {
+            harness
+                .find(|node| node.description() == Some(description))
+                .is_some()
+        });
+        let target = self
+            .find(|node| node.description() == Some(description))
+            .unwrap();
+        self.request(Action::Default, target);
+        self.step();
+    }
+
+    /// The style the app last drew with.
+    pub fn style(&self) -> std::sync::Arc<egui::Style> {
+        self.ctx.style()
+    }
+
+    /// What screen readers say about the widget labelled `name`, besides its label.
+    pub fn description(&self, name: &str) -> Option<String> {
+        let id = self.find(|node| node.name() == Some(name) && node.role() != Role::StaticText)?;
+        self.node(id).description().map(str::to_string)
+    }
+
+    /// Labels of the text field currently holding `current`.
+    pub fn labels_of_text_field(&self, current: &str) -> Vec<String> {
+        let Some(field) = self.find_text_field(current) else {
+            return Vec::new();
+        };
+        self.node(field)
+            .labelled_by()
+            .iter()
+            .filter_map(|id| self.node(*id).name().map(str::to_string))
+            .collect()
+    }
+
+    /// Moves the slider labelled `name` one step up.
+    pub fn increment(&mut self, name: &str) {
+        self.wait_for(name);
+        let target = self
+            .find(|node| node.name() == Some(name) && node.role() == Role::Slider)
+            .unwrap();
+        self.request(Action::Increment, target);
+        self.step();
+    }
+
+    /// Current value of the slider labelled `name`.
+    pub fn slider_value(&self, name: &str) -> Option<f64> {
+        let id = self.find(|node| node.name() == Some(name) && node.role() == Role::Slider)?;
+        self.node(id).numeric_value()
+    }
+
+    /// Presses and releases `key` without modifiers.
+    pub fn press(&mut self, key: egui::Key) {
+        for pressed in [true, false] {
+            self.events.push(egui::Event::Key {
+                key,
+                pressed,
+                repeat: false,
+                modifiers: egui::Modifiers::NONE,
+            });
+        }
+        self.step();
+    }
+
+    /// Replaces the contents of the text field currently holding `current`.
+    pub fn replace_text(&mut self, current: &str, text: &str) {
+        let what = format!("text field containing {:?}", current);
+        self.run_until(&what, |harness| harness.find_text_field(current).is_some());
+        let target = self.find_text_field(current).unwrap();
+        self.type_into(target, text);
+    }
+
+    /// Replaces the contents of the text field labelled `label`.
+    pub fn fill_in(&mut self, label: &str, text: &str) {
+        let what = format!("text field labelled {:?}", label);
+        self.run_until(&what, |harness| {
+            harness.find_labelled_text_field(label).is_some()
+        });
+        let target = self.find_labelled_text_field(label).unwrap();
+        self.type_into(target, text);
+    }
+
+    fn type_into(&mut self, target: accesskit::NodeId, text: &str) {
+        self.request(Action::Focus, target);
+        self.step();
+
+        self.events.push(egui::Event::Key {
+            key: egui::Key::A,
+            pressed: true,
+            repeat: false,
+            modifiers: egui::Modifiers::COMMAND,
+        });
+        self.events.push(egui::Event::Text(text.to_string()));
+        self.step();
+    }
+
+    fn request(&mut self, action: Action, target: accesskit::NodeId) {
+        self.events
+            .push(egui::Event::AccessKitActionRequest(ActionRequest {
+                action,
+                target,
+                data: None,
+            }));
+    }
+
+    fn node(&self, id: accesskit::NodeId) -> &accesskit::Node {
+        &self
+            .nodes
+            .iter()
+            .find(|(node_id, _)| *node_id == id)
+            .unwrap()
+            .1
+    }
+
+    fn find_text_field(&self, current: &str) -> Option<accesskit::NodeId> {
+        self.find(|node| node.role() == Role::TextField && node.value() == Some(current))
+    }
+
+    fn find_labelled_text_field(&self, label: &str) -> Option<accesskit::NodeId> {
+        self.find(|node| {
+            node.role() == Role::TextField
+                && node.labelled_by().iter().any(|id| {
+                    self.nodes.iter().any(|(node_id, label_node)| {
+                        node_id == id && label_node.name() == Some(label)
+                    })
+                })
+        })
+    }
+
+    fn find(&self, predicate: impl Fn(&accesskit::Node) -> bool) -> Option<accesskit::NodeId> {
+        self.nodes
+            .iter()
+            .find(|(_, node)| predicate(node))
+            .map(|(id, _)| *id)
+    }
+}
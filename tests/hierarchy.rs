@@ -0,0 +1,58 @@
+//! Criteria trees: goal → criteria → sub-criteria.
+
+use ranking_client::hierarchy::{global_weights, leaves, path, sibling_groups, tree_order};
+use ranking_client::schema::Criterion;
+
+fn criterion(criteria_id: i64, name: &str, parent_id: Option<i64>) -> Criterion {
+    Criterion {
+        criteria_id,
+        ranking_id: 1,
+        name: name.to_string(),
+        description: String::new(),
+        parent_id,
+        ..Criterion::default()
+    }
+}
+
+/// Cost (fuel, price) and comfort, listed children first
+fn car_criteria() -> Vec<Criterion> {
+    vec![
+        criterion(3, "Fuel", Some(1)),
+        criterion(1, "Cost", None),
+        criterion(2, "Comfort", None),
+        criterion(4, "Price", Some(1)),
+    ]
+}
+
+#[test]
+fn tree_is_walked_level_by_level_and_depth_first() {
+    let criteria = car_criteria();
+    assert_eq!(sibling_groups(&criteria), vec![vec![1, 2], vec![0, 3]]);
+    assert_eq!(tree_order(&criteria), vec![(1, 0), (0, 1), (3, 1), (2, 0)]);
+    assert_eq!(path(&criteria, 3), "Cost › Price");
+
+    let leaves: Vec<i64> = leaves(&criteria).iter().map(|c| c.criteria_id).collect();
+    assert_eq!(leaves, vec![3, 2, 4]);
+}
+
+#[test]
+fn global_weights_multiply_down_the_tree() {
+    let criteria = car_criteria();
+    // Cost 60% split 25/75 between fuel and price, comfort 40%
+    let global = global_weights(&criteria, &[0.25, 0.6, 0.4, 0.75]);
+    let expected = [0.15, 0.6, 0.4, 0.45];
+    for (weight, expected) in global.iter().zip(expected) {
+        assert!((weight - expected).abs() < 1e-9, "{:?}", global);
+    }
+}
+
+#[test]
+fn broken_parents_do_not_hang() {
+    let criteria = vec![
+        criterion(1, "Loop", Some(2)),
+        criterion(2, "Back", Some(1)),
+        criterion(3, "Orphan", Some(99)),
+    ];
+    assert_eq!(sibling_groups(&criteria), vec![vec![2]]);
+    assert_eq!(global_weights(&criteria, &[0.5, 0.5, 1.0]).len(), 3);
+}
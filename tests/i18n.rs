@@ -0,0 +1,91 @@
+//! Message catalogs and plural-aware formatting.
+
+use ranking_client::i18n::{parse_po, set_language, tr, tr_args, tr_plural, Language};
+
+#[test]
+fn po_files_are_parsed_with_plurals_and_continuation_lines() {
+    let catalog = parse_po(
+        r#"
+# A comment
+msgid ""
+msgstr ""
+"Language: pl\n"
+
+msgid "Login"
+msgstr "Zaloguj"
+
+msgid "Untranslated"
+msgstr ""
+
+msgid "{count} file"
+msgid_plural "{count} files"
+msgstr[0] "{count} plik"
+msgstr[1] "{count} pliki"
+msgstr[2] "{count} "
+"plików"
+"#,
+    );
+    assert_eq!(catalog.len(), 2);
+    assert_eq!(catalog["Login"], vec!["Zaloguj"]);
+    assert_eq!(
+        catalog["{count} file"],
+        vec!["{count} plik", "{count} pliki", "{count} plików"]
+    );
+}
+
+#[test]
+fn polish_has_three_plural_forms() {
+    let forms: Vec<usize> = [0, 1, 2, 4, 5, 11, 12, 14, 21, 22, 25, 104, 112]
+        .iter()
+        .map(|&n| Language::Polish.plural_form(n))
+        .collect();
+    assert_eq!(forms, vec![2, 0, 1, 1, 2, 2, 2, 2, 2, 1, 2, 1, 2]);
+    assert_eq!(Language::English.plural_form(1), 0);
+    assert_eq!(Language::English.plural_form(0), 1);
+}
+
+#[test]
+fn messages_follow_the_current_language() {
+    let left = |count| {
+        tr_plural(
+            "{count} comparison left",
+            "{count} comparisons left",
+            count,
+            &[],
+        )
+    };
+
+    set_language(Language::English);
+    assert_eq!(tr("Login"), "Login");
+    assert_eq!(left(1), "1 comparison left");
+    assert_eq!(left(3), "3 comparisons left");
+
+    set_language(Language::Polish);
+    assert_eq!(tr("Login"), "Zaloguj");
+    assert_eq!(
+        tr_args("Expiring: {date}", &[("date", &"2030-01-01")]),
+        "Wygasa: 2030-01-01"
+    );
+    assert_eq!(left(1), "Zostało 1 porównanie");
+    assert_eq!(left(3), "Zostały 3 porównania");
+    assert_eq!(left(12), "Zostało 12 porównań");
+    // Messages missing from the catalog stay in English
+    assert_eq!(tr("Not in the catalog"), "Not in the catalog");
+    set_language(Language::English);
+}
+
+#[test]
+fn polish_catalog_is_complete() {
+    let catalog = Language::Polish.catalog().unwrap();
+    for (id, forms) in catalog {
+        let plural = forms.len() > 1;
+        assert!(!plural || forms.len() == 3, "{} needs 3 forms", id);
+        for form in forms {
+            for placeholder in id.split('{').skip(1) {
+                let name = placeholder.split('}').next().unwrap();
+                let name = format!("{{{}}}", name);
+                assert!(form.contains(&name), "{:?} lost {} in {:?}", id, name, form);
+            }
+        }
+    }
+}
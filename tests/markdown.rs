@@ -0,0 +1,77 @@
+//! The markdown subset alternative descriptions are written in.
+
+use ranking_client::markdown::{parse, Block, Span};
+
+fn text(text: &str) -> Span {
+    Span::Text {
+        text: text.to_string(),
+        strong: false,
+        italic: false,
+        code: false,
+    }
+}
+
+#[test]
+fn headings_bullets_and_paragraphs_are_split() {
+    let blocks = parse("# Pizza\n\nThin crust,\nbaked in a stone oven.\n- cheese\n* basil");
+    assert_eq!(
+        blocks,
+        vec![
+            Block::Heading(1, vec![text("Pizza")]),
+            Block::Paragraph(vec![text("Thin crust, baked in a stone oven.")]),
+            Block::Bullet(vec![text("cheese")]),
+            Block::Bullet(vec![text("basil")]),
+        ]
+    );
+    // Without a space it is just text.
+    assert_eq!(parse("#1"), vec![Block::Paragraph(vec![text("#1")])]);
+}
+
+#[test]
+fn inline_styles_and_links() {
+    let blocks = parse("**Big** and *small* `a*b` see [menu](https://example.com/menu).");
+    let Block::Paragraph(spans) = &blocks[0] else {
+        panic!("{:?}", blocks);
+    };
+    assert_eq!(
+        spans,
+        &vec![
+            Span::Text {
+                text: "Big".to_string(),
+                strong: true,
+                italic: false,
+                code: false,
+            },
+            text(" and "),
+            Span::Text {
+                text: "small".to_string(),
+                strong: false,
+                italic: true,
+                code: false,
+            },
+            text(" "),
+            Span::Text {
+                text: "a*b".to_string(),
+                strong: false,
+                italic: false,
+                code: true,
+            },
+            text(" see "),
+            Span::Link {
+                text: "menu".to_string(),
+                url: "https://example.com/menu".to_string(),
+            },
+            text("."),
+        ]
+    );
+    // Brackets that don't form a link stay as they are.
+    assert_eq!(
+        parse("[not a link] (x)"),
+        vec![Block::Paragraph(vec![text("[not a link] (x)")])]
+    );
+    // So do stars that don't close or don't touch a word.
+    assert_eq!(
+        parse("5 * 3 is 15, *not 16"),
+        vec![Block::Paragraph(vec![text("5 * 3 is 15, *not 16")])]
+    );
+}
@@ -0,0 +1,625 @@
+//! Drives the client's API layer against the in-memory mock backend.
+
+use ranking_client::api::Client;
+use ranking_client::i18n::Language;
+use ranking_client::mock_server::{MockServer, State};
+use ranking_client::schema::{
+    ABInput, AggregationMethod, Alternative, ComparisonDesign, Criterion, Expert, Link,
+    Participation, ParticipationStatus, Presentation, Ranking, RankingMethod, RankingState, Scale,
+    Translation, Translations, Variables, Weights,
+};
+
+fn start() -> MockServer {
+    MockServer::start("127.0.0.1:0", State::example()).unwrap()
+}
+
+fn login(server: &MockServer, email: &str) -> Client {
+    let mut client = Client::new(server.base_url());
+    client.login(email).unwrap();
+    client
+}
+
+#[test]
+fn login_returns_user_info() {
+    let server = start();
+    let mut client = Client::new(server.base_url());
+
+    let admin = client.login("example@example.com").unwrap();
+    assert!(admin.admin);
+    assert!(client.token().is_some());
+
+    let expert = client.login("expert@example.com").unwrap();
+    assert!(!expert.admin);
+    assert_eq!(expert.name, "Bill Nye");
+}
+
+#[test]
+fn emails_with_form_characters_can_log_in() {
+    let server = start();
+    let email = "ada+100%&co@example.com";
+    server.state().add_expert("Ada", email, false);
+    let mut client = Client::new(server.base_url());
+    assert_eq!(client.login(email).unwrap().email, email);
+}
+
+#[test]
+fn unknown_user_cannot_log_in() {
+    let server = start();
+    let mut client = Client::new(server.base_url());
+    let error = client.login("nobody@example.com").unwrap_err();
+    assert!(error.contains("401"), "{}", error);
+}
+
+#[test]
+fn requests_without_token_are_rejected() {
+    let server = start();
+    let client = Client::new(server.base_url());
+    assert!(client.get::<Expert>("/me").is_err());
+}
+
+#[test]
+fn experts_only_see_assigned_rankings() {
+    let server = start();
+    let admin = login(&server, "example@example.com");
+    let expert = login(&server, "expert@example.com");
+    let me: Expert = expert.get("/me").unwrap();
+
+    let ranking = Ranking::new(0, "Unassigned ranking".to_string(), 0);
+    admin.post("/create_ranking", &ranking).unwrap();
+
+    let all: Vec<Ranking> = admin.get("/all_rankings").unwrap();
+    assert_eq!(all.len(), 2);
+
+    let mine: Vec<Ranking> = expert.get(&format!("/rankings/{}", me.expert_id)).unwrap();
+    assert_eq!(mine.len(), 1);
+    assert_eq!(mine[0].description, "Superheroes ranking");
+
+    assert!(expert.get::<Vec<Ranking>>("/all_rankings").is_err());
+}
+
+#[test]
+fn admin_can_set_up_a_ranking() {
+    let server = start();
+    let admin = login(&server, "example@example.com");
+
+    let body = admin
+        .post(
+            "/create_ranking",
+            &Ranking::new(0, "Pizza toppings".to_string(), 0),
+        )
+        .unwrap();
+    let ranking: Ranking = serde_json::from_str(&body).unwrap();
+    let id = ranking.ranking_id;
+
+    admin
+        .post(
+            &format!("/create_alternative/{}", id),
+            &Alternative::default(),
+        )
+        .unwrap();
+    admin
+        .post(&format!("/create_criteria/{}", id), &Criterion::default())
+        .unwrap();
+    admin
+        .post(&format!("/create_scale/{}", id), &Scale::default())
+        .unwrap();
+    admin
+        .post(&format!("/create_expert/{}", id), &Expert::default())
+        .unwrap();
+
+    let alternatives: Vec<Alternative> = admin.get(&format!("/all_alternatives/{}", id)).unwrap();
+    assert_eq!(alternatives.len(), 1);
+    let criteria: Vec<Criterion> = admin.get(&format!("/criteria/{}", id)).unwrap();
+    assert_eq!(criteria.len(), 1);
+    let scale: Vec<Scale> = admin.get(&format!("/get_scale/{}", id)).unwrap();
+    assert_eq!(scale.len(), 1);
+    let experts: Vec<Expert> = admin.get(&format!("/experts/{}", id)).unwrap();
+    assert_eq!(experts.len(), 1);
+
+    admin
+        .delete(&format!(
+            "/alternative/{}/{}",
+            id, alternatives[0].alternative_id
+        ))
+        .unwrap();
+    let alternatives: Vec<Alternative> = admin.get(&format!("/all_alternatives/{}", id)).unwrap();
+    assert!(alternatives.is_empty());
+
+    admin.delete(&format!("/ranking/{}", id)).unwrap();
+    let all: Vec<Ranking> = admin.get("/all_rankings").unwrap();
+    assert!(all.iter().all(|r| r.ranking_id != id));
+}
+
+#[test]
+fn alternatives_keep_their_picture_and_links() {
+    let server = start();
+    let admin = login(&server, "example@example.com");
+    let alternative = Alternative {
+        name: "Margherita".to_string(),
+        description: "# Classic\n- **tomato**\n- mozzarella".to_string(),
+        image_url: "https://example.com/margherita.png".to_string(),
+        links: vec![Link {
+            title: "Recipe".to_string(),
+            url: "https://example.com/recipe".to_string(),
+        }],
+        ..Alternative::default()
+    };
+    let ranking_id = server.state().rankings[0].ranking_id;
+    admin
+        .post(&format!("/create_alternative/{}", ranking_id), &alternative)
+        .unwrap();
+
+    let alternatives: Vec<Alternative> = admin
+        .get(&format!("/all_alternatives/{}", ranking_id))
+        .unwrap();
+    let saved = alternatives
+        .iter()
+        .find(|a| a.name == "Margherita")
+        .unwrap();
+    assert_eq!(saved.description, alternative.description);
+    assert_eq!(saved.image_url, alternative.image_url);
+    assert_eq!(saved.links, alternative.links);
+}
+
+#[test]
+fn translations_are_saved_with_new_alternatives() {
+    let server = start();
+    let admin = login(&server, "example@example.com");
+    let ranking_id = server.state().rankings[0].ranking_id;
+    let alternative = Alternative {
+        name: "Catwoman".to_string(),
+        translations: Translations::from([(
+            Language::Polish.code().to_string(),
+            Translation {
+                name: "Kobieta-Kot".to_string(),
+                description: String::new(),
+            },
+        )]),
+        ..Alternative::default()
+    };
+    admin
+        .post(&format!("/create_alternative/{}", ranking_id), &alternative)
+        .unwrap();
+
+    let alternatives: Vec<Alternative> = admin
+        .get(&format!("/all_alternatives/{}", ranking_id))
+        .unwrap();
+    let saved = alternatives.last().unwrap();
+    assert_eq!(saved.localized(Language::Polish).name, "Kobieta-Kot");
+}
+
+#[test]
+#[cfg(feature = "content-updates")]
+fn translations_are_saved_with_alternatives_and_criteria() {
+    let server = start();
+    let admin = login(&server, "example@example.com");
+    let expert = login(&server, "expert@example.com");
+    let ranking_id = server.state().rankings[0].ranking_id;
+    let polish = |name: &str, description: &str| {
+        Translations::from([(
+            Language::Polish.code().to_string(),
+            Translation {
+                name: name.to_string(),
+                description: description.to_string(),
+            },
+        )])
+    };
+
+    let alternatives: Vec<Alternative> = admin
+        .get(&format!("/all_alternatives/{}", ranking_id))
+        .unwrap();
+    let mut batman = alternatives[0].clone();
+    batman.translations = polish("Człowiek nietoperz", "");
+    admin
+        .put(
+            &format!("/alternative/{}/{}", ranking_id, batman.alternative_id),
+            &batman,
+        )
+        .unwrap();
+
+    let criteria: Vec<Criterion> = admin.get(&format!("/criteria/{}", ranking_id)).unwrap();
+    let mut strength = criteria[0].clone();
+    strength.translations = polish("Siła", "Jak silna jest postać");
+    strength.parent_id = Some(criteria[1].criteria_id);
+    admin
+        .put(
+            &format!("/criteria/{}/{}", ranking_id, strength.criteria_id),
+            &strength,
+        )
+        .unwrap();
+
+    let alternatives: Vec<Alternative> = expert
+        .get(&format!("/all_alternatives/{}", ranking_id))
+        .unwrap();
+    let localized = alternatives[0].localized(Language::Polish);
+    assert_eq!(localized.name, "Człowiek nietoperz");
+    // Not translated, the default shows instead
+    assert_eq!(localized.description, batman.description);
+    assert_eq!(alternatives[0].localized(Language::English).name, "Batman");
+
+    let criteria: Vec<Criterion> = expert.get(&format!("/criteria/{}", ranking_id)).unwrap();
+    assert_eq!(criteria[0].localized(Language::Polish).name, "Siła");
+    // Only the texts change, not the place in the tree
+    assert_eq!(criteria[0].parent_id, None);
+}
+
+#[test]
+fn experts_can_be_assigned_to_more_rankings() {
+    let server = start();
+    let admin = login(&server, "example@example.com");
+    let expert = login(&server, "expert@example.com");
+    let me: Expert = expert.get("/me").unwrap();
+
+    let ranking = Ranking::new(0, "Second ranking".to_string(), 0);
+    admin.post("/create_ranking", &ranking).unwrap();
+    let all: Vec<Ranking> = admin.get("/all_rankings").unwrap();
+    let second = all.last().unwrap().ranking_id;
+
+    // Creating an expert with a known e-mail assigns the existing one
+    let url = format!("/create_expert/{}", second);
+    admin.post(&url, &me).unwrap();
+    admin.post(&url, &me).unwrap();
+    let members: Vec<Expert> = admin.get(&format!("/experts/{}", second)).unwrap();
+    assert_eq!(members, vec![me.clone()]);
+
+    let mine: Vec<Ranking> = expert.get(&format!("/rankings/{}", me.expert_id)).unwrap();
+    assert_eq!(mine.len(), 2);
+    assert!(expert.post(&url, &me).is_err());
+}
+
+#[test]
+fn variables_can_be_created_and_replaced() {
+    let server = start();
+    let admin = login(&server, "example@example.com");
+    let ranking_id = admin.get::<Vec<Ranking>>("/all_rankings").unwrap()[0].ranking_id;
+    let url = format!("/variables/{}", ranking_id);
+    let create_url = format!("/create_variables/{}", ranking_id);
+
+    let mut variables = Variables::default();
+    assert!(admin.get::<Variables>(&url).is_err());
+    admin.post(&create_url, &variables).unwrap();
+
+    variables.ranking_method = RankingMethod::Gmm;
+    variables.presentation = Presentation {
+        random_order: true,
+        random_sides: true,
+        interleave_criteria: false,
+        seed: 1234,
+    };
+    admin.post(&create_url, &variables).unwrap();
+    let stored: Variables = admin.get(&url).unwrap();
+    assert_eq!(stored.ranking_method, RankingMethod::Gmm);
+    assert_eq!(stored.aggregation_method, AggregationMethod::Aip);
+    assert_eq!(stored.presentation, variables.presentation);
+}
+
+#[test]
+fn unknown_methods_are_kept_and_flagged() {
+    let variables: Variables = serde_json::from_str(
+        r#"{"ranking_method":"XYZ","aggregation_method":"AIJ","completness_required":true}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        variables.ranking_method,
+        RankingMethod::Unknown("XYZ".to_string())
+    );
+    assert_eq!(variables.warnings().len(), 1);
+    assert_eq!(variables.presentation, Presentation::default());
+    assert_eq!(
+        serde_json::to_value(&variables).unwrap()["ranking_method"],
+        "XYZ"
+    );
+}
+
+#[test]
+fn answers_end_up_in_the_export() {
+    let server = start();
+    let admin = login(&server, "example@example.com");
+    let expert = login(&server, "expert@example.com");
+    let me: Expert = expert.get("/me").unwrap();
+
+    let ranking_id = server.state().rankings[0].ranking_id;
+    let alternatives: Vec<Alternative> = expert
+        .get(&format!("/all_alternatives/{}", ranking_id))
+        .unwrap();
+    let criteria: Vec<Criterion> = expert.get(&format!("/criteria/{}", ranking_id)).unwrap();
+    let scale: Vec<Scale> = expert.get(&format!("/get_scale/{}", ranking_id)).unwrap();
+
+    let answer = ABInput {
+        alternative_a_id: alternatives[0].alternative_id,
+        alternative_b_id: alternatives[1].alternative_id,
+        winner_id: alternatives[1].alternative_id,
+        expert_id: me.expert_id,
+        criteria_id: criteria[0].criteria_id,
+        comment: "Flies and is bulletproof".to_string(),
+        answered_at: None,
+    };
+    expert
+        .post(&format!("/rankAB/{}", ranking_id), &answer)
+        .unwrap();
+    // Answering the same pair again replaces the first answer
+    expert
+        .post(&format!("/rankAB/{}", ranking_id), &answer)
+        .unwrap();
+    expert
+        .post(
+            &format!("/weight/{}", ranking_id),
+            &Weights {
+                weights_id: 0,
+                ranking_id,
+                expert_id: me.expert_id,
+                criteria_id: criteria[0].criteria_id,
+                scale_id: scale[2].scale_id,
+                comment: "Matters most in a fight".to_string(),
+                answered_at: None,
+            },
+        )
+        .unwrap();
+
+    assert!(expert
+        .post(&format!("/trigger_algorithm/{}", ranking_id), &())
+        .is_err());
+    let results = admin
+        .post(&format!("/trigger_algorithm/{}", ranking_id), &())
+        .unwrap();
+    let results: serde_json::Value = serde_json::from_str(&results).unwrap();
+    assert_eq!(results["ranking"].as_array().unwrap().len(), 3);
+
+    let export: serde_json::Value = admin.get(&format!("/export_data/{}", ranking_id)).unwrap();
+    assert_eq!(export["comparisons"].as_array().unwrap().len(), 1);
+    assert_eq!(export["weights"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        export["comparisons"][0]["comment"],
+        "Flies and is bulletproof"
+    );
+    assert_eq!(export["weights"][0]["comment"], "Matters most in a fight");
+    let winner = &export["results"]["ranking"][0]["alternative_id"];
+    assert_eq!(winner, alternatives[1].alternative_id);
+}
+
+#[test]
+fn participation_counts_each_experts_answers() {
+    let server = start();
+    let admin = login(&server, "example@example.com");
+    let expert = login(&server, "expert@example.com");
+    let me: Expert = expert.get("/me").unwrap();
+    let ranking_id = admin.get::<Vec<Ranking>>("/all_rankings").unwrap()[0].ranking_id;
+    let alternatives: Vec<Alternative> = admin
+        .get(&format!("/all_alternatives/{}", ranking_id))
+        .unwrap();
+    let criteria: Vec<Criterion> = admin.get(&format!("/criteria/{}", ranking_id)).unwrap();
+
+    let answer = ABInput {
+        alternative_a_id: alternatives[0].alternative_id,
+        alternative_b_id: alternatives[1].alternative_id,
+        winner_id: alternatives[0].alternative_id,
+        expert_id: 0,
+        criteria_id: criteria[0].criteria_id,
+        comment: String::new(),
+        answered_at: None,
+    };
+    expert
+        .post(&format!("/rankAB/{}", ranking_id), &answer)
+        .unwrap();
+
+    let export: serde_json::Value = admin.get(&format!("/export_data/{}", ranking_id)).unwrap();
+    let participation = Participation::from_export(&export.to_string()).unwrap();
+    assert_eq!(participation.len(), 2);
+    let mine = participation
+        .iter()
+        .find(|p| p.expert_id == me.expert_id)
+        .unwrap();
+    assert_eq!(mine.comparisons, 1);
+    assert_eq!(mine.weights, 0);
+    let now = chrono::Utc::now().timestamp();
+    assert!(mine.last_answered.map_or(false, |at| (now - at).abs() < 60));
+    assert_eq!(
+        mine.status(alternatives.len(), criteria.len()),
+        ParticipationStatus::Incomplete
+    );
+    let admins = participation
+        .iter()
+        .find(|p| p.expert_id != me.expert_id)
+        .unwrap();
+    assert_eq!(
+        admins.status(alternatives.len(), criteria.len()),
+        ParticipationStatus::NotStarted
+    );
+}
+
+#[test]
+fn answering_everything_completes_participation() {
+    let participation = Participation {
+        expert_id: 1,
+        comparisons: 6,
+        weights: 2,
+        last_answered: None,
+    };
+    assert_eq!(participation.status(3, 2), ParticipationStatus::Complete);
+    assert_eq!(participation.status(4, 2), ParticipationStatus::Incomplete);
+
+    // A cycle through 4 alternatives is 4 of their 6 pairs
+    let reduced = Participation {
+        comparisons: 8,
+        ..participation
+    };
+    let design = ComparisonDesign::Cyclic {
+        comparisons_per_alternative: 2,
+    };
+    assert_eq!(reduced.status(4, 2), ParticipationStatus::Incomplete);
+    assert_eq!(
+        reduced.status_in(&design, 4, 2, 2),
+        ParticipationStatus::Complete
+    );
+}
+
+#[test]
+fn ranking_state_controls_what_experts_can_do() {
+    let server = start();
+    let admin = login(&server, "example@example.com");
+    let expert = login(&server, "expert@example.com");
+    let me: Expert = expert.get("/me").unwrap();
+    let mut ranking = admin.get::<Vec<Ranking>>("/all_rankings").unwrap()[0].clone();
+    let url = format!("/ranking/{}", ranking.ranking_id);
+    let mine = format!("/rankings/{}", me.expert_id);
+    assert_eq!(ranking.state, RankingState::Open);
+
+    ranking.state = RankingState::Archived;
+    assert!(admin.put(&url, &ranking).is_err());
+
+    ranking.state = RankingState::Closed;
+    admin.put(&url, &ranking).unwrap();
+    assert_eq!(expert.get::<Vec<Ranking>>(&mine).unwrap().len(), 1);
+    let alternatives: Vec<Alternative> = expert
+        .get(&format!("/all_alternatives/{}", ranking.ranking_id))
+        .unwrap();
+    let answer = ABInput {
+        alternative_a_id: alternatives[0].alternative_id,
+        alternative_b_id: alternatives[1].alternative_id,
+        winner_id: alternatives[0].alternative_id,
+        expert_id: 0,
+        criteria_id: 0,
+        comment: String::new(),
+        answered_at: None,
+    };
+    let error = expert
+        .post(&format!("/rankAB/{}", ranking.ranking_id), &answer)
+        .unwrap_err();
+    assert!(error.contains("409 Conflict"), "{}", error);
+
+    ranking.state = RankingState::Archived;
+    admin.put(&url, &ranking).unwrap();
+    assert!(expert.get::<Vec<Ranking>>(&mine).unwrap().is_empty());
+}
+
+#[test]
+fn rankings_without_a_state_are_open() {
+    let ranking: Ranking =
+        serde_json::from_str(r#"{"description":"Old","ranking_id":1,"expiring":0}"#).unwrap();
+    assert_eq!(ranking.state, RankingState::Open);
+    assert_eq!(Ranking::default().state, RankingState::Open);
+    let draft = Ranking::draft("New".to_string(), 0);
+    assert_eq!(draft.state, RankingState::Draft);
+}
+
+#[test]
+fn answers_after_the_deadline_are_rejected() {
+    let mut state = State::example();
+    state.rankings[0].expiring = chrono::Utc::now().timestamp() - 1;
+    let ranking_id = state.rankings[0].ranking_id;
+    let server = MockServer::start("127.0.0.1:0", state).unwrap();
+    let expert = login(&server, "expert@example.com");
+
+    let answer = ABInput {
+        alternative_a_id: 0,
+        alternative_b_id: 0,
+        winner_id: 0,
+        expert_id: 0,
+        criteria_id: 0,
+        comment: String::new(),
+        answered_at: None,
+    };
+    let error = expert
+        .post(&format!("/rankAB/{}", ranking_id), &answer)
+        .unwrap_err();
+    assert!(error.contains("expired"), "{}", error);
+}
+
+#[test]
+#[cfg(feature = "pairwise-criteria")]
+fn pairwise_criteria_weights_drive_the_results() {
+    use ranking_client::schema::{CriteriaWeighting, CriterionWeight};
+
+    let server = start();
+    let admin = login(&server, "example@example.com");
+    let expert = login(&server, "expert@example.com");
+    let ranking_id = admin.get::<Vec<Ranking>>("/all_rankings").unwrap()[0].ranking_id;
+    let criteria: Vec<Criterion> = admin.get(&format!("/criteria/{}", ranking_id)).unwrap();
+
+    let mut variables = Variables::default();
+    variables.criteria_weighting = CriteriaWeighting::Pairwise;
+    admin
+        .post(&format!("/create_variables/{}", ranking_id), &variables)
+        .unwrap();
+
+    let url = format!("/criteria_weights/{}", ranking_id);
+    let weight = |criteria_id, weight| CriterionWeight {
+        ranking_id,
+        expert_id: 0,
+        criteria_id,
+        weight,
+        comment: String::new(),
+    };
+    assert!(expert.post(&url, &vec![weight(-1, 1.0)]).is_err());
+    let mut weights = vec![
+        weight(criteria[0].criteria_id, 0.2),
+        weight(criteria[1].criteria_id, 0.8),
+    ];
+    weights[1].comment = "Looks matter most".to_string();
+    expert.post(&url, &weights).unwrap();
+    // Posting again replaces the previous weights
+    expert.post(&url, &weights).unwrap();
+    assert_eq!(server.state().criteria_weights.len(), 2);
+    let export: serde_json::Value = admin.get(&format!("/export_data/{}", ranking_id)).unwrap();
+    assert_eq!(
+        export["criteria_weights"][1]["comment"],
+        "Looks matter most"
+    );
+
+    admin
+        .post(&format!("/trigger_algorithm/{}", ranking_id), &())
+        .unwrap();
+    let results = server.state().results[&ranking_id].clone();
+    let stored = results["criteria_weights"][1]["weight"].as_f64().unwrap();
+    assert!((stored - 0.8).abs() < 1e-9, "{}", results);
+}
+
+#[test]
+fn sub_criteria_are_weighed_within_their_parent() {
+    let server = start();
+    let admin = login(&server, "example@example.com");
+    let ranking_id = admin.get::<Vec<Ranking>>("/all_rankings").unwrap()[0].ranking_id;
+    let criteria: Vec<Criterion> = admin.get(&format!("/criteria/{}", ranking_id)).unwrap();
+    let parent = &criteria[0];
+
+    let mut child = Criterion {
+        parent_id: Some(-1),
+        ..Criterion::default()
+    };
+    let create = format!("/create_criteria/{}", ranking_id);
+    assert!(admin.post(&create, &child).is_err());
+    child.parent_id = Some(parent.criteria_id);
+    admin.post(&create, &child).unwrap();
+    admin.post(&create, &child).unwrap();
+
+    let delete = format!("/criteria/{}/{}", ranking_id, parent.criteria_id);
+    assert!(admin.delete(&delete).is_err());
+
+    admin
+        .post(&format!("/trigger_algorithm/{}", ranking_id), &())
+        .unwrap();
+    let results = server.state().results[&ranking_id].clone();
+    let weights = results["criteria_weights"].as_array().unwrap();
+    let weight = |criteria_id: i64, field: &str| {
+        weights
+            .iter()
+            .find(|w| w["criteria_id"] == criteria_id)
+            .unwrap()[field]
+            .as_f64()
+            .unwrap()
+    };
+    // Nobody answered, so both levels are split evenly
+    let children: Vec<Criterion> = admin.get(&format!("/criteria/{}", ranking_id)).unwrap();
+    let child = children.last().unwrap();
+    assert!((weight(parent.criteria_id, "weight") - 0.5).abs() < 1e-9);
+    assert!((weight(child.criteria_id, "local_weight") - 0.5).abs() < 1e-9);
+    assert!((weight(child.criteria_id, "weight") - 0.25).abs() < 1e-9);
+    // Only the leaves are scored
+    let scored = results["scores"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|s| s["criteria_id"] == parent.criteria_id)
+        .count();
+    assert_eq!(scored, 0);
+}
@@ -0,0 +1,172 @@
+//! Order in which the questions of a ranking are put to the experts.
+
+use ranking_client::schedule::{schedule, Question};
+use ranking_client::schema::{Alternative, ComparisonDesign, Criterion, Presentation};
+
+fn alternatives() -> Vec<Alternative> {
+    (1..=5)
+        .map(|id| Alternative {
+            alternative_id: id,
+            name: format!("Alternative {}", id),
+            description: String::new(),
+            image_url: String::new(),
+            links: Vec::new(),
+            ..Alternative::default()
+        })
+        .collect()
+}
+
+fn criteria() -> Vec<Criterion> {
+    (1..=3)
+        .map(|id| Criterion {
+            criteria_id: id,
+            ranking_id: 1,
+            name: format!("Criterion {}", id),
+            description: String::new(),
+            parent_id: None,
+            ..Criterion::default()
+        })
+        .collect()
+}
+
+fn ids(questions: &[Question]) -> Vec<(i64, i64, i64)> {
+    questions
+        .iter()
+        .map(|q| {
+            (
+                q.criterion.criteria_id,
+                q.left.alternative_id,
+                q.right.alternative_id,
+            )
+        })
+        .collect()
+}
+
+fn randomized(seed: u64) -> Presentation {
+    Presentation {
+        random_order: true,
+        random_sides: true,
+        interleave_criteria: false,
+        seed,
+    }
+}
+
+#[test]
+fn default_order_asks_every_pair_per_criterion_in_turn() {
+    let questions = ids(&schedule(
+        &alternatives(),
+        &criteria(),
+        &ComparisonDesign::Complete,
+        &Presentation::default(),
+        7,
+    ));
+    assert_eq!(questions.len(), 10 * 3);
+    assert_eq!(&questions[..3], &[(1, 1, 2), (1, 1, 3), (1, 1, 4)]);
+    assert_eq!(questions[10], (2, 1, 2));
+}
+
+#[test]
+fn randomized_order_is_reproducible_from_the_seed_and_expert() {
+    let run = |seed, expert_id| {
+        ids(&schedule(
+            &alternatives(),
+            &criteria(),
+            &ComparisonDesign::Complete,
+            &randomized(seed),
+            expert_id,
+        ))
+    };
+    assert_eq!(run(42, 7), run(42, 7));
+    assert_ne!(run(42, 7), run(43, 7));
+    assert_ne!(run(42, 7), run(42, 8));
+
+    // Still every pair exactly once per criterion
+    let mut pairs: Vec<_> = run(42, 7)
+        .into_iter()
+        .map(|(c, a, b)| (c, a.min(b), a.max(b)))
+        .collect();
+    pairs.sort();
+    let mut expected = ids(&schedule(
+        &alternatives(),
+        &criteria(),
+        &ComparisonDesign::Complete,
+        &Presentation::default(),
+        7,
+    ));
+    expected.sort();
+    assert_eq!(pairs, expected);
+    assert!(run(42, 7).iter().any(|(_, a, b)| a > b));
+}
+
+#[test]
+fn interleaving_alternates_the_criteria() {
+    let presentation = Presentation {
+        interleave_criteria: true,
+        ..Presentation::default()
+    };
+    let criteria: Vec<i64> = schedule(
+        &alternatives(),
+        &criteria(),
+        &ComparisonDesign::Complete,
+        &presentation,
+        7,
+    )
+    .iter()
+    .map(|q| q.criterion.criteria_id)
+    .collect();
+    assert_eq!(&criteria[..6], &[1, 2, 3, 1, 2, 3]);
+}
+
+#[test]
+fn cyclic_design_compares_every_alternative_a_few_times_and_stays_connected() {
+    let design = ComparisonDesign::Cyclic {
+        comparisons_per_alternative: 4,
+    };
+    let pairs = design.pairs(20);
+    assert_eq!(pairs.len(), 40);
+    assert_eq!(design.pair_count(20), 40);
+    for alternative in 0..20 {
+        let count = pairs
+            .iter()
+            .filter(|(a, b)| *a == alternative || *b == alternative)
+            .count();
+        assert_eq!(count, 4);
+    }
+
+    // Every alternative is reachable from the first one
+    let mut reached = vec![0];
+    while let Some((a, b)) = pairs
+        .iter()
+        .find(|(a, b)| reached.contains(a) != reached.contains(b))
+    {
+        reached.push(if reached.contains(a) { *b } else { *a });
+    }
+    assert_eq!(reached.len(), 20);
+
+    // Asking for more than there are pairs falls back to every pair
+    assert_eq!(design.pairs(5), ComparisonDesign::Complete.pairs(5));
+}
+
+#[test]
+fn reduced_design_gives_experts_different_pairs() {
+    let design = ComparisonDesign::Cyclic {
+        comparisons_per_alternative: 2,
+    };
+    let pairs = |expert_id| {
+        let mut pairs: Vec<_> = ids(&schedule(
+            &alternatives(),
+            &criteria()[..1],
+            &design,
+            &randomized(42),
+            expert_id,
+        ))
+        .into_iter()
+        .map(|(_, a, b)| (a.min(b), a.max(b)))
+        .collect();
+        pairs.sort();
+        pairs
+    };
+    assert_eq!(pairs(7).len(), 5);
+    assert_eq!(pairs(7), pairs(7));
+    assert!((8..20).any(|expert_id| pairs(expert_id) != pairs(7)));
+}
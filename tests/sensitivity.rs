@@ -0,0 +1,90 @@
+//! Criteria weights moved around the results of the algorithm.
+
+use ranking_client::sensitivity::{set_weight, Model, Stretch};
+
+/// Batman is the clever one, Superman the strong one, Wonder Woman trails.
+fn heroes() -> Model {
+    Model {
+        alternatives: vec![
+            "Batman".to_string(),
+            "Superman".to_string(),
+            "Wonder Woman".to_string(),
+        ],
+        criteria: vec!["Strength".to_string(), "Intelligence".to_string()],
+        weights: vec![0.5, 0.5],
+        scores: vec![vec![0.2, 0.7], vec![0.7, 0.1], vec![0.1, 0.2]],
+    }
+}
+
+#[test]
+fn the_other_weights_keep_their_proportions() {
+    let weights = set_weight(&[0.5, 0.3, 0.2], 0, 0.75);
+    let expected = [0.75, 0.15, 0.1];
+    for (weight, expected) in weights.iter().zip(expected) {
+        assert!((weight - expected).abs() < 1e-9, "{:?}", weights);
+    }
+    // Nothing to keep in proportion, the rest is shared equally
+    assert_eq!(set_weight(&[1.0, 0.0, 0.0], 0, 0.5), vec![0.5, 0.25, 0.25]);
+}
+
+#[test]
+fn the_leader_changes_where_the_totals_cross() {
+    let model = heroes();
+    assert_eq!(model.leader(&model.weights), 0);
+
+    let stretches = model.leaders(&model.weights, 0);
+    assert_eq!(stretches.len(), 2);
+    assert_eq!((stretches[0].leader, stretches[1].leader), (0, 1));
+    assert!((stretches[0].to - 6.0 / 11.0).abs() < 1e-9);
+    assert_eq!(stretches[1].from, stretches[0].to);
+    assert_eq!(stretches[1].to, 1.0);
+
+    let stronger = set_weight(&model.weights, 0, 0.6);
+    assert_eq!(model.leader(&stronger), 1);
+}
+
+#[test]
+fn a_clear_winner_leads_at_any_weight() {
+    let mut model = heroes();
+    model.scores[0] = vec![0.8, 0.8];
+    assert_eq!(
+        model.leaders(&model.weights, 1),
+        vec![Stretch {
+            from: 0.0,
+            to: 1.0,
+            leader: 0
+        }]
+    );
+}
+
+#[test]
+fn exports_are_read_once_there_are_results() {
+    let export = r#"{
+        "alternatives": [{"alternative_id": 1, "name": "Batman"}, {"alternative_id": 2, "name": "Superman"}],
+        "criteria": [
+            {"criteria_id": 1, "name": "Power", "parent_id": null},
+            {"criteria_id": 2, "name": "Strength", "parent_id": 1},
+            {"criteria_id": 3, "name": "Speed", "parent_id": 1}
+        ],
+        "results": null
+    }"#;
+    assert_eq!(Model::from_export(export), None);
+
+    let results = r#""results": {
+        "criteria_weights": [
+            {"criteria_id": 1, "weight": 1.0},
+            {"criteria_id": 2, "weight": 0.75},
+            {"criteria_id": 3, "weight": 0.25}
+        ],
+        "scores": [
+            {"alternative_id": 1, "criteria_id": 2, "score": 0.4},
+            {"alternative_id": 2, "criteria_id": 2, "score": 0.6},
+            {"alternative_id": 1, "criteria_id": 3, "score": 0.5}
+        ]
+    }"#;
+    let model = Model::from_export(&export.replace(r#""results": null"#, results)).unwrap();
+    // Power has sub-criteria, the alternatives aren't scored on it
+    assert_eq!(model.criteria, vec!["Strength", "Speed"]);
+    assert_eq!(model.weights, vec![0.75, 0.25]);
+    assert_eq!(model.scores, vec![vec![0.4, 0.5], vec![0.6, 0.0]]);
+}
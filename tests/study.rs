@@ -0,0 +1,171 @@
+//! Study files and the changes planned from them.
+
+use ranking_client::schema::{Alternative, Criterion, Expert, Ranking, Scale};
+use ranking_client::study::{mismatches, parse_date, plan, Snapshot, Study};
+
+const PIZZAS: &str = r#"
+[ranking]
+description = "Pizzas"
+expires = "2030-01-31"
+
+[[alternatives]]
+name = "Margherita"
+
+[[alternatives]]
+name = "Hawaii"
+description = "With pineapple"
+
+[[criteria]]
+name = "Taste"
+
+[[criteria]]
+name = "Sweetness"
+parent = "Taste"
+
+[[scale]]
+description = "Important"
+value = 5.0
+"#;
+
+fn printed(study: &Study, server: &Snapshot) -> Vec<String> {
+    plan(study, server).iter().map(|c| c.to_string()).collect()
+}
+
+fn criterion(criteria_id: i64, name: &str, parent_id: Option<i64>) -> Criterion {
+    Criterion {
+        criteria_id,
+        ranking_id: 1,
+        name: name.to_string(),
+        description: String::new(),
+        parent_id,
+        ..Criterion::default()
+    }
+}
+
+/// What applying `PIZZAS` leaves on the server.
+fn pizzas_on_server() -> Snapshot {
+    let mut ranking = Ranking::default();
+    ranking.ranking_id = 1;
+    ranking.description = "Pizzas".to_string();
+    ranking.expiring = parse_date("2030-01-31").unwrap();
+    let alternative = |alternative_id, name: &str, description: &str| Alternative {
+        alternative_id,
+        name: name.to_string(),
+        description: description.to_string(),
+        ..Alternative::default()
+    };
+    Snapshot {
+        ranking: Some(ranking),
+        variables: None,
+        alternatives: vec![
+            alternative(1, "Margherita", ""),
+            alternative(2, "Hawaii", "With pineapple"),
+        ],
+        criteria: vec![
+            criterion(1, "Taste", None),
+            criterion(2, "Sweetness", Some(1)),
+        ],
+        scale: vec![Scale {
+            scale_id: 1,
+            description: "Important".to_string(),
+            value: 5.0,
+            ranking_id: 1,
+        }],
+        experts: Vec::new(),
+    }
+}
+
+#[test]
+fn a_new_study_is_created_parents_first() {
+    let study = Study::from_toml(PIZZAS).unwrap();
+    assert_eq!(
+        printed(&study, &Snapshot::default()),
+        vec![
+            "create ranking \"Pizzas\"",
+            "create criterion \"Taste\"",
+            "create criterion \"Sweetness\" under \"Taste\"",
+            "create alternative \"Margherita\"",
+            "create alternative \"Hawaii\"",
+            "create scale entry \"Important\"",
+        ]
+    );
+}
+
+#[test]
+fn nothing_changes_when_the_server_matches() {
+    let study = Study::from_toml(PIZZAS).unwrap();
+    assert!(plan(&study, &pizzas_on_server()).is_empty());
+}
+
+#[test]
+fn only_the_differences_are_planned() {
+    let edited = PIZZAS
+        .replace("2030-01-31", "2030-02-28")
+        .replace("With pineapple", "Controversial")
+        .replace("name = \"Margherita\"\n", "")
+        .replace("[[alternatives]]\n\n", "")
+        .replace("parent = \"Taste\"\n", "")
+        .replace("value = 5.0", "value = 7.0");
+    let study = Study::from_toml(&edited).unwrap();
+    assert_eq!(
+        printed(&study, &pizzas_on_server()),
+        vec![
+            "update ranking \"Pizzas\"",
+            // Criteria can't move, Sweetness is made again as a main criterion
+            "delete criterion \"Sweetness\"",
+            "delete alternative \"Margherita\"",
+            "delete scale entry \"Important\"",
+            "create criterion \"Sweetness\"",
+            "update alternative \"Hawaii\"",
+            "create scale entry \"Important\"",
+        ]
+    );
+}
+
+#[test]
+fn renamed_experts_are_reported_not_planned() {
+    let study = Study::from_toml(&format!(
+        "{}\n[[experts]]\nname = \"Ada\"\nemail = \"ada@example.com\"\nadmin = true\n",
+        PIZZAS
+    ))
+    .unwrap();
+    let mut server = pizzas_on_server();
+    server.experts.push(Expert {
+        expert_id: 1,
+        name: "Ada Lovelace".to_string(),
+        email: "ada@example.com".to_string(),
+        admin: false,
+    });
+    assert!(printed(&study, &server).is_empty());
+    assert_eq!(
+        mismatches(&study, &server),
+        vec![
+            "Expert ada@example.com is called \"Ada Lovelace\" on the server, not \"Ada\"",
+            "Expert ada@example.com has admin = false on the server, not true",
+        ]
+    );
+}
+
+#[test]
+fn broken_files_are_refused() {
+    let twice = format!("{}\n[[criteria]]\nname = \"Taste\"\n", PIZZAS);
+    assert!(Study::from_toml(&twice)
+        .err()
+        .unwrap()
+        .contains("listed twice"));
+
+    let orphan = PIZZAS.replace("parent = \"Taste\"", "parent = \"Price\"");
+    assert!(Study::from_toml(&orphan)
+        .err()
+        .unwrap()
+        .contains("isn't in the file"));
+
+    let cycle = PIZZAS.replace(
+        "name = \"Taste\"",
+        "name = \"Taste\"\nparent = \"Sweetness\"",
+    );
+    assert!(Study::from_toml(&cycle).err().unwrap().contains("cycle"));
+
+    let date = PIZZAS.replace("2030-01-31", "next year");
+    assert!(Study::from_toml(&date).is_err());
+}
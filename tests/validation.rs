@@ -0,0 +1,155 @@
+//! Checks the forms make before sending anything to the server.
+
+use ranking_client::schema::{
+    is_email, scale_warnings, Alternative, ComparisonDesign, Criterion, Expert, Link, Ranking,
+    Scale, Validate, ValidationContext, Variables,
+};
+
+fn fields<T: Validate>(value: &T, context: &ValidationContext<'_>) -> Vec<&'static str> {
+    value
+        .validate(context)
+        .into_iter()
+        .map(|error| error.field)
+        .collect()
+}
+
+#[test]
+fn names_have_to_be_filled_in_and_unique() {
+    let taken = ["Superman".to_string(), "Wonder Woman".to_string()];
+    let context = ValidationContext::now(&taken);
+    let mut alternative = Alternative::default();
+    assert!(fields(&alternative, &context).is_empty());
+
+    alternative.name = "  superman ".to_string();
+    let errors = alternative.validate(&context);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "Another alternative is called superman");
+
+    alternative.name = " ".to_string();
+    assert_eq!(fields(&alternative, &context), vec!["name"]);
+
+    let criterion = Criterion {
+        name: "Wonder Woman".to_string(),
+        ..Criterion::default()
+    };
+    assert_eq!(fields(&criterion, &context), vec!["name"]);
+}
+
+#[test]
+fn pictures_and_links_need_web_addresses() {
+    let context = ValidationContext::default();
+    let mut alternative = Alternative {
+        image_url: "data:image/png;base64,AAAA".to_string(),
+        ..Alternative::default()
+    };
+    assert!(fields(&alternative, &context).is_empty());
+
+    alternative.image_url = "C:\\batman.png".to_string();
+    alternative.links = vec![
+        Link {
+            title: "Wiki".to_string(),
+            url: "https://en.wikipedia.org/wiki/Batman".to_string(),
+        },
+        Link {
+            title: "Fans".to_string(),
+            url: "batman.example.com".to_string(),
+        },
+    ];
+    assert_eq!(fields(&alternative, &context), vec!["image_url", "links"]);
+}
+
+#[test]
+fn experts_need_an_email_address_that_is_not_taken() {
+    for email in ["ada@example.com", "a.b+c@mail.example.org"] {
+        assert!(is_email(email), "{}", email);
+    }
+    for email in [
+        "",
+        "ada",
+        "ada@",
+        "@example.com",
+        "ada@example",
+        "a da@example.com",
+    ] {
+        assert!(!is_email(email), "{}", email);
+    }
+
+    let taken = ["example@example.com".to_string()];
+    let context = ValidationContext::now(&taken);
+    let mut expert = Expert::default();
+    assert_eq!(fields(&expert, &context), vec!["email"]);
+    expert.email = "ada@example.com".to_string();
+    expert.name = String::new();
+    assert_eq!(fields(&expert, &context), vec!["name"]);
+}
+
+#[test]
+fn scale_values_are_positive() {
+    let context = ValidationContext::default();
+    let mut scale = Scale::default();
+    assert!(fields(&scale, &context).is_empty());
+    for value in [0.0, -1.0, f32::NAN] {
+        scale.value = value;
+        assert_eq!(fields(&scale, &context), vec!["value"]);
+    }
+}
+
+#[test]
+fn deadlines_cannot_be_in_the_past() {
+    let context = ValidationContext {
+        now: 1_000_000,
+        taken: &[],
+    };
+    // Never expires
+    let mut ranking = Ranking::new(1, "Heroes".to_string(), 0);
+    assert!(fields(&ranking, &context).is_empty());
+    ranking.expiring = 999_999;
+    assert_eq!(fields(&ranking, &context), vec!["expiring"]);
+    ranking.expiring = 1_000_001;
+    assert!(fields(&ranking, &context).is_empty());
+}
+
+#[test]
+fn variables_report_the_field_of_each_warning() {
+    let mut variables = Variables::default();
+    assert!(fields(&variables, &ValidationContext::default()).is_empty());
+    variables.design = ComparisonDesign::Cyclic {
+        comparisons_per_alternative: 4,
+    };
+    assert_eq!(
+        fields(&variables, &ValidationContext::default()),
+        vec!["design"]
+    );
+    assert_eq!(variables.warnings().len(), 1);
+}
+
+#[test]
+#[cfg(not(feature = "pairwise-criteria"))]
+fn pairwise_criteria_weighting_needs_the_feature() {
+    let mut variables = Variables::default();
+    variables.criteria_weighting = ranking_client::schema::CriteriaWeighting::Pairwise;
+    assert_eq!(
+        fields(&variables, &ValidationContext::default()),
+        vec!["criteria_weighting"]
+    );
+}
+
+#[test]
+fn scales_have_to_go_up_without_repeating() {
+    let entry = |description: &str, value| Scale {
+        description: description.to_string(),
+        value,
+        ..Scale::default()
+    };
+    let fine = [entry("Low", 1.0), entry("High", 3.0)];
+    assert!(scale_warnings(&fine).is_empty());
+
+    let swapped = [entry("High", 3.0), entry("Low", 1.0)];
+    assert_eq!(
+        scale_warnings(&swapped),
+        vec!["Low (1) should be worth more than High (3)"]
+    );
+
+    let repeated = [entry("Low", 1.0), entry("High", 3.0), entry("low ", 5.0)];
+    assert_eq!(scale_warnings(&repeated), vec!["Low is listed 2 times"]);
+}
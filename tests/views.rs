@@ -0,0 +1,940 @@
+//! Clicks through the views headlessly, see [`harness::Harness`].
+
+mod harness;
+
+use harness::Harness;
+use ranking_client::i18n::Language;
+use ranking_client::mock_server::State;
+use ranking_client::schema::{
+    ABInput, RankingMethod, RankingState, Scale, Translation, Translations, Variables, Weights,
+};
+use ranking_client::study::parse_date;
+use serde_json::json;
+
+fn log_in(harness: &mut Harness, email: &str) {
+    harness.replace_text("example@example.com", email);
+    harness.click("Login");
+    harness.wait_for("Available rankings");
+}
+
+#[test]
+fn admin_sees_admin_controls() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "example@example.com");
+
+    assert!(harness.has("Logged in as admin: "));
+    assert!(harness.has("Superheroes ranking"));
+    assert!(harness.has("Create new ranking"));
+    assert!(harness.has("Edit"));
+}
+
+#[test]
+fn expert_does_not_see_admin_controls() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "expert@example.com");
+
+    assert!(harness.has("Logged in as: "));
+    assert!(harness.has("Superheroes ranking"));
+    assert!(!harness.has("Create new ranking"));
+    assert!(!harness.has("Edit"));
+}
+
+#[test]
+fn clicking_a_ranking_shows_the_first_pair() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "expert@example.com");
+
+    harness.click("Superheroes ranking");
+    harness.wait_for("Which is better based on: Strength");
+    assert!(harness.has("Batman"));
+    assert!(harness.has("Superman"));
+    assert!(!harness.has("Wonder Woman"));
+}
+
+#[test]
+fn answering_a_pair_posts_it_and_moves_on() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "expert@example.com");
+    harness.click("Superheroes ranking");
+
+    harness.click("Superman");
+    harness.wait_for("Wonder Woman");
+
+    let state = harness.server.state();
+    let comparisons = state.comparisons.values().next().unwrap();
+    assert_eq!(comparisons.len(), 1);
+    let superman = &state.alternatives.values().next().unwrap()[1];
+    assert_eq!(comparisons[0].winner_id, superman.alternative_id);
+}
+
+#[test]
+fn admin_can_open_the_ranking_editor() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "example@example.com");
+
+    harness.click("Edit");
+    harness.wait_for("Parameters");
+    harness.click("Alternatives");
+    harness.wait_for("Wonder Woman");
+    harness.click("Criteria");
+    harness.wait_for("Strength");
+    harness.click("Scale");
+    harness.run_until("the scale entries", |harness| {
+        harness.texts().contains(&"Very important".to_string())
+    });
+
+    harness.click("Back to ranking_list");
+    harness.wait_for("Available rankings");
+}
+
+/// Creates the variables of the example ranking from the Variables tab and
+/// waits for the server to have them with `method`.
+fn create_variables(harness: &mut Harness, method: RankingMethod) {
+    harness.click("Variables");
+    harness.click("Create");
+    harness.run_until("the variables to be created", |harness| {
+        harness
+            .server
+            .state()
+            .variables
+            .values()
+            .any(|variables| variables.ranking_method == method)
+    });
+}
+
+#[test]
+fn going_back_to_the_editor_keeps_unsaved_variables() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    harness.click("Variables");
+    harness.click("GMM");
+
+    harness.click("Parameters");
+    harness.click("Participation");
+    harness.click("Back to ranking editing");
+    create_variables(&mut harness, RankingMethod::Gmm);
+}
+
+#[test]
+fn breadcrumbs_and_back_forward_move_through_the_history() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    harness.click("Participation");
+    harness.wait_for("Back to ranking editing");
+    assert!(harness.has("Rankings"));
+    assert!(harness.has("Edit Superheroes ranking"));
+
+    harness.click("Edit Superheroes ranking");
+    harness.wait_for("Parameters");
+
+    harness.click("➡");
+    harness.wait_for("Back to ranking editing");
+
+    harness.click("Rankings");
+    harness.wait_for("Available rankings");
+    assert!(!harness.has("Edit Superheroes ranking"));
+}
+
+#[test]
+fn creation_forms_open_over_the_section() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    harness.click("Alternatives");
+    harness.wait_for("Wonder Woman");
+
+    harness.click("Create new alternative");
+    harness.wait_for("Submit");
+    assert!(harness.has("Wonder Woman"));
+    harness.replace_text("Batman", "Aquaman");
+    harness.click("Submit");
+    harness.run_until("the form to close", |harness| !harness.has("Submit"));
+    // Still on the same tab, with the new alternative in it
+    harness.wait_for("Aquaman");
+    assert!(harness.has("Wonder Woman"));
+
+    // Other tabs keep their unsaved changes
+    harness.click("Variables");
+    harness.click("GMM");
+    harness.click("Alternatives");
+    harness.click("Create new alternative");
+    harness.replace_text("Batman", "  ");
+    harness.wait_for("Can't be empty");
+    harness.click("Submit");
+    harness.click("Cancel");
+    harness.run_until("the form to close", |harness| !harness.has("Submit"));
+    let alternatives = harness
+        .server
+        .state()
+        .alternatives
+        .values()
+        .flatten()
+        .count();
+    assert_eq!(alternatives, 4);
+    create_variables(&mut harness, RankingMethod::Gmm);
+}
+
+#[test]
+fn the_side_panel_switches_to_another_ranking() {
+    let mut state = State::example();
+    state.add_ranking("Villains ranking", 0);
+    let mut harness = Harness::with_state(state);
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    harness.wait_for("Edit Villains ranking");
+
+    harness.click("Superheroes ranking");
+    harness.wait_for("Edit Superheroes ranking");
+    harness.click("Alternatives");
+    harness.wait_for("Wonder Woman");
+}
+
+#[test]
+fn a_scale_preset_keeps_the_entries_it_shares_with_the_scale() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    harness.click("Scale");
+    let kept: Vec<i64> = harness
+        .server
+        .state()
+        .scales
+        .iter()
+        .filter(|s| s.description != "Important")
+        .map(|s| s.scale_id)
+        .collect();
+
+    harness.click("Likert 1–5");
+    harness.click("Save scale");
+    // The old entries are deleted once the new ones are created
+    harness.run_until("the preset to be saved", |harness| {
+        let state = harness.server.state();
+        let old = |s: &Scale| s.description == "Important" && s.value == 3.0;
+        let deleted = !state.scales.iter().any(old);
+        state.scales.len() == 5 && deleted
+    });
+
+    let state = harness.server.state();
+    let mut scale: Vec<_> = state
+        .scales
+        .iter()
+        .map(|s| (s.description.as_str(), s.value))
+        .collect();
+    scale.sort_by(|a, b| a.1.total_cmp(&b.1));
+    assert_eq!(
+        scale,
+        vec![
+            ("Not important", 1.0),
+            ("Slightly important", 2.0),
+            ("Moderately important", 3.0),
+            ("Important", 4.0),
+            ("Very important", 5.0),
+        ]
+    );
+    assert!(kept
+        .iter()
+        .all(|id| state.scales.iter().any(|s| s.scale_id == *id)));
+}
+
+#[test]
+fn moving_a_scale_entry_keeps_the_values_in_order() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    harness.click("Scale");
+
+    harness.click_described("Move Very important up");
+    harness.click("Save scale");
+    harness.run_until("the moved entries to be saved", |harness| {
+        let state = harness.server.state();
+        let value = |description: &str| {
+            state
+                .scales
+                .iter()
+                .find(|s| s.description == description)
+                .map(|s| s.value)
+        };
+        // The old entries are gone once there are only three
+        state.scales.len() == 3
+            && value("Very important") == Some(3.0)
+            && value("Important") == Some(5.0)
+    });
+}
+
+#[test]
+fn a_failed_scale_save_deletes_nothing_and_can_be_retried() {
+    let mut state = State::example();
+    state.failing.push("POST create_scale".to_string());
+    let scale = state.scales.clone();
+    let mut harness = Harness::with_state(state);
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    harness.click("Scale");
+
+    harness.click_described("Move Very important up");
+    harness.click("Save scale");
+    harness.wait_for("Failed, server responded with: 500 Internal Server Error");
+    for _ in 0..10 {
+        harness.step();
+    }
+    let ids = |scale: &[Scale]| scale.iter().map(|s| s.scale_id).collect::<Vec<_>>();
+    assert_eq!(ids(&harness.server.state().scales), ids(&scale));
+
+    // The draft is still there to save again
+    harness.server.state().failing.clear();
+    harness.click("Save scale");
+    harness.run_until("the moved entries to be saved", |harness| {
+        let state = harness.server.state();
+        let value = |description: &str| {
+            state
+                .scales
+                .iter()
+                .find(|s| s.description == description)
+                .map(|s| s.value)
+        };
+        state.scales.len() == 3
+            && value("Very important") == Some(3.0)
+            && value("Important") == Some(5.0)
+    });
+}
+
+#[test]
+fn the_scale_is_locked_once_experts_used_it() {
+    let mut state = State::example();
+    let expert = &state.experts[1];
+    state.weights.push(Weights {
+        weights_id: 1,
+        ranking_id: state.rankings[0].ranking_id,
+        expert_id: expert.expert_id,
+        criteria_id: state.criteria[0].criteria_id,
+        scale_id: state.scales[1].scale_id,
+        comment: String::new(),
+        answered_at: None,
+    });
+    let scale = state.scales.clone();
+    let mut harness = Harness::with_state(state);
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    harness.click("Scale");
+
+    harness.click("Likert 1–5");
+    harness.wait_for(
+        "Experts have weighted criteria with this scale already, it can't be changed any more",
+    );
+    harness.click("Save scale");
+    for _ in 0..10 {
+        harness.step();
+    }
+    let ids = |scale: &[Scale]| scale.iter().map(|s| s.scale_id).collect::<Vec<_>>();
+    assert_eq!(ids(&harness.server.state().scales), ids(&scale));
+}
+
+#[test]
+fn admin_can_assign_an_expert_to_another_ranking_and_invite_them() {
+    let mut state = State::example();
+    let ranking_id = state.add_ranking("Villains ranking", 0);
+    let mut harness = Harness::with_state(state);
+    log_in(&mut harness, "example@example.com");
+
+    harness.click("Expert directory");
+    harness.wait_for("Bill Nye");
+    harness.click("Bill Nye");
+    harness.click("Villains ranking");
+    harness.click("Assign");
+    harness.wait_for("Superheroes ranking, Villains ranking");
+
+    let expert_id = harness.server.state().experts[1].expert_id;
+    assert!(harness.server.state().members[&ranking_id].contains(&expert_id));
+    assert!(harness.has("Copy invitation"));
+    let link = format!("{}/#/rank/{}", harness.server.base_url(), ranking_id);
+    assert!(harness.texts().iter().any(|text| text.contains(&link)));
+
+    // The web app may be served somewhere else than the backend
+    harness.fill_in("Web app address: ", "https://rankings.example.com");
+    let link = format!("https://rankings.example.com/#/rank/{}", ranking_id);
+    harness.run_until("the link to the web app", |harness| {
+        harness.texts().iter().any(|text| text.contains(&link))
+    });
+}
+
+#[test]
+fn participation_can_be_filtered_by_status() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "expert@example.com");
+    harness.click("Superheroes ranking");
+    harness.click("Superman");
+    harness.wait_for("Wonder Woman");
+
+    let mut harness = Harness::with_state(std::mem::take(&mut *harness.server.state()));
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    harness.click("Participation");
+    harness.wait_for("0 of 2 experts have answered everything");
+    assert!(harness.has("Bill Nye"));
+    assert!(harness.has("Admin"));
+
+    harness.click("Not started");
+    harness.run_until("the filter", |harness| !harness.has("Bill Nye"));
+    assert!(harness.has("Admin"));
+}
+
+#[test]
+fn participation_shows_when_experts_last_answered() {
+    let mut state = State::example();
+    let ranking_id = state.rankings[0].ranking_id;
+    let alternatives = state.alternatives[&ranking_id].clone();
+    let expert = state.experts[1].clone();
+    let answer = |criteria_id, answered_at| ABInput {
+        alternative_a_id: alternatives[0].alternative_id,
+        alternative_b_id: alternatives[1].alternative_id,
+        winner_id: alternatives[0].alternative_id,
+        expert_id: expert.expert_id,
+        criteria_id,
+        comment: String::new(),
+        answered_at: Some(answered_at),
+    };
+    let (strength, intelligence) = (state.criteria[0].criteria_id, state.criteria[1].criteria_id);
+    let comparisons = state.comparisons.entry(ranking_id).or_default();
+    // 2023-11-14 22:13 UTC, then a day earlier
+    comparisons.push(answer(strength, 1_700_000_000));
+    comparisons.push(answer(intelligence, 1_700_000_000 - 24 * 60 * 60));
+    let mut harness = Harness::with_state(state);
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    harness.click("Participation");
+
+    harness.wait_for("2023-11-14 22:13");
+    assert!(!harness.has("2023-11-13 22:13"));
+    // The admin hasn't answered anything
+    assert!(harness.has("Never"));
+}
+
+#[test]
+fn algorithm_is_not_triggered_until_the_ranking_is_ready() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    harness.wait_for("The ranking has no variables yet");
+
+    harness.click("Trigger algorithm");
+    harness.step();
+    assert!(!harness.has("Run the algorithm?"));
+}
+
+#[test]
+fn triggered_algorithm_links_to_the_results() {
+    let mut state = State::example();
+    let ranking_id = state.rankings[0].ranking_id;
+    let mut variables = Variables::default();
+    variables.completness_required = false;
+    state.variables.insert(ranking_id, variables);
+    let mut harness = Harness::with_state(state);
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    // Before the editor shows there's nothing "Still downloading" either
+    harness.wait_for("Parameters");
+    harness.run_until("the ranking to load", |harness| {
+        !harness
+            .texts()
+            .iter()
+            .any(|text| text.starts_with("Still downloading"))
+    });
+
+    harness.click("Trigger algorithm");
+    harness.click("Run");
+    harness.click("Show results");
+    harness.wait_for(&format!("Results of ranking {}", ranking_id));
+    assert!(harness.server.state().results.contains_key(&ranking_id));
+}
+
+#[test]
+fn sensitivity_analysis_shows_where_the_winner_changes() {
+    let mut state = State::example();
+    let ranking_id = state.rankings[0].ranking_id;
+    let alternatives = state.alternatives[&ranking_id].clone();
+    let (strength, intelligence) = (state.criteria[0].criteria_id, state.criteria[1].criteria_id);
+    // Batman is the clever one, Superman the strong one
+    let mut scores = Vec::new();
+    for (alternative, on_strength, on_intelligence) in [(0, 0.2, 0.7), (1, 0.7, 0.1), (2, 0.1, 0.2)]
+    {
+        let alternative_id = alternatives[alternative].alternative_id;
+        scores.push(json!({"alternative_id": alternative_id, "criteria_id": strength, "score": on_strength}));
+        scores.push(json!({"alternative_id": alternative_id, "criteria_id": intelligence, "score": on_intelligence}));
+    }
+    state.results.insert(
+        ranking_id,
+        json!({
+            "criteria_weights": [
+                {"criteria_id": strength, "weight": 0.5, "local_weight": 0.5},
+                {"criteria_id": intelligence, "weight": 0.5, "local_weight": 0.5},
+            ],
+            "scores": scores,
+        }),
+    );
+    let mut harness = Harness::with_state(state);
+    log_in(&mut harness, "example@example.com");
+
+    harness.click("Download results");
+    harness.wait_for("Criteria weights");
+    harness.click("Sensitivity analysis");
+    harness.wait_for("0–54.5%: Batman, 54.5–100%: Superman");
+    assert!(harness.has("0–45.5%: Superman, 45.5–100%: Batman"));
+    assert!(harness.has("45%"));
+
+    harness.increment("Strength");
+    let intelligence = harness.slider_value("Intelligence").unwrap();
+    assert!((intelligence - 49.9).abs() < 1e-9, "{}", intelligence);
+}
+
+#[test]
+fn admin_can_close_a_ranking() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "example@example.com");
+    harness.click("Closed");
+    assert!(!harness.has("Superheroes ranking"));
+    harness.click("All");
+
+    harness.click("Close");
+    harness.click("Closed");
+    harness.wait_for("Superheroes ranking");
+    assert!(harness.has("Reopen"));
+    assert_eq!(
+        harness.server.state().rankings[0].state,
+        RankingState::Closed
+    );
+}
+
+#[test]
+fn experts_cannot_answer_closed_rankings() {
+    let mut state = State::example();
+    state.rankings[0].state = RankingState::Closed;
+    let mut harness = Harness::with_state(state);
+    log_in(&mut harness, "expert@example.com");
+
+    harness.click("Superheroes ranking");
+    harness.wait_for("This ranking is closed and no longer accepts answers.");
+    assert!(!harness.has("Batman"));
+}
+
+#[test]
+fn expired_rankings_are_read_only() {
+    let mut state = State::example();
+    state.rankings[0].expiring = chrono::Utc::now().timestamp() - 60;
+    let mut harness = Harness::with_state(state);
+    log_in(&mut harness, "expert@example.com");
+
+    harness.click("Superheroes ranking");
+    harness.run_until("the closed screen", |harness| {
+        harness
+            .texts()
+            .iter()
+            .any(|text| text.starts_with("This ranking closed on"))
+    });
+    assert!(!harness.has("Batman"));
+}
+
+#[test]
+fn experts_are_warned_just_before_the_deadline() {
+    let mut state = State::example();
+    state.rankings[0].expiring = chrono::Utc::now().timestamp() + 120;
+    let mut harness = Harness::with_state(state);
+    log_in(&mut harness, "expert@example.com");
+
+    harness.click("Superheroes ranking");
+    harness.wait_for("Batman");
+    assert!(harness.has("Answers sent after the deadline are rejected, finish up soon."));
+}
+
+#[test]
+fn admin_can_add_a_sub_criterion() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    harness.click("Criteria");
+    harness.click_described("Add a sub-criterion to Strength");
+    // Criteria are told apart by name, the default one is taken
+    harness.wait_for("Another criterion is called Strength");
+    harness.replace_text("Strength", "Lifting");
+    harness.click("Submit");
+    harness.run_until("the form to close", |harness| !harness.has("Submit"));
+
+    let state = harness.server.state();
+    let strength = state
+        .criteria
+        .iter()
+        .find(|c| c.name == "Strength")
+        .unwrap();
+    let children = state
+        .criteria
+        .iter()
+        .filter(|c| c.parent_id == Some(strength.criteria_id))
+        .count();
+    assert_eq!(children, 1);
+}
+
+#[test]
+fn experts_can_justify_their_answers() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "expert@example.com");
+    harness.click("Superheroes ranking");
+    harness.wait_for("Which is better based on: Strength");
+
+    harness.replace_text("", "He can fly");
+    harness.click("Superman");
+    harness.wait_for("Wonder Woman");
+
+    let state = harness.server.state();
+    let comparisons = state.comparisons.values().next().unwrap();
+    assert_eq!(comparisons[0].comment, "He can fly");
+}
+
+#[test]
+fn admin_browses_comments_by_pair_and_by_criterion() {
+    let mut state = State::example();
+    let ranking_id = state.rankings[0].ranking_id;
+    let alternatives = state.alternatives[&ranking_id].clone();
+    let criterion = state.criteria[0].clone();
+    let expert = state.experts[1].clone();
+    state
+        .comparisons
+        .entry(ranking_id)
+        .or_default()
+        .push(ABInput {
+            alternative_a_id: alternatives[1].alternative_id,
+            alternative_b_id: alternatives[0].alternative_id,
+            winner_id: alternatives[1].alternative_id,
+            expert_id: expert.expert_id,
+            criteria_id: criterion.criteria_id,
+            comment: "He can fly".to_string(),
+            answered_at: None,
+        });
+    let mut harness = Harness::with_state(state);
+    log_in(&mut harness, "example@example.com");
+
+    harness.click("Download results");
+    harness.wait_for("Batman vs Superman (1)");
+    let comment = format!("{} (Strength: Superman is better): He can fly", expert.name);
+    assert!(harness.has(&comment));
+
+    harness.click("By criterion");
+    harness.wait_for("Strength (1)");
+    let comment = format!(
+        "{} (Batman vs Superman: Superman is better): He can fly",
+        expert.name
+    );
+    assert!(harness.has(&comment));
+}
+
+#[test]
+fn pairs_can_be_answered_with_the_keyboard() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "expert@example.com");
+    harness.click("Superheroes ranking");
+    harness.wait_for("Which is better based on: Strength");
+    let alternatives = harness
+        .server
+        .state()
+        .alternatives
+        .values()
+        .next()
+        .unwrap()
+        .clone();
+    let (batman, superman) = (&alternatives[0], &alternatives[1]);
+
+    // Typing into the comment doesn't pick anyone
+    harness.replace_text("", "b");
+    harness.press(egui::Key::A);
+    assert!(harness.server.state().comparisons.is_empty());
+    harness.press(egui::Key::Escape);
+
+    harness.press(egui::Key::ArrowRight);
+    harness.wait_for("Wonder Woman");
+    harness.press(egui::Key::S);
+    harness.run_until("the skipped pair to go", |harness| !harness.has("Batman"));
+    harness.press(egui::Key::Backspace);
+    harness.wait_for("Batman");
+    // There is no tie between alternatives
+    let answers = |harness: &Harness| {
+        harness
+            .server
+            .state()
+            .comparisons
+            .values()
+            .next()
+            .unwrap()
+            .len()
+    };
+    harness.press(egui::Key::E);
+    assert_eq!(answers(&harness), 1);
+    harness.press(egui::Key::A);
+    harness.run_until("the second answer to be posted", |harness| {
+        answers(harness) == 2
+    });
+
+    let state = harness.server.state();
+    let comparisons = state.comparisons.values().next().unwrap();
+    assert_eq!(comparisons[0].winner_id, superman.alternative_id);
+    assert_eq!(comparisons[1].alternative_a_id, batman.alternative_id);
+    assert_eq!(comparisons[1].winner_id, batman.alternative_id);
+}
+
+#[test]
+fn repeated_key_presses_answer_a_pair_once() {
+    let mut state = State::example();
+    state.keep_repeated_answers = true;
+    let mut harness = Harness::with_state(state);
+    log_in(&mut harness, "expert@example.com");
+    harness.click("Superheroes ranking");
+    harness.wait_for("Which is better based on: Strength");
+
+    // A held key and a double click while the answer is on its way
+    for _ in 0..5 {
+        harness.press(egui::Key::ArrowRight);
+    }
+    harness.click_described("Batman is better based on Strength");
+    harness.wait_for("Wonder Woman");
+    for _ in 0..20 {
+        harness.step();
+    }
+
+    let state = harness.server.state();
+    let comparisons = state.comparisons.values().next().unwrap();
+    let mut pairs: Vec<_> = comparisons
+        .iter()
+        .map(|c| (c.criteria_id, c.alternative_a_id, c.alternative_b_id))
+        .collect();
+    let answered = pairs.len();
+    pairs.dedup();
+    assert_eq!(pairs.len(), answered, "{:#?}", comparisons);
+}
+
+#[test]
+fn criteria_can_be_rated_with_the_keyboard() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "expert@example.com");
+    harness.click("Superheroes ranking");
+    harness.wait_for("Which is better based on: Strength");
+    for _ in 0..6 {
+        harness.press(egui::Key::S);
+    }
+    harness.wait_for("How important is this criterion?");
+
+    harness.press(egui::Key::Num3);
+    harness.press(egui::Key::Enter);
+    harness.run_until("the weight to be posted", |harness| {
+        !harness.server.state().weights.is_empty()
+    });
+
+    let state = harness.server.state();
+    let scale: Vec<_> = state.scales.iter().map(|s| s.scale_id).collect();
+    assert!(state.comparisons.is_empty());
+    assert_eq!(state.weights[0].scale_id, scale[2]);
+}
+
+#[test]
+fn experts_get_the_scale_in_value_order() {
+    let mut state = State::example();
+    state.scales.reverse();
+    let mut harness = Harness::with_state(state);
+    log_in(&mut harness, "expert@example.com");
+    harness.click("Superheroes ranking");
+    harness.wait_for("Which is better based on: Strength");
+    for _ in 0..6 {
+        harness.press(egui::Key::S);
+    }
+    harness.wait_for("How important is this criterion?");
+
+    harness.press(egui::Key::Num1);
+    harness.press(egui::Key::Enter);
+    harness.run_until("the weight to be posted", |harness| {
+        !harness.server.state().weights.is_empty()
+    });
+
+    let state = harness.server.state();
+    let lowest = state.scales.iter().find(|s| s.value == 1.0).unwrap();
+    assert_eq!(state.weights[0].scale_id, lowest.scale_id);
+}
+
+#[test]
+fn screen_readers_get_labelled_fields_and_described_choices() {
+    let mut harness = Harness::new();
+    assert_eq!(
+        harness.labels_of_text_field("example@example.com"),
+        vec!["Email: "]
+    );
+    log_in(&mut harness, "expert@example.com");
+    harness.click("Superheroes ranking");
+    harness.wait_for("Which is better based on: Strength");
+
+    assert_eq!(
+        harness.description("Superman").as_deref(),
+        Some("Superman is better based on Strength")
+    );
+    assert_eq!(
+        harness.labels_of_text_field(""),
+        vec!["Comment (optional):"]
+    );
+}
+
+#[test]
+fn high_contrast_and_text_size_can_be_changed() {
+    let mut harness = Harness::new();
+    let body_size = harness.style().text_styles[&egui::TextStyle::Body].size;
+
+    harness.click("High contrast");
+    harness.step();
+    assert_eq!(harness.style().visuals.panel_fill, egui::Color32::BLACK);
+
+    harness.increment("Text size");
+    harness.step();
+    let larger = harness.style().text_styles[&egui::TextStyle::Body].size;
+    assert_eq!(larger, body_size * 1.25);
+}
+
+#[test]
+fn the_interface_can_be_switched_to_polish() {
+    let mut harness = Harness::new();
+    harness.click("Language");
+    harness.click("Polski");
+    harness.wait_for("Zaloguj");
+
+    harness.click("Zaloguj");
+    harness.wait_for("Dostępne rankingi");
+    assert!(harness.has("Zalogowano jako administrator: "));
+    assert!(harness.has("Utwórz nowy ranking"));
+
+    // So are the invitations
+    harness.click("Katalog ekspertów");
+    harness.click("Bill Nye");
+    harness.click("Superheroes ranking");
+    harness.run_until("the invitation", |harness| {
+        harness
+            .texts()
+            .iter()
+            .any(|text| text.contains("zapraszamy do udziału w rankingu „Superheroes ranking”"))
+    });
+    assert!(harness.has("Wyślij e-mailem"));
+    harness.click("Powrót do listy rankingów");
+
+    // The editing screens are translated too
+    harness.click("Edytuj");
+    harness.click("Kryteria");
+    harness.wait_for("Usuń pozycję");
+    assert!(harness.has("Utwórz nowe"));
+    harness.click("Skala");
+    harness.wait_for("Zrównoważona");
+    harness.click_described("Przesuń w górę: Very important");
+}
+
+#[test]
+#[cfg(feature = "content-updates")]
+fn admins_translate_alternatives_side_by_side() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    harness.click("Translations");
+    harness.wait_for("Wonder Woman");
+
+    harness.fill_in("Wonder Woman", "Cudowna Kobieta");
+    harness.click("Save translations");
+    harness.run_until("the translation to be saved", |harness| {
+        harness
+            .server
+            .state()
+            .alternatives
+            .values()
+            .flatten()
+            .any(|alternative| alternative.localized(Language::Polish).name == "Cudowna Kobieta")
+    });
+}
+
+#[test]
+fn experts_see_the_content_in_their_language() {
+    let mut state = State::example();
+    let polish = |name: &str| {
+        Translations::from([(
+            Language::Polish.code().to_string(),
+            Translation {
+                name: name.to_string(),
+                description: String::new(),
+            },
+        )])
+    };
+    state.criteria[0].translations = polish("Siła");
+    for alternative in state.alternatives.values_mut().flatten() {
+        if alternative.name == "Superman" {
+            alternative.translations = polish("Człowiek ze stali");
+        }
+    }
+    let mut harness = Harness::with_state(state);
+    log_in(&mut harness, "expert@example.com");
+    harness.click("Language");
+    harness.click("Polski");
+    harness.click("Superheroes ranking");
+
+    harness.wait_for("Który jest lepszy pod względem: Siła");
+    assert!(harness.has("Człowiek ze stali"));
+    // The description isn't translated, so the default one is shown
+    assert!(harness.has("Faster than a speeding bullet"));
+}
+
+#[test]
+fn new_rankings_get_the_deadline_typed_in() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "example@example.com");
+    harness.click("Create new ranking");
+    harness.fill_in("Description: ", "Villains ranking");
+    harness.fill_in("Expiring: ", "2099-12-31");
+    harness.click("Submit");
+    harness.run_until("the new ranking", |harness| {
+        harness.server.state().rankings.len() == 2
+    });
+
+    let ranking = harness.server.state().rankings[1].clone();
+    assert_eq!(ranking.description, "Villains ranking");
+    assert_eq!(ranking.expiring, parse_date("2099-12-31").unwrap());
+    // The editor it moves on to shows the same date
+    harness.wait_for("Parameters");
+    assert!(harness.texts().contains(&"2099-12-31".to_string()));
+}
+
+#[test]
+fn closing_a_ranking_leaves_unsaved_edits_out() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    harness.fill_in("Description: ", "Villains ranking");
+    harness.click("Close");
+    harness.run_until("the state change", |harness| {
+        harness.server.state().rankings[0].state == RankingState::Closed
+    });
+
+    harness.wait_for("Closed");
+    let state = harness.server.state();
+    assert_eq!(state.rankings[0].description, "Superheroes ranking");
+}
+
+#[test]
+fn rejected_state_changes_keep_the_state() {
+    let mut harness = Harness::new();
+    log_in(&mut harness, "example@example.com");
+    harness.click("Edit");
+    harness.wait_for("Completeness is required, but 2 experts have not answered everything");
+    harness.server.state().rankings[0].state = RankingState::Draft;
+
+    harness.click("Close");
+    harness.run_until("the server's answer", |harness| {
+        harness
+            .texts()
+            .iter()
+            .any(|text| text.contains("Cannot move a ranking from Draft to Closed"))
+    });
+    assert!(harness.has("Open"));
+    assert!(!harness.has("Closed"));
+    assert_eq!(
+        harness.server.state().rankings[0].state,
+        RankingState::Draft
+    );
+}